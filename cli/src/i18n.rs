@@ -0,0 +1,142 @@
+//! Texty príkazového riadku v angličtine a slovenčine.
+//!
+//! Jazyk sa vyberá v nastaveniach, pozri [`financial_lib::i18n`]. Popisy
+//! akcií v histórii sú v knižnici, zdieľa ich TUI aj server. Pomoc
+//! prepínačov od `clap` sa neprekladá, vypíše sa skôr, než sa načítajú
+//! nastavenia.
+use std::fmt;
+
+use financial_lib::i18n::{Catalogue, Table};
+
+/// Texty príkazového riadku.
+pub static TEXTS: Catalogue = Catalogue { en: EN, sk: SK };
+
+/// Vráti text v jazyku z nastavení.
+pub fn tr(key: &str) -> &'static str {
+    TEXTS.text(key)
+}
+
+/// Vráti text s doplnenými hodnotami namiesto `{}`.
+pub fn trf(key: &str, args: &[&dyn fmt::Display]) -> String {
+    TEXTS.format(key, args)
+}
+
+const EN: Table = &[
+    // chyby
+    ("error.query", "query {}"),
+    ("error.unknown_file_format", "unknown file format, use --format"),
+    ("error.invalid_amount", "invalid amount {}, expected a positive number"),
+    ("error.invalid_currency", "invalid currency '{}'"),
+    ("error.invalid_date", "invalid date '{}', expected {}"),
+    ("error.empty_account", "account name must not be empty"),
+    ("error.empty_asset", "asset name must not be empty"),
+    ("error.empty_loan", "loan name must not be empty"),
+    ("error.invalid_principal", "invalid principal {}"),
+    ("error.invalid_rate", "invalid rate {}"),
+    ("error.invalid_term", "term must be at least one payment"),
+    ("error.invalid_tag", "invalid tag '{}'"),
+    ("error.record_not_found", "record {} not found"),
+    ("error.not_in_trash", "record {} is not in the trash"),
+    ("error.asset_not_found", "asset {} not found"),
+    // výsledky príkazov
+    ("done.purged", "record {} permanently deleted"),
+    ("done.trashed", "record {} moved to the trash"),
+    ("done.asset_added", "asset {} added"),
+    ("done.liability_added", "liability {} added"),
+    ("done.valued", "asset {} valued"),
+    ("done.loan_added", "loan {} added, payment {}"),
+    ("done.bought", "bought {} {} at {}"),
+    ("done.sold", "sold {} {} at {}"),
+    ("done.imported_records", "imported {} records"),
+    ("done.imported_prices", "imported {} prices"),
+    ("done.imported_rates", "imported {} rates"),
+    // stĺpce tabuliek
+    ("column.period", "Period"),
+    ("column.group", "Group"),
+    ("column.income", "Income"),
+    ("column.expense", "Expense"),
+    ("column.invested", "Invested"),
+    ("column.net", "Net"),
+    ("column.count", "Count"),
+    ("column.id", "ID"),
+    ("column.name", "Name"),
+    ("column.kind", "Kind"),
+    ("column.value", "Value"),
+    ("column.valued_on", "Valued on"),
+    ("column.tag", "Tag"),
+    ("column.payment", "Payment"),
+    ("column.remaining", "Remaining"),
+    ("column.payoff", "Payoff"),
+    ("column.symbol", "Symbol"),
+    ("column.quantity", "Quantity"),
+    ("column.cost", "Cost"),
+    ("column.price", "Price"),
+    ("column.unrealised", "Unrealised"),
+    ("column.realised", "Realised"),
+    ("column.account", "Account"),
+    ("column.currency", "Currency"),
+    // súhrny
+    ("kind.asset", "asset"),
+    ("kind.liability", "liability"),
+    ("net_worth", "net worth {} (assets {}, liabilities {})"),
+];
+
+const SK: Table = &[
+    // chyby
+    ("error.query", "dopyt {}"),
+    ("error.unknown_file_format", "neznámy formát súboru, použite --format"),
+    ("error.invalid_amount", "neplatná suma {}, očakáva sa kladné číslo"),
+    ("error.invalid_currency", "neplatná mena '{}'"),
+    ("error.invalid_date", "neplatný dátum '{}', očakáva sa {}"),
+    ("error.empty_account", "názov účtu nesmie byť prázdny"),
+    ("error.empty_asset", "názov majetku nesmie byť prázdny"),
+    ("error.empty_loan", "názov úveru nesmie byť prázdny"),
+    ("error.invalid_principal", "neplatná istina {}"),
+    ("error.invalid_rate", "neplatná úroková sadzba {}"),
+    ("error.invalid_term", "úver musí mať aspoň jednu splátku"),
+    ("error.invalid_tag", "neplatný štítok '{}'"),
+    ("error.record_not_found", "záznam {} neexistuje"),
+    ("error.not_in_trash", "záznam {} nie je v koši"),
+    ("error.asset_not_found", "majetok {} neexistuje"),
+    // výsledky príkazov
+    ("done.purged", "záznam {} je natrvalo vymazaný"),
+    ("done.trashed", "záznam {} je presunutý do koša"),
+    ("done.asset_added", "majetok {} je pridaný"),
+    ("done.liability_added", "záväzok {} je pridaný"),
+    ("done.valued", "majetok {} je ocenený"),
+    ("done.loan_added", "úver {} je pridaný, splátka {}"),
+    ("done.bought", "nákup {} {} za {} je zapísaný"),
+    ("done.sold", "predaj {} {} za {} je zapísaný"),
+    ("done.imported_records", "importované záznamy: {}"),
+    ("done.imported_prices", "importované ceny: {}"),
+    ("done.imported_rates", "importované kurzy: {}"),
+    // stĺpce tabuliek
+    ("column.period", "Obdobie"),
+    ("column.group", "Skupina"),
+    ("column.income", "Príjmy"),
+    ("column.expense", "Výdavky"),
+    ("column.invested", "Investície"),
+    ("column.net", "Spolu"),
+    ("column.count", "Počet"),
+    ("column.id", "ID"),
+    ("column.name", "Názov"),
+    ("column.kind", "Druh"),
+    ("column.value", "Hodnota"),
+    ("column.valued_on", "Ocenené"),
+    ("column.tag", "Štítok"),
+    ("column.payment", "Splátka"),
+    ("column.remaining", "Zostáva"),
+    ("column.payoff", "Splatenie"),
+    ("column.symbol", "Symbol"),
+    ("column.quantity", "Počet"),
+    ("column.cost", "Náklady"),
+    ("column.price", "Cena"),
+    ("column.unrealised", "Nerealizované"),
+    ("column.realised", "Realizované"),
+    ("column.account", "Účet"),
+    ("column.currency", "Mena"),
+    // súhrny
+    ("kind.asset", "majetok"),
+    ("kind.liability", "záväzok"),
+    ("net_worth", "čistá hodnota {} (majetok {}, záväzky {})"),
+];
@@ -0,0 +1,902 @@
+//! Neinteraktívne rozhranie príkazového riadku pre skripty a cron.
+//!
+//! Binárka `finance` používa rovnaké API knižnice `financial_lib` ako TUI.
+//! Pridanie, úprava, odstránenie a import záznamov sa preto ukladajú do
+//! histórie a dajú sa vrátiť v TUI, import ako jedna akcia. Trvalé vymazanie
+//! z koša a zmeny majetku, úverov, obchodov, cien, kurzov a účtov sa do
+//! histórie neukladajú a vrátiť sa nedajú.
+//! Výstup je tabuľka alebo JSON (`--output json`). Nastavenia sa berú
+//! zo súboru nastavení ([`financial_lib::config`]), súbor nastavení sa dá
+//! zmeniť prepínačom `--config` a jednotlivé nastavenia prepínačmi `--db`,
+//! `--default-account`, `--base-currency`, `--date-format` a `--language`.
+//!
+//! Kódy ukončenia:
+//! - `0` – úspech
+//! - `1` – chyba pri čítaní alebo zápise súboru alebo databázy
+//! - `2` – nesprávne argumenty alebo dopyt
+//! - `3` – záznam neexistuje
+//! - `4` – záznam je zamknutý alebo sa medzičasom zmenil, nič sa nezmenilo
+//! - `5` – neplatné dáta na importe alebo chýbajúci kurz meny
+mod i18n;
+mod output;
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chrono::{Local, NaiveDate};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use financial_lib::{
+    aggregate::{GroupBy, Period},
+    config::{self, Config, ConfigError, Flags, date_format, date_hint},
+    currency::{Account, parse_rates_csv},
+    db::{
+        aggregate, balance, commit, get_next_id, insert_asset, insert_loan, insert_prices,
+        insert_rates, insert_trade, insert_valuation, load_accounts, load_assets, load_converter,
+        load_loans, load_prices, load_records, load_trades, load_trash, load_valuations,
+        purge_record, query_records, save_account,
+    },
+    history::{Action, Change, HistoryError},
+    i18n::{MESSAGES, fill},
+    investment::{Trade, TradeKind, holdings, parse_prices_csv},
+    loan::{Loan, PaymentFrequency},
+    networth::{Asset, AssetKind, Valuation, net_worth_on},
+    query::RecordFilter,
+    record::{ExpenseType, MoneyType, Record},
+    search::parse_query,
+    transfer::{self, Format},
+};
+
+use crate::i18n::{tr, trf};
+use crate::output::Output;
+
+/// Osobné financie z príkazového riadku.
+#[derive(Debug, Parser)]
+#[command(name = "finance", version, about)]
+struct Cli {
+    /// Output format
+    #[arg(short, long, value_enum, global = true, default_value_t = Output::Table)]
+    output: Output,
+    /// Configuration file instead of the default one
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Database file, overrides the configuration and DATABASE_URL
+    #[arg(long, global = true, value_name = "FILE")]
+    db: Option<PathBuf>,
+    /// Account of new records, overrides the configuration and FINANCE_ACCOUNT
+    #[arg(long, global = true, value_name = "NAME")]
+    default_account: Option<String>,
+    /// Currency of totals, overrides the configuration and BASE_CURRENCY
+    #[arg(long, global = true, value_name = "CODE")]
+    base_currency: Option<String>,
+    /// Date format such as %d.%m.%Y, overrides the configuration and FINANCE_DATE_FORMAT
+    #[arg(long, global = true, value_name = "FORMAT")]
+    date_format: Option<String>,
+    /// Language of messages (en or sk), overrides the configuration and FINANCE_LANGUAGE
+    #[arg(long, global = true, value_name = "CODE")]
+    language: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Podpríkazy.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Add a record (an expense unless --income is given)
+    Add {
+        /// Amount, always positive
+        amount: f32,
+        #[command(flatten)]
+        fields: RecordFields,
+    },
+    /// List records matching a search query, e.g. `cat:RESTAURANT amount>20`
+    List {
+        /// List the trash instead of the records
+        #[arg(long)]
+        trash: bool,
+        /// Search query
+        query: Vec<String>,
+    },
+    /// Change fields of a record
+    Edit {
+        /// Record ID
+        id: i32,
+        /// New amount
+        #[arg(long)]
+        amount: Option<f32>,
+        #[command(flatten)]
+        fields: RecordFields,
+    },
+    /// Move a record to the trash
+    Delete {
+        /// Record ID
+        id: i32,
+        /// Permanently delete a record that is already in the trash
+        #[arg(long)]
+        purge: bool,
+    },
+    /// Balance of records matching a search query in the base currency
+    Balance {
+        /// Search query
+        query: Vec<String>,
+    },
+    /// Income, expense and net per period in the base currency
+    Report {
+        /// Length of one period
+        #[arg(long, value_enum, default_value_t = PeriodArg::Month)]
+        period: PeriodArg,
+        /// Split each period further
+        #[arg(long, value_enum)]
+        by: Option<GroupArg>,
+        /// Search query
+        query: Vec<String>,
+    },
+    /// Import records from a CSV or JSON file as new records, all or nothing, undone as one step
+    Import {
+        /// File to import
+        file: PathBuf,
+        /// File format, guessed from the extension if not given
+        #[arg(long, value_parser = parse_format)]
+        format: Option<Format>,
+    },
+    /// Export records matching a search query as CSV or JSON
+    Export {
+        /// Write into a file instead of the standard output
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// File format, guessed from the extension of --file, JSON otherwise
+        #[arg(long, value_parser = parse_format)]
+        format: Option<Format>,
+        /// Search query
+        query: Vec<String>,
+    },
+    /// Assets and liabilities counted in the net worth
+    Asset {
+        #[command(subcommand)]
+        command: AssetCommand,
+    },
+    /// Loans repaid by expenses with the loan's tag
+    Loan {
+        #[command(subcommand)]
+        command: LoanCommand,
+    },
+    /// Trades of securities and the resulting holdings
+    Trade {
+        #[command(subcommand)]
+        command: TradeCommand,
+    },
+    /// Prices of securities
+    Prices {
+        #[command(subcommand)]
+        command: PricesCommand,
+    },
+    /// Exchange rates used to convert to the base currency
+    Rates {
+        #[command(subcommand)]
+        command: RatesCommand,
+    },
+    /// Accounts and their currencies
+    Account {
+        #[command(subcommand)]
+        command: AccountCommand,
+    },
+}
+
+/// Podpríkazy `asset`.
+#[derive(Debug, Subcommand)]
+enum AssetCommand {
+    /// List assets and liabilities with their latest value and the net worth
+    List,
+    /// Add an asset, or a liability with --liability
+    Add {
+        /// Name, e.g. Flat or Mortgage
+        name: String,
+        /// Liability instead of asset
+        #[arg(long)]
+        liability: bool,
+        /// Initial value, the owed amount for a liability
+        #[arg(long)]
+        value: Option<f32>,
+        /// Date of the initial value, today if not given
+        #[arg(long, requires = "value")]
+        date: Option<String>,
+    },
+    /// Record the value of an asset or liability on a day
+    Value {
+        /// Asset ID
+        id: i32,
+        /// Value, the owed amount for a liability
+        value: f32,
+        /// Date of the value, today if not given
+        #[arg(long)]
+        date: Option<String>,
+    },
+}
+
+/// Podpríkazy `loan`.
+#[derive(Debug, Subcommand)]
+enum LoanCommand {
+    /// List loans with the remaining debt and the expected payoff date
+    List,
+    /// Add a loan
+    Add {
+        /// Name, e.g. Mortgage
+        name: String,
+        /// Borrowed amount in the base currency
+        #[arg(long)]
+        principal: f32,
+        /// Annual interest rate in percent
+        #[arg(long, default_value_t = 0.0)]
+        rate: f32,
+        /// Number of payments
+        #[arg(long)]
+        term: u32,
+        /// How often the loan is repaid
+        #[arg(long, value_enum, default_value_t = FrequencyArg::Monthly)]
+        frequency: FrequencyArg,
+        /// Date of the first payment, today if not given
+        #[arg(long)]
+        start: Option<String>,
+        /// Tag of the expenses that repay the loan, the name if not given
+        #[arg(long)]
+        tag: Option<String>,
+    },
+}
+
+/// Podpríkazy `trade`.
+#[derive(Debug, Subcommand)]
+enum TradeCommand {
+    /// List holdings with cost basis, market value and gains
+    List,
+    /// Record a purchase, which opens a new lot
+    Buy(TradeFields),
+    /// Record a sale, which closes the oldest lots first
+    Sell(TradeFields),
+}
+
+/// Položky obchodu spoločné pre `trade buy` a `trade sell`.
+#[derive(Debug, Args)]
+struct TradeFields {
+    /// Symbol of the security, e.g. VWCE
+    symbol: String,
+    /// Number of units, must be positive
+    quantity: f32,
+    /// Price per unit
+    price: f32,
+    /// Fee paid for the trade
+    #[arg(long, default_value_t = 0.0)]
+    fee: f32,
+    /// Date of the trade, today if not given
+    #[arg(long)]
+    date: Option<String>,
+}
+
+/// Podpríkazy `prices`.
+#[derive(Debug, Subcommand)]
+enum PricesCommand {
+    /// Import prices from a CSV file with lines `symbol,YYYY-MM-DD,price`
+    Import {
+        /// File to import
+        file: PathBuf,
+    },
+}
+
+/// Podpríkazy `rates`.
+#[derive(Debug, Subcommand)]
+enum RatesCommand {
+    /// Import exchange rates from a CSV file with lines `from,to,YYYY-MM-DD,rate`
+    Import {
+        /// File to import
+        file: PathBuf,
+    },
+}
+
+/// Podpríkazy `account`.
+#[derive(Debug, Subcommand)]
+enum AccountCommand {
+    /// List accounts and their currencies
+    List,
+    /// Set the currency of an account, new records on it use this currency
+    Set {
+        /// Account name
+        name: String,
+        /// Currency code, e.g. USD
+        #[arg(long)]
+        currency: String,
+    },
+}
+
+/// Položky záznamu spoločné pre `add` a `edit`.
+#[derive(Debug, Args)]
+struct RecordFields {
+    /// Income instead of expense
+    #[arg(long, conflicts_with = "expense")]
+    income: bool,
+    /// Expense instead of income
+    #[arg(long)]
+    expense: bool,
+    /// Category code, e.g. RESTAURANT, or NONE
+    #[arg(long)]
+    category: Option<String>,
+    /// Date in the configured format (dd.mm.yyyy by default) or yyyy-mm-dd, today when adding
+    #[arg(long)]
+    date: Option<String>,
+    /// Account name, the configured default account when adding
+    #[arg(long)]
+    account: Option<String>,
+    /// Tag, may be repeated; replaces all tags when editing
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    /// Note
+    #[arg(long)]
+    note: Option<String>,
+    /// Currency code, the account or base currency when adding
+    #[arg(long)]
+    currency: Option<String>,
+}
+
+/// Dĺžka obdobia pre `report`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PeriodArg {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl From<PeriodArg> for Period {
+    fn from(value: PeriodArg) -> Self {
+        match value {
+            PeriodArg::Day => Period::Day,
+            PeriodArg::Week => Period::Week,
+            PeriodArg::Month => Period::Month,
+            PeriodArg::Quarter => Period::Quarter,
+            PeriodArg::Year => Period::Year,
+        }
+    }
+}
+
+/// Frekvencia splátok pre `loan add`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum FrequencyArg {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl From<FrequencyArg> for PaymentFrequency {
+    fn from(value: FrequencyArg) -> Self {
+        match value {
+            FrequencyArg::Weekly => PaymentFrequency::Weekly,
+            FrequencyArg::Monthly => PaymentFrequency::Monthly,
+            FrequencyArg::Quarterly => PaymentFrequency::Quarterly,
+            FrequencyArg::Yearly => PaymentFrequency::Yearly,
+        }
+    }
+}
+
+/// Ďalšie delenie obdobia pre `report`.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum GroupArg {
+    Category,
+    Account,
+    Tag,
+}
+
+impl From<GroupArg> for GroupBy {
+    fn from(value: GroupArg) -> Self {
+        match value {
+            GroupArg::Category => GroupBy::Category,
+            GroupArg::Account => GroupBy::Account,
+            GroupArg::Tag => GroupBy::Tag,
+        }
+    }
+}
+
+/// Chyba príkazu, určuje kód ukončenia.
+#[derive(Debug)]
+enum CliError {
+    /// súbor sa nedal prečítať alebo zapísať
+    Io(String),
+    /// nesprávne argumenty alebo dopyt
+    Usage(String),
+    /// záznam neexistuje
+    NotFound(String),
+    /// záznam je zamknutý alebo sa medzičasom zmenil
+    Conflict(String),
+    /// neplatné dáta
+    Data(String),
+}
+
+impl CliError {
+    /// Vráti kód ukončenia procesu.
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Io(_) => 1,
+            CliError::Usage(_) => 2,
+            CliError::NotFound(_) => 3,
+            CliError::Conflict(_) => 4,
+            CliError::Data(_) => 5,
+        }
+    }
+}
+
+impl From<HistoryError> for CliError {
+    fn from(error: HistoryError) -> CliError {
+        match error {
+            HistoryError::Database { .. } => CliError::Io(error.to_string()),
+            HistoryError::Conflict { .. } | HistoryError::Locked { .. } => {
+                CliError::Conflict(error.to_string())
+            }
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Io(m)
+            | CliError::Usage(m)
+            | CliError::NotFound(m)
+            | CliError::Conflict(m)
+            | CliError::Data(m) => write!(f, "{}", m),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let flags = Flags {
+        database: cli.db,
+        account: cli.default_account,
+        currency: cli.base_currency,
+        date_format: cli.date_format,
+        language: cli.language,
+    };
+    if let Err(e) = configure(cli.config.as_deref(), &flags) {
+        // nastavenia sa nenainštalovali, chyba pozná jazyk, v ktorom je napísaná
+        eprintln!("{}", fill(MESSAGES.text_in(e.language, "message.error"), &[&e]));
+        // chybné nastavenia ako nesprávne argumenty
+        return ExitCode::from(2);
+    }
+    match run(cli.command, cli.output) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", MESSAGES.format("message.error", &[&e]));
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+/// Načíta nastavenia, prepíše ich prepínačmi a nastaví ich pre celý program.
+fn configure(path: Option<&Path>, flags: &Flags) -> Result<(), ConfigError> {
+    let mut config = Config::load(path)?;
+    config.apply_flags(flags)?;
+    let legacy = config.legacy_database();
+    config::install(config);
+    if let Some(legacy) = legacy {
+        let warning = MESSAGES.format("config.legacy_database", &[&legacy.display()]);
+        eprintln!("{}", MESSAGES.format("message.warning", &[&warning]));
+    }
+    Ok(())
+}
+
+/// Vykoná podpríkaz a vypíše výsledok.
+fn run(command: Command, output: Output) -> Result<(), CliError> {
+    match command {
+        Command::Add { amount, fields } => {
+            let money_type = if fields.income {
+                MoneyType::INCOME
+            } else {
+                MoneyType::EXPENSE
+            };
+            let time = fields.date()?.unwrap_or_else(|| Local::now().date_naive());
+            let mut record =
+                Record::new(get_next_id(), money_type, check_amount(amount)?, None, time);
+            record.account = config::current().defaults.account.clone();
+            let mut record = fields.apply(record)?;
+            if fields.currency.is_none() {
+                record.currency = default_currency(record.account.as_deref());
+            }
+            transfer::check_record(&record).map_err(CliError::Usage)?;
+            let action = Action::new(
+                MESSAGES.format("action.add", &[&record.id]),
+                vec![Change::insert(record.clone())],
+            );
+            commit(&action).map_err(CliError::from)?;
+            output.records(&[record]);
+        }
+        Command::List { trash, query } => {
+            let records = if trash {
+                load_trash()
+            } else {
+                query_records(&filter(&query)?)
+            };
+            output.records(&records);
+        }
+        Command::Edit { id, amount, fields } => {
+            let before = find_record(id)?;
+            let mut after = fields.apply(before.clone())?;
+            if let Some(amount) = amount {
+                after.amount = check_amount(amount)?;
+            }
+            transfer::check_record(&after).map_err(CliError::Usage)?;
+            let action = Action::new(
+                MESSAGES.format("action.edit", &[&id]),
+                vec![Change::update(before, after.clone())],
+            );
+            commit(&action).map_err(CliError::from)?;
+            output.records(&[after]);
+        }
+        Command::Delete { id, purge: true } => {
+            if !purge_record(id) {
+                return Err(CliError::NotFound(trf("error.not_in_trash", &[&id])));
+            }
+            output.message(&trf("done.purged", &[&id]), id);
+        }
+        Command::Delete { id, purge: false } => {
+            let before = find_record(id)?;
+            let mut trashed = before.clone();
+            trashed.deleted_on = Some(Local::now().date_naive());
+            let action = Action::new(
+                MESSAGES.format("action.delete", &[&id]),
+                vec![Change::update(before, trashed)],
+            );
+            commit(&action).map_err(CliError::from)?;
+            output.message(&trf("done.trashed", &[&id]), id);
+        }
+        Command::Balance { query } => {
+            let converter = load_converter();
+            let value = balance(&filter(&query)?, &converter)
+                .map_err(|e| CliError::Data(e.to_string()))?;
+            output.balance(value, &converter.base);
+        }
+        Command::Report { period, by, query } => {
+            let converter = load_converter();
+            let buckets = aggregate(
+                &filter(&query)?,
+                period.into(),
+                by.map(|g| g.into()),
+                &converter,
+            )
+            .map_err(|e| CliError::Data(e.to_string()))?;
+            output.report(&buckets, &converter.base);
+        }
+        Command::Import { file, format } => {
+            let format = format
+                .or_else(|| Format::from_path(&file))
+                .ok_or_else(|| CliError::Usage(tr("error.unknown_file_format").to_string()))?;
+            let input = fs::read_to_string(&file)
+                .map_err(|e| CliError::Io(format!("{}: {}", file.display(), e)))?;
+            let records = transfer::import(&input, format)
+                .map_err(|e| CliError::Data(format!("{}: {}", file.display(), e)))?;
+            let changes: Vec<Change> = records
+                .into_iter()
+                .zip(get_next_id()..)
+                .map(|(r, id)| Change::insert(Record { id, ..r }))
+                .collect();
+            let ids: Vec<i32> = changes.iter().map(|c| c.id()).collect();
+            let action = Action::new(
+                MESSAGES.format("action.import", &[&ids.len(), &file.display()]),
+                changes,
+            );
+            commit(&action).map_err(CliError::from)?;
+            output.imported(&ids);
+        }
+        Command::Export {
+            file,
+            format,
+            query,
+        } => {
+            let format = format
+                .or_else(|| file.as_deref().and_then(Format::from_path))
+                .unwrap_or(Format::Json);
+            let mut text = transfer::export(&query_records(&filter(&query)?), format);
+            if !text.ends_with('\n') {
+                text.push('\n');
+            }
+            match file {
+                Some(file) => fs::write(&file, text)
+                    .map_err(|e| CliError::Io(format!("{}: {}", file.display(), e)))?,
+                None => print!("{}", text),
+            }
+        }
+        Command::Asset { command } => run_asset(command, output)?,
+        Command::Loan { command } => run_loan(command, output)?,
+        Command::Trade { command } => run_trade(command, output)?,
+        Command::Prices {
+            command: PricesCommand::Import { file },
+        } => {
+            let input = fs::read_to_string(&file)
+                .map_err(|e| CliError::Io(format!("{}: {}", file.display(), e)))?;
+            let prices = parse_prices_csv(&input)
+                .map_err(|e| CliError::Data(format!("{}: {}", file.display(), e)))?;
+            insert_prices(&prices);
+            output.imported_count("done.imported_prices", prices.len());
+        }
+        Command::Rates {
+            command: RatesCommand::Import { file },
+        } => {
+            let input = fs::read_to_string(&file)
+                .map_err(|e| CliError::Io(format!("{}: {}", file.display(), e)))?;
+            let rates = parse_rates_csv(&input)
+                .map_err(|e| CliError::Data(format!("{}: {}", file.display(), e)))?;
+            insert_rates(&rates);
+            output.imported_count("done.imported_rates", rates.len());
+        }
+        Command::Account {
+            command: AccountCommand::List,
+        } => output.accounts(&load_accounts()),
+        Command::Account {
+            command: AccountCommand::Set { name, currency },
+        } => {
+            if name.is_empty() {
+                return Err(CliError::Usage(tr("error.empty_account").to_string()));
+            }
+            let account = Account {
+                name,
+                currency: parse_currency(&currency)?,
+            };
+            save_account(&account);
+            output.accounts(&[account]);
+        }
+    }
+    Ok(())
+}
+
+/// Vykoná podpríkaz `asset`.
+fn run_asset(command: AssetCommand, output: Output) -> Result<(), CliError> {
+    let today = Local::now().date_naive();
+    match command {
+        AssetCommand::List => {
+            let assets = load_assets();
+            let valuations = load_valuations();
+            output.assets(&assets, &valuations, net_worth_on(&assets, &valuations, today));
+        }
+        AssetCommand::Add {
+            name,
+            liability,
+            value,
+            date,
+        } => {
+            if name.trim().is_empty() {
+                return Err(CliError::Usage(tr("error.empty_asset").to_string()));
+            }
+            let kind = if liability {
+                AssetKind::Liability
+            } else {
+                AssetKind::Asset
+            };
+            let value = value.map(check_amount).transpose()?;
+            let time = date.as_deref().map(parse_date).transpose()?.unwrap_or(today);
+            let id = insert_asset(&Asset::new(name.trim(), kind));
+            if let Some(value) = value {
+                insert_valuation(&Valuation {
+                    asset_id: id,
+                    time,
+                    value,
+                });
+            }
+            let key = match kind {
+                AssetKind::Asset => "done.asset_added",
+                AssetKind::Liability => "done.liability_added",
+            };
+            output.message(&trf(key, &[&id]), id);
+        }
+        AssetCommand::Value { id, value, date } => {
+            if !load_assets().iter().any(|a| a.id == Some(id)) {
+                return Err(CliError::NotFound(trf("error.asset_not_found", &[&id])));
+            }
+            insert_valuation(&Valuation {
+                asset_id: id,
+                time: date.as_deref().map(parse_date).transpose()?.unwrap_or(today),
+                value: check_amount(value)?,
+            });
+            output.message(&trf("done.valued", &[&id]), id);
+        }
+    }
+    Ok(())
+}
+
+/// Vykoná podpríkaz `loan`.
+fn run_loan(command: LoanCommand, output: Output) -> Result<(), CliError> {
+    match command {
+        LoanCommand::List => {
+            let records = load_records().get_all();
+            let converter = load_converter();
+            let loans = load_loans()
+                .into_iter()
+                .map(|l| {
+                    let status = l
+                        .converted_status(&records, &converter)
+                        .map_err(|e| CliError::Data(e.to_string()))?;
+                    Ok((l, status))
+                })
+                .collect::<Result<Vec<_>, CliError>>()?;
+            output.loans(&loans);
+        }
+        LoanCommand::Add {
+            name,
+            principal,
+            rate,
+            term,
+            frequency,
+            start,
+            tag,
+        } => {
+            let name = name.trim().to_string();
+            if name.is_empty() {
+                return Err(CliError::Usage(tr("error.empty_loan").to_string()));
+            }
+            if !(principal.is_finite() && principal > 0.0) {
+                return Err(CliError::Usage(trf("error.invalid_principal", &[&principal])));
+            }
+            if !(rate.is_finite() && rate >= 0.0) {
+                return Err(CliError::Usage(trf("error.invalid_rate", &[&rate])));
+            }
+            if term == 0 {
+                return Err(CliError::Usage(tr("error.invalid_term").to_string()));
+            }
+            let tag = tag.unwrap_or_else(|| name.clone()).trim().to_string();
+            if tag.is_empty() || tag.contains(',') {
+                return Err(CliError::Usage(trf("error.invalid_tag", &[&tag])));
+            }
+            let mut loan = Loan {
+                id: None,
+                name,
+                principal,
+                annual_rate: rate,
+                term,
+                frequency: frequency.into(),
+                start: start
+                    .as_deref()
+                    .map(parse_date)
+                    .transpose()?
+                    .unwrap_or_else(|| Local::now().date_naive()),
+                tag,
+            };
+            let id = insert_loan(&loan);
+            loan.id = Some(id);
+            output.message(
+                &trf("done.loan_added", &[&id, &format!("{:.2}", loan.payment())]),
+                id,
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Vykoná podpríkaz `trade`.
+fn run_trade(command: TradeCommand, output: Output) -> Result<(), CliError> {
+    let today = Local::now().date_naive();
+    let (kind, fields) = match command {
+        TradeCommand::List => {
+            output.holdings(&holdings(&load_trades(), &load_prices(), today));
+            return Ok(());
+        }
+        TradeCommand::Buy(fields) => (TradeKind::Buy, fields),
+        TradeCommand::Sell(fields) => (TradeKind::Sell, fields),
+    };
+    let time = fields.date.as_deref().map(parse_date).transpose()?.unwrap_or(today);
+    let trade = Trade::new(
+        fields.symbol.trim(),
+        time,
+        kind,
+        fields.quantity,
+        fields.price,
+        fields.fee,
+    )
+    .map_err(|e| CliError::Usage(e.to_string()))?;
+    let id = insert_trade(&trade).map_err(|e| CliError::Usage(e.to_string()))?;
+    let key = match kind {
+        TradeKind::Buy => "done.bought",
+        TradeKind::Sell => "done.sold",
+    };
+    let price = format!("{:.2}", trade.price);
+    output.message(&trf(key, &[&trade.quantity, &trade.symbol, &price]), id);
+    Ok(())
+}
+
+impl RecordFields {
+    /// Načíta zadaný dátum.
+    fn date(&self) -> Result<Option<NaiveDate>, CliError> {
+        self.date.as_deref().map(parse_date).transpose()
+    }
+
+    /// Prepíše zadané položky záznamu.
+    fn apply(&self, mut record: Record) -> Result<Record, CliError> {
+        if self.income {
+            record.money_type = MoneyType::INCOME;
+        }
+        if self.expense {
+            record.money_type = MoneyType::EXPENSE;
+        }
+        if let Some(category) = &self.category {
+            record.expense = match category.to_uppercase().as_str() {
+                "NONE" => None,
+                code => Some(code.parse::<ExpenseType>().map_err(CliError::Usage)?),
+            };
+        }
+        if let Some(date) = self.date()? {
+            record.time = date;
+        }
+        if let Some(account) = &self.account {
+            record.account = Some(account.clone()).filter(|a| !a.is_empty());
+        }
+        if !self.tags.is_empty() {
+            record.tags = self.tags.clone();
+        }
+        if let Some(note) = &self.note {
+            record.note = Some(note.clone()).filter(|n| !n.is_empty());
+        }
+        if let Some(currency) = &self.currency {
+            record.currency = parse_currency(currency)?;
+        }
+        Ok(record)
+    }
+}
+
+/// Spracuje dopyt vyhľadávania, prázdny dopyt vyberie všetky záznamy.
+fn filter(query: &[String]) -> Result<RecordFilter, CliError> {
+    parse_query(&query.join(" ")).map_err(|e| CliError::Usage(trf("error.query", &[&e])))
+}
+
+/// Nájde záznam mimo koša.
+fn find_record(id: i32) -> Result<Record, CliError> {
+    load_records()
+        .get_record_id(id)
+        .cloned()
+        .ok_or_else(|| CliError::NotFound(trf("error.record_not_found", &[&id])))
+}
+
+/// Skontroluje, že suma je kladné číslo.
+fn check_amount(amount: f32) -> Result<f32, CliError> {
+    if amount.is_finite() && amount >= 0.0 {
+        Ok(amount)
+    } else {
+        Err(CliError::Usage(trf("error.invalid_amount", &[&amount])))
+    }
+}
+
+/// Načíta kód meny z troch písmen a zjednotí ho na veľké písmená.
+fn parse_currency(text: &str) -> Result<String, CliError> {
+    let code = text.to_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(CliError::Usage(trf("error.invalid_currency", &[&text])));
+    }
+    Ok(code)
+}
+
+/// Mena nového záznamu: mena účtu, inak základná mena.
+fn default_currency(account: Option<&str>) -> String {
+    load_accounts()
+        .into_iter()
+        .find(|a| Some(a.name.as_str()) == account)
+        .map(|a| a.currency)
+        .unwrap_or_else(|| load_converter().base)
+}
+
+/// Načíta dátum v nastavenom formáte alebo v tvare `yyyy-mm-dd`.
+fn parse_date(text: &str) -> Result<NaiveDate, CliError> {
+    NaiveDate::parse_from_str(text, date_format())
+        .or_else(|_| NaiveDate::parse_from_str(text, "%Y-%m-%d"))
+        .map_err(|_| {
+            let expected = date_hint(date_format());
+            CliError::Usage(trf("error.invalid_date", &[&text, &expected]))
+        })
+}
+
+/// Načíta formát súboru (`csv` alebo `json`).
+///
+/// Prepínače sa spracujú pred [`configure`], `Format::from_str` sa preto
+/// nepoužije – jeho preložená chyba by načítala nastavenia priskoro.
+fn parse_format(text: &str) -> Result<Format, String> {
+    match text.to_lowercase().as_str() {
+        "csv" => Ok(Format::Csv),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("unknown format '{}', expected csv or json", text)),
+    }
+}
@@ -0,0 +1,330 @@
+//! Výpis výsledkov príkazov ako tabuľky alebo JSON.
+//!
+//! Tabuľka je určená pre ľudí, JSON pre skripty – každý príkaz vypíše
+//! jednu JSON hodnotu na štandardný výstup.
+use clap::ValueEnum;
+use financial_lib::{
+    aggregate::{Bucket, GroupKey},
+    config::date_format,
+    currency::Account,
+    investment::Holding,
+    loan::{Loan, LoanStatus},
+    networth::{Asset, AssetKind, NetWorthPoint, Valuation, value_on},
+    record::Record,
+};
+use serde_json::{Value, json};
+
+use crate::i18n::{tr, trf};
+
+/// Formát výstupu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    Table,
+    Json,
+}
+
+impl Output {
+    /// Vypíše záznamy.
+    pub fn records(&self, records: &[Record]) {
+        match self {
+            Output::Table => records.iter().for_each(|r| println!("{}", r.format_record())),
+            Output::Json => print_json(&json!(records)),
+        }
+    }
+
+    /// Vypíše výsledok zmeny jedného záznamu.
+    pub fn message(&self, message: &str, id: i32) {
+        match self {
+            Output::Table => println!("{}", message),
+            Output::Json => print_json(&json!({ "id": id, "message": message })),
+        }
+    }
+
+    /// Vypíše zostatok v základnej mene.
+    pub fn balance(&self, balance: f32, currency: &str) {
+        match self {
+            Output::Table => println!("{:.2} {}", balance, currency),
+            Output::Json => print_json(&json!({ "balance": balance, "currency": currency })),
+        }
+    }
+
+    /// Vypíše súčty za obdobia.
+    pub fn report(&self, buckets: &[Bucket], currency: &str) {
+        match self {
+            Output::Table => {
+                println!(
+                    "{:<10}  {:<14}  {:>10}  {:>10}  {:>10}  {:>10}  {:>5}  ({})",
+                    tr("column.period"),
+                    tr("column.group"),
+                    tr("column.income"),
+                    tr("column.expense"),
+                    tr("column.invested"),
+                    tr("column.net"),
+                    tr("column.count"),
+                    currency
+                );
+                for b in buckets {
+                    println!(
+                        "{:<10}  {:<14}  {:>10.2}  {:>10.2}  {:>10.2}  {:>10.2}  {:>5}",
+                        b.start.format(date_format()),
+                        group_name(&b.key).unwrap_or_default(),
+                        b.income,
+                        b.expense,
+                        b.invested,
+                        b.net,
+                        b.count
+                    );
+                }
+            }
+            Output::Json => {
+                let rows: Vec<Value> = buckets
+                    .iter()
+                    .map(|b| {
+                        json!({
+                            "start": b.start,
+                            "group": group_name(&b.key),
+                            "income": b.income,
+                            "expense": b.expense,
+                            "invested": b.invested,
+                            "net": b.net,
+                            "count": b.count,
+                            "currency": currency,
+                        })
+                    })
+                    .collect();
+                print_json(&Value::Array(rows));
+            }
+        }
+    }
+
+    /// Vypíše majetok a záväzky s posledným ocenením a čistú hodnotu.
+    pub fn assets(&self, assets: &[Asset], valuations: &[Valuation], net_worth: NetWorthPoint) {
+        let latest = |a: &Asset| a.id.and_then(|id| value_on(valuations, id, net_worth.date));
+        match self {
+            Output::Table => {
+                println!(
+                    "{:>3}  {:<20}  {:<9}  {:>12}  {:<10}",
+                    tr("column.id"),
+                    tr("column.name"),
+                    tr("column.kind"),
+                    tr("column.value"),
+                    tr("column.valued_on")
+                );
+                for a in assets {
+                    let (value, time) = match latest(a) {
+                        Some(v) => (
+                            format!("{:.2}", v.value),
+                            v.time.format(date_format()).to_string(),
+                        ),
+                        None => ("-".to_string(), "-".to_string()),
+                    };
+                    println!(
+                        "{:>3}  {:<20}  {:<9}  {:>12}  {}",
+                        a.id.unwrap_or_default(),
+                        a.name,
+                        kind_name(a.kind),
+                        value,
+                        time
+                    );
+                }
+                let amount = |v: f32| format!("{:.2}", v);
+                println!(
+                    "{}",
+                    trf(
+                        "net_worth",
+                        &[
+                            &amount(net_worth.net),
+                            &amount(net_worth.assets),
+                            &amount(net_worth.liabilities)
+                        ]
+                    )
+                );
+            }
+            Output::Json => {
+                let rows: Vec<Value> = assets
+                    .iter()
+                    .map(|a| {
+                        let latest = latest(a);
+                        json!({
+                            "id": a.id,
+                            "name": a.name,
+                            "kind": a.kind.code(),
+                            "value": latest.map(|v| v.value),
+                            "valued_on": latest.map(|v| v.time),
+                        })
+                    })
+                    .collect();
+                print_json(&json!({
+                    "assets": rows,
+                    "net_worth": {
+                        "date": net_worth.date,
+                        "assets": net_worth.assets,
+                        "liabilities": net_worth.liabilities,
+                        "net": net_worth.net,
+                    },
+                }));
+            }
+        }
+    }
+
+    /// Vypíše úvery so zostatkom dlhu a odhadom splatenia.
+    pub fn loans(&self, loans: &[(Loan, LoanStatus)]) {
+        match self {
+            Output::Table => {
+                println!(
+                    "{:>3}  {:<20}  {:<12}  {:>10}  {:>12}  {:<10}",
+                    tr("column.id"),
+                    tr("column.name"),
+                    tr("column.tag"),
+                    tr("column.payment"),
+                    tr("column.remaining"),
+                    tr("column.payoff")
+                );
+                for (loan, status) in loans {
+                    let payoff = status
+                        .payoff_date
+                        .map_or("-".to_string(), |d| d.format(date_format()).to_string());
+                    println!(
+                        "{:>3}  {:<20}  {:<12}  {:>10.2}  {:>12.2}  {}",
+                        loan.id.unwrap_or_default(),
+                        loan.name,
+                        loan.tag,
+                        loan.payment(),
+                        status.remaining,
+                        payoff
+                    );
+                }
+            }
+            Output::Json => {
+                let rows: Vec<Value> = loans
+                    .iter()
+                    .map(|(loan, status)| {
+                        json!({
+                            "id": loan.id,
+                            "name": loan.name,
+                            "principal": loan.principal,
+                            "annual_rate": loan.annual_rate,
+                            "term": loan.term,
+                            "frequency": loan.frequency.code(),
+                            "start": loan.start,
+                            "tag": loan.tag,
+                            "payment": loan.payment(),
+                            "remaining": status.remaining,
+                            "interest_paid": status.interest_paid,
+                            "payoff_date": status.payoff_date,
+                        })
+                    })
+                    .collect();
+                print_json(&Value::Array(rows));
+            }
+        }
+    }
+
+    /// Vypíše pozície v cenných papieroch.
+    pub fn holdings(&self, holdings: &[Holding]) {
+        match self {
+            Output::Table => {
+                println!(
+                    "{:<10}  {:>10}  {:>12}  {:>10}  {:>12}  {:>12}  {:>12}",
+                    tr("column.symbol"),
+                    tr("column.quantity"),
+                    tr("column.cost"),
+                    tr("column.price"),
+                    tr("column.value"),
+                    tr("column.unrealised"),
+                    tr("column.realised")
+                );
+                let amount = |v: Option<f32>| v.map_or("-".to_string(), |v| format!("{:.2}", v));
+                for h in holdings {
+                    println!(
+                        "{:<10}  {:>10}  {:>12.2}  {:>10}  {:>12}  {:>12}  {:>12.2}",
+                        h.symbol,
+                        h.quantity(),
+                        h.cost_basis(),
+                        amount(h.price.as_ref().map(|p| p.price)),
+                        amount(h.market_value()),
+                        amount(h.unrealised_gain()),
+                        h.realised_gain
+                    );
+                }
+            }
+            Output::Json => {
+                let rows: Vec<Value> = holdings
+                    .iter()
+                    .map(|h| {
+                        json!({
+                            "symbol": h.symbol,
+                            "quantity": h.quantity(),
+                            "cost_basis": h.cost_basis(),
+                            "price": h.price.as_ref().map(|p| p.price),
+                            "priced_on": h.price.as_ref().map(|p| p.time),
+                            "market_value": h.market_value(),
+                            "unrealised_gain": h.unrealised_gain(),
+                            "realised_gain": h.realised_gain,
+                        })
+                    })
+                    .collect();
+                print_json(&Value::Array(rows));
+            }
+        }
+    }
+
+    /// Vypíše účty a ich meny.
+    pub fn accounts(&self, accounts: &[Account]) {
+        match self {
+            Output::Table => {
+                println!("{:<20}  {}", tr("column.account"), tr("column.currency"));
+                for a in accounts {
+                    println!("{:<20}  {}", a.name, a.currency);
+                }
+            }
+            Output::Json => {
+                let rows: Vec<Value> = accounts
+                    .iter()
+                    .map(|a| json!({ "name": a.name, "currency": a.currency }))
+                    .collect();
+                print_json(&Value::Array(rows));
+            }
+        }
+    }
+
+    /// Vypíše počet importovaných riadkov textom `key`, napr. cien alebo kurzov.
+    pub fn imported_count(&self, key: &str, count: usize) {
+        match self {
+            Output::Table => println!("{}", trf(key, &[&count])),
+            Output::Json => print_json(&json!({ "imported": count })),
+        }
+    }
+
+    /// Vypíše ID importovaných záznamov.
+    pub fn imported(&self, ids: &[i32]) {
+        match self {
+            Output::Table => println!("{}", trf("done.imported_records", &[&ids.len()])),
+            Output::Json => print_json(&json!({ "imported": ids })),
+        }
+    }
+}
+
+/// Vráti preložený názov druhu majetku.
+fn kind_name(kind: AssetKind) -> &'static str {
+    match kind {
+        AssetKind::Asset => tr("kind.asset"),
+        AssetKind::Liability => tr("kind.liability"),
+    }
+}
+
+/// Vráti názov skupiny, `None` ak sa obdobie ďalej nedelí.
+fn group_name(key: &GroupKey) -> Option<String> {
+    match key {
+        GroupKey::All => None,
+        GroupKey::Category(c) => Some(c.map_or("-".to_string(), |c| c.code().to_string())),
+        GroupKey::Account(a) | GroupKey::Tag(a) => Some(a.clone().unwrap_or("-".to_string())),
+    }
+}
+
+fn print_json(value: &Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).expect("JSON values are always serializable")
+    );
+}
@@ -0,0 +1,160 @@
+//! Integračné testy binárky `finance` – kódy ukončenia a výstup JSON.
+//!
+//! Každý test spúšťa binárku nad vlastnou dočasnou databázou.
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use financial_lib::config::{self, Config, DatabaseConfig};
+use financial_lib::db;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+/// Premenné prostredia, ktoré by prepísali nastavenia testu.
+const ENV: [&str; 8] = [
+    "DATABASE_URL",
+    "FINANCE_ACCOUNT",
+    "BASE_CURRENCY",
+    "FINANCE_DATE_FORMAT",
+    "FINANCE_LANGUAGE",
+    "TRASH_RETENTION_DAYS",
+    "FINANCE_THEME",
+    "FINANCE_TICK_RATE",
+];
+
+/// Dva záznamy na import, druhý je odsúhlasený.
+const CSV: &str = "\
+id,date,type,amount,currency,category,account,tags,note,status
+1,2026-03-01,EXPENSE,12.50,EUR,RESTAURANT,card,,lunch,
+2,2026-03-02,INCOME,100.00,EUR,,bank,,salary,RECONCILED
+";
+
+/// Spustí `finance` nad databázou v adresári `dir`.
+fn finance(dir: &Path, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_finance"));
+    command
+        .arg("--db")
+        .arg(dir.join("test.db"))
+        .env("FINANCE_CONFIG", dir.join("config.toml"));
+    for key in ENV {
+        command.env_remove(key);
+    }
+    command.args(args).output().expect("finance runs")
+}
+
+/// Načíta JSON výstup úspešného príkazu.
+fn json(output: &Output) -> Value {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("output is JSON")
+}
+
+/// Skontroluje kód ukončenia neúspešného príkazu a chybu na štandardnom chybovom výstupe.
+fn assert_fails(output: &Output, code: i32) {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(code), "{}", stderr);
+    assert!(stderr.starts_with("error:"), "{}", stderr);
+    assert!(output.stdout.is_empty());
+}
+
+/// Vytvorí dočasný adresár so súborom importu.
+fn import_dir() -> TempDir {
+    let dir = TempDir::new().expect("temporary directory");
+    fs::write(dir.path().join("records.csv"), CSV).unwrap();
+    dir
+}
+
+#[test]
+fn json_output_of_records_and_balance() {
+    let dir = TempDir::new().expect("temporary directory");
+    let dir = dir.path();
+
+    let added = json(&finance(
+        dir,
+        &["-o", "json", "add", "12.5", "--category", "restaurant", "--date", "2026-03-01"],
+    ));
+    assert_eq!(added[0]["id"], 1);
+    assert_eq!(added[0]["amount"], 12.5);
+    assert_eq!(added[0]["expense"], "RESTAURANT");
+
+    let listed = json(&finance(dir, &["list", "--output", "json", "cat:RESTAURANT"]));
+    assert_eq!(listed, added);
+
+    let balance = json(&finance(dir, &["--output", "json", "balance"]));
+    assert_eq!(balance, json!({ "balance": -12.5, "currency": "EUR" }));
+
+    let deleted = json(&finance(dir, &["--output", "json", "delete", "1"]));
+    assert_eq!(deleted["id"], 1);
+    assert_eq!(json(&finance(dir, &["--output", "json", "list"])), json!([]));
+}
+
+#[test]
+fn exit_codes() {
+    let dir = import_dir();
+    let dir = dir.path();
+    let csv = dir.join("records.csv");
+    let csv = csv.to_str().unwrap();
+
+    assert_fails(&finance(dir, &["import", "missing.csv"]), 1);
+    assert_fails(&finance(dir, &["add", "5", "--date", "someday"]), 2);
+    assert_fails(&finance(dir, &["list", "cat:NOTHING"]), 2);
+    assert_fails(&finance(dir, &["add", "5", "--tag", "a,b"]), 2);
+    assert_fails(&finance(dir, &["add", "5", "--tag", ""]), 2);
+    assert_fails(&finance(dir, &["edit", "99", "--amount", "1"]), 3);
+
+    fs::write(dir.join("bad.csv"), "id,date\n1,2026-03-01\n").unwrap();
+    let bad = dir.join("bad.csv");
+    assert_fails(&finance(dir, &["import", bad.to_str().unwrap()]), 5);
+
+    assert!(finance(dir, &["import", csv]).status.success());
+    assert_fails(&finance(dir, &["edit", "2", "--amount", "1"]), 4);
+    assert_fails(&finance(dir, &["delete", "--purge", "1"]), 3);
+}
+
+#[test]
+fn import_is_undone_as_one_action() {
+    let dir = import_dir();
+    let csv = dir.path().join("records.csv");
+
+    let imported = json(&finance(
+        dir.path(),
+        &["import", "--output", "json", csv.to_str().unwrap()],
+    ));
+    assert_eq!(imported, json!({ "imported": [1, 2] }));
+
+    config::install(Config {
+        database: DatabaseConfig {
+            path: Some(dir.path().join("test.db")),
+        },
+        ..Config::default()
+    });
+    let history = db::load_history();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].changes.len(), 2);
+    assert!(db::undo().unwrap().is_some());
+    assert!(db::load_records().get_all().is_empty());
+}
+
+#[test]
+fn messages_follow_configured_language() {
+    let dir = TempDir::new().expect("temporary directory");
+    fs::write(dir.path().join("config.toml"), "[defaults]\nlanguage = \"sk\"\n").unwrap();
+    let output = finance(dir.path(), &["delete", "9"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "chyba: záznam 9 neexistuje\n");
+    let output = finance(dir.path(), &["--language", "en", "delete", "9"]);
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "error: record 9 not found\n");
+
+    let output = finance(dir.path(), &["account", "list"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("Účet"));
+
+    fs::write(dir.path().join("config.toml"), "[defaults]\nlanguage = \"sk\"\ncurrency = \"E\"\n")
+        .unwrap();
+    let output = finance(dir.path(), &["account", "list"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("chyba: nastavenia "));
+}
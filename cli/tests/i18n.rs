@@ -0,0 +1,54 @@
+//! Testy, že každý text príkazového riadku, ktorý sa používa, má anglický preklad.
+//!
+//! CLI je binárka, modul s textami sa preto do testu vkladá priamo zo
+//! zdrojového súboru `src/i18n.rs`.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use financial_lib::i18n::{Language, MESSAGES, source_keys};
+
+#[allow(dead_code)]
+#[path = "../src/i18n.rs"]
+mod i18n;
+
+use i18n::TEXTS;
+
+/// Predpony kľúčov, ktoré sa vyberajú vetvami `match` a nie priamo v `tr(`.
+const PREFIXES: [&str; 4] = ["error.", "done.", "column.", "kind."];
+
+fn src() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src")
+}
+
+#[test]
+fn every_used_key_is_in_english_table() {
+    let mut keys = source_keys(&src(), &["tr(", "trf("]);
+    for file in ["main.rs", "output.rs"] {
+        let path = src().join(file);
+        let source = fs::read_to_string(&path).unwrap();
+        keys.extend(
+            source
+                .split('"')
+                .filter(|s| PREFIXES.iter().any(|p| s.starts_with(p)) && !s.contains(' '))
+                .map(|key| (path.clone(), key.to_string())),
+        );
+    }
+    for (path, key) in &keys {
+        assert!(TEXTS.has(Language::En, key), "{}: missing text '{}'", path.display(), key);
+    }
+    // texty knižnice, napr. popisy akcií zdieľané s TUI a serverom
+    for (path, key) in source_keys(&src(), &["MESSAGES.format("]) {
+        assert!(
+            MESSAGES.has(Language::En, &key),
+            "{}: missing library text '{}'",
+            path.display(),
+            key
+        );
+    }
+    assert!(keys.len() > 50, "only {} keys found", keys.len());
+}
+
+#[test]
+fn every_translation_has_english_text() {
+    assert_eq!(TEXTS.inconsistencies(), Vec::<String>::new());
+}
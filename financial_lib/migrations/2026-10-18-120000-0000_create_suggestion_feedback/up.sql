@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE suggestion_feedback (
+    id INTEGER PRIMARY KEY,
+    amount REAL NOT NULL,
+    time TEXT NOT NULL,
+    category TEXT NOT NULL,
+    accepted BOOLEAN NOT NULL
+);
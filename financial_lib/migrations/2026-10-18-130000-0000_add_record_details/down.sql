@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+UPDATE suggestion_feedback
+SET time = substr(time, 9, 2) || '.' || substr(time, 6, 2) || '.' || substr(time, 1, 4);
+UPDATE records
+SET time = substr(time, 9, 2) || '.' || substr(time, 6, 2) || '.' || substr(time, 1, 4);
+
+ALTER TABLE records DROP COLUMN note;
+ALTER TABLE records DROP COLUMN tags;
+ALTER TABLE records DROP COLUMN account;
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+ALTER TABLE records ADD COLUMN account TEXT;
+ALTER TABLE records ADD COLUMN tags TEXT NOT NULL DEFAULT '';
+ALTER TABLE records ADD COLUMN note TEXT;
+
+-- dátumy v tvare YYYY-MM-DD sa dajú porovnávať a triediť priamo v SQL
+UPDATE records
+SET time = substr(time, 7, 4) || '-' || substr(time, 4, 2) || '-' || substr(time, 1, 2);
+UPDATE suggestion_feedback
+SET time = substr(time, 7, 4) || '-' || substr(time, 4, 2) || '-' || substr(time, 1, 2);
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE assets (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL
+);
+
+CREATE TABLE valuations (
+    id INTEGER PRIMARY KEY,
+    asset_id INTEGER NOT NULL REFERENCES assets(id),
+    time TEXT NOT NULL,
+    value REAL NOT NULL
+);
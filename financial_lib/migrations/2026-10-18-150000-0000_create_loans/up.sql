@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TABLE loans (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    principal REAL NOT NULL,
+    annual_rate REAL NOT NULL,
+    term INTEGER NOT NULL,
+    frequency TEXT NOT NULL,
+    start TEXT NOT NULL,
+    tag TEXT NOT NULL
+);
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE trades (
+    id INTEGER PRIMARY KEY,
+    symbol TEXT NOT NULL,
+    time TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    quantity REAL NOT NULL,
+    price REAL NOT NULL,
+    fee REAL NOT NULL DEFAULT 0
+);
+
+CREATE TABLE prices (
+    id INTEGER PRIMARY KEY,
+    symbol TEXT NOT NULL,
+    time TEXT NOT NULL,
+    price REAL NOT NULL,
+    UNIQUE (symbol, time)
+);
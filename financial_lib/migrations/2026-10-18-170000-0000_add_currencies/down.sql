@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE exchange_rates;
+DROP TABLE accounts;
+ALTER TABLE records DROP COLUMN currency;
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+ALTER TABLE records ADD COLUMN currency TEXT NOT NULL DEFAULT 'EUR';
+
+CREATE TABLE accounts (
+    name TEXT PRIMARY KEY NOT NULL,
+    currency TEXT NOT NULL
+);
+
+CREATE TABLE exchange_rates (
+    id INTEGER PRIMARY KEY,
+    from_currency TEXT NOT NULL,
+    to_currency TEXT NOT NULL,
+    time TEXT NOT NULL,
+    rate REAL NOT NULL,
+    UNIQUE (from_currency, to_currency, time)
+);
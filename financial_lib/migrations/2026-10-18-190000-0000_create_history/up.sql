@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    description TEXT NOT NULL,
+    changes TEXT NOT NULL,
+    undone BOOLEAN NOT NULL DEFAULT 0
+);
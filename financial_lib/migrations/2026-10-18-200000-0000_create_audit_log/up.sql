@@ -0,0 +1,22 @@
+-- Your SQL goes here
+CREATE TABLE audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    record_id INTEGER NOT NULL,
+    operation TEXT NOT NULL,
+    before TEXT,
+    after TEXT,
+    time TEXT NOT NULL,
+    actor TEXT NOT NULL
+);
+
+CREATE INDEX audit_log_record_id ON audit_log (record_id);
+
+CREATE TRIGGER audit_log_no_update BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
+
+CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit log is append-only');
+END;
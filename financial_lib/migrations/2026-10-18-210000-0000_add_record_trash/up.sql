@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE records ADD COLUMN deleted_on TEXT;
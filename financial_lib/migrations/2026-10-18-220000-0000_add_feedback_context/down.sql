@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE suggestion_feedback DROP COLUMN note;
+ALTER TABLE suggestion_feedback DROP COLUMN account;
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE suggestion_feedback ADD COLUMN account TEXT;
+ALTER TABLE suggestion_feedback ADD COLUMN note TEXT;
@@ -0,0 +1,196 @@
+//! Agregácia záznamov podľa obdobia.
+//!
+//! Záznamy sa zoskupia podľa dňa, týždňa, mesiaca, štvrťroka alebo roka
+//! a voliteľne aj podľa kategórie, účtu alebo štítku. Pre každú skupinu
+//! ([`Bucket`]) sa vráti súčet príjmov, výdavkov, nákupu investícií,
+//! čistý výsledok a počet záznamov. Na týchto dátach sa dajú postaviť
+//! grafy aj reporty.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::record::{ExpenseType, MoneyType, Record};
+
+/// Dĺžka obdobia, podľa ktorého sa záznamy zoskupujú.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Period {
+    Day,
+    /// týždeň začínajúci pondelkom
+    Week,
+    Month,
+    /// štvrťrok začínajúci januárom, aprílom, júlom alebo októbrom
+    Quarter,
+    Year,
+}
+
+impl Period {
+    /// Vráti prvý deň obdobia, do ktorého patrí dátum.
+    pub fn start_of(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Day => date,
+            Period::Week => date - Days::new(date.weekday().num_days_from_monday() as u64),
+            Period::Month => date.with_day(1).expect("first day of month"),
+            Period::Quarter => {
+                let month = (date.month0() / 3) * 3 + 1;
+                NaiveDate::from_ymd_opt(date.year(), month, 1).expect("first day of quarter")
+            }
+            Period::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).expect("first day of year"),
+        }
+    }
+
+    /// Vráti prvý deň nasledujúceho obdobia.
+    pub fn next_start(&self, date: NaiveDate) -> NaiveDate {
+        let start = self.start_of(date);
+        match self {
+            Period::Day => start + Days::new(1),
+            Period::Week => start + Days::new(7),
+            Period::Month => start + Months::new(1),
+            Period::Quarter => start + Months::new(3),
+            Period::Year => start + Months::new(12),
+        }
+    }
+
+    /// Vráti posledný deň obdobia, do ktorého patrí dátum.
+    pub fn end_of(&self, date: NaiveDate) -> NaiveDate {
+        self.next_start(date) - Days::new(1)
+    }
+
+    /// Vráti začiatky všetkých období, ktoré zasahujú do rozsahu `from..=to`.
+    pub fn starts_between(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let mut ret = Vec::new();
+        let mut start = self.start_of(from);
+        while start <= to {
+            ret.push(start);
+            start = self.next_start(start);
+        }
+        ret
+    }
+}
+
+/// Podľa čoho sa záznamy v rámci obdobia ďalej delia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupBy {
+    Category,
+    Account,
+    /// záznam s viacerými štítkami sa započíta do každého z nich
+    Tag,
+}
+
+/// Kľúč skupiny v rámci obdobia.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GroupKey {
+    /// bez ďalšieho delenia
+    All,
+    /// kategória, `None` pre záznamy bez kategórie
+    Category(Option<ExpenseType>),
+    /// účet, `None` pre záznamy bez účtu
+    Account(Option<String>),
+    /// štítok, `None` pre záznamy bez štítkov
+    Tag(Option<String>),
+}
+
+impl GroupKey {
+    /// Vráti kľúče skupín, do ktorých záznam patrí.
+    fn of(record: &Record, group: Option<GroupBy>) -> Vec<GroupKey> {
+        match group {
+            None => vec![GroupKey::All],
+            Some(GroupBy::Category) => vec![GroupKey::Category(record.expense)],
+            Some(GroupBy::Account) => vec![GroupKey::Account(record.account.clone())],
+            Some(GroupBy::Tag) if record.tags.is_empty() => vec![GroupKey::Tag(None)],
+            Some(GroupBy::Tag) => record
+                .tags
+                .iter()
+                .map(|t| GroupKey::Tag(Some(t.clone())))
+                .collect(),
+        }
+    }
+}
+
+/// Súhrn záznamov jednej skupiny v jednom období.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bucket {
+    /// prvý deň obdobia
+    pub start: NaiveDate,
+    pub key: GroupKey,
+    /// súčet príjmov
+    pub income: f32,
+    /// súčet výdavkov bez nákupu investícií (kladné číslo)
+    pub expense: f32,
+    /// nákup investícií a iné výdavky, ktoré nie sú spotrebou (kladné číslo)
+    pub invested: f32,
+    /// príjmy mínus výdavky a investície, teda zmena zostatku
+    pub net: f32,
+    /// počet záznamov
+    pub count: usize,
+}
+
+impl Bucket {
+    /// Vytvorí prázdnu skupinu.
+    pub fn empty(start: NaiveDate, key: GroupKey) -> Bucket {
+        Bucket {
+            start,
+            key,
+            income: 0.0,
+            expense: 0.0,
+            invested: 0.0,
+            net: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Započíta jeden záznam do skupiny.
+    pub fn add(&mut self, record: &Record) {
+        self.add_sum(record.money_type, record.expense, record.amount, 1);
+    }
+
+    /// Započíta súčet viacerých záznamov rovnakého typu a kategórie do skupiny.
+    ///
+    /// Výdavky v kategórii, ktorá nie je spotrebou ([`ExpenseType::is_spending`]),
+    /// sa pripočítajú k `invested` namiesto `expense`.
+    pub fn add_sum(
+        &mut self,
+        money_type: MoneyType,
+        category: Option<ExpenseType>,
+        amount: f32,
+        count: usize,
+    ) {
+        match money_type {
+            MoneyType::INCOME => self.income += amount,
+            MoneyType::EXPENSE if category.is_some_and(|c| !c.is_spending()) => {
+                self.invested += amount
+            }
+            MoneyType::EXPENSE => self.expense += amount,
+        }
+        self.net = self.income - self.expense - self.invested;
+        self.count += count;
+    }
+}
+
+/// Zoskupí záznamy podľa obdobia a voliteľne podľa kategórie, účtu alebo štítku.
+///
+/// Vráti iba neprázdne skupiny zoradené podľa začiatku obdobia a kľúča.
+///
+/// # Arguments
+/// * `records` – záznamy na agregáciu
+/// * `period` – dĺžka obdobia
+/// * `group` – ďalšie delenie v rámci obdobia, `None` znamená žiadne
+pub fn aggregate<'a>(
+    records: impl IntoIterator<Item = &'a Record>,
+    period: Period,
+    group: Option<GroupBy>,
+) -> Vec<Bucket> {
+    let mut buckets: BTreeMap<(NaiveDate, GroupKey), Bucket> = BTreeMap::new();
+
+    for r in records {
+        let start = period.start_of(r.time);
+        for key in GroupKey::of(r, group) {
+            buckets
+                .entry((start, key.clone()))
+                .or_insert_with(|| Bucket::empty(start, key))
+                .add(r);
+        }
+    }
+
+    buckets.into_values().collect()
+}
@@ -0,0 +1,139 @@
+//! Audit log zmien záznamov.
+//!
+//! Každé vloženie, úprava a odstránenie záznamu cez [`crate::db`] sa
+//! zapíše do tabuľky `audit_log` spolu s hodnotami pred zmenou a po nej,
+//! časom a menom toho, kto zmenu urobil ([`actor`]). Tabuľka je len na
+//! pridávanie – databáza odmietne jej riadky upraviť alebo zmazať.
+//!
+//! Históriu jedného záznamu vráti [`crate::db::record_history`].
+
+use std::env;
+use std::fmt;
+
+use chrono::NaiveDateTime;
+
+use crate::config;
+use crate::record::Record;
+
+/// Formát času zmeny v databáze.
+pub const AUDIT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Meno používané, keď sa autor zmeny nedá zistiť.
+pub const UNKNOWN_ACTOR: &str = "unknown";
+
+/// Vráti meno autora zmien.
+///
+/// Použije premennú prostredia `FINANCE_ACTOR`,
+/// inak meno používateľa systému z `USER` alebo `USERNAME`.
+pub fn actor() -> String {
+    ["FINANCE_ACTOR", "USER", "USERNAME"]
+        .iter()
+        .find_map(|key| env::var(key).ok().filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| UNKNOWN_ACTOR.to_string())
+}
+
+/// Druh zmeny záznamu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl Operation {
+    /// Vráti kód operácie, ktorý sa ukladá do databázy.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Operation::Insert => "INSERT",
+            Operation::Update => "UPDATE",
+            Operation::Delete => "DELETE",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code().to_lowercase())
+    }
+}
+
+/// Jeden riadok audit logu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEntry {
+    pub id: i32,
+    /// ID zmeneného záznamu
+    pub record_id: i32,
+    pub operation: Operation,
+    /// záznam pred zmenou, `None` pri vložení
+    pub before: Option<Record>,
+    /// záznam po zmene, `None` pri odstránení
+    pub after: Option<Record>,
+    /// kedy sa zmena stala (miestny čas)
+    pub time: NaiveDateTime,
+    /// kto zmenu urobil
+    pub actor: String,
+}
+
+/// Zmena jednej položky záznamu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// názov položky
+    pub field: &'static str,
+    /// hodnota pred zmenou, prázdna ak záznam neexistoval
+    pub before: String,
+    /// hodnota po zmene, prázdna ak bol záznam odstránený
+    pub after: String,
+}
+
+/// Vráti položky záznamu ako dvojice názov a hodnota.
+///
+/// Dátumy sú vo formáte z nastavení ([`config::date_format`]).
+fn fields(record: &Record) -> [(&'static str, String); 10] {
+    let date_format = config::date_format();
+    [
+        ("type", record.money_type.code().to_string()),
+        ("amount", format!("{:.2}", record.amount)),
+        ("currency", record.currency.clone()),
+        (
+            "category",
+            record.expense.map_or("NONE".to_string(), |e| e.code().to_string()),
+        ),
+        ("date", record.time.format(date_format).to_string()),
+        ("account", record.account.clone().unwrap_or_default()),
+        ("tags", record.tags.join(", ")),
+        ("note", record.note.clone().unwrap_or_default()),
+        ("status", record.status.code().to_string()),
+        (
+            "deleted",
+            record
+                .deleted_on
+                .map_or(String::new(), |d| d.format(date_format).to_string()),
+        ),
+    ]
+}
+
+impl AuditEntry {
+    /// Vráti položky, ktoré sa zmenou zmenili.
+    ///
+    /// Pri vložení a odstránení sa vrátia všetky neprázdne položky.
+    pub fn changed_fields(&self) -> Vec<FieldChange> {
+        let record = self
+            .before
+            .as_ref()
+            .or(self.after.as_ref())
+            .expect("audit entry without record");
+        let names = fields(record).map(|(name, _)| name);
+        let values = |r: Option<&Record>| r.map(|r| fields(r).map(|(_, v)| v)).unwrap_or_default();
+        names
+            .into_iter()
+            .zip(values(self.before.as_ref()))
+            .zip(values(self.after.as_ref()))
+            .filter(|((_, before), after)| before != after)
+            .map(|((field, before), after)| FieldChange {
+                field,
+                before,
+                after,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,457 @@
+//! Nastavenia aplikácie zo súboru TOML.
+//!
+//! Súbor sa hľadá v `FINANCE_CONFIG`, inak v `finance/config.toml`
+//! v adresári nastavení používateľa (`$XDG_CONFIG_HOME`, zvyčajne
+//! `~/.config`). Ak súbor neexistuje, platia predvolené hodnoty.
+//! Hodnoty zo súboru sa dajú prepísať premennými prostredia a tie zasa
+//! prepínačmi príkazového riadku, ktoré si načíta každý program sám
+//! a uplatní cez [`Config::apply_flags`] pred [`install`]. Súbor `.env`
+//! v pracovnom adresári sa nečíta, nastavenia teda nezávisia od toho,
+//! odkiaľ sa program spustí.
+//!
+//! ```toml
+//! [database]
+//! # relatívna cesta je voči adresáru tohto súboru   (DATABASE_URL, --db)
+//! path = "~/finance/database.db"
+//!
+//! [defaults]
+//! account = "card"              # FINANCE_ACCOUNT, --default-account
+//! currency = "EUR"              # BASE_CURRENCY, --base-currency
+//! date_format = "%d.%m.%Y"      # FINANCE_DATE_FORMAT, --date-format
+//! language = "sk"               # FINANCE_LANGUAGE, --language: en alebo sk
+//!
+//! [trash]
+//! retention_days = 30           # TRASH_RETENTION_DAYS
+//!
+//! [ui]
+//! tick_rate = 30.0              # FINANCE_TICK_RATE, snímok za sekundu
+//! monthly_view = false          # po spustení ukázať mesačný prehľad
+//! forecast_days = 90            # po spustení ukázať predpoveď zostatku
+//! theme = "light"               # FINANCE_THEME: dark, light, high-contrast alebo súbor
+//!
+//! [keys.normal]                 # klávesové skratky TUI podľa režimu
+//! help = ["?"]
+//! ```
+//!
+//! Bez nastavenej databázy sa použije `finance/database.db` v adresári
+//! dát používateľa (`$XDG_DATA_HOME`, zvyčajne `~/.local/share`), aplikácia
+//! teda funguje bez ohľadu na pracovný adresár.
+//!
+//! Staršie verzie brali cestu k databáze zo súboru `.env` v koreni
+//! repozitára ([`LEGACY_DATABASE`]), ten sa už nečíta. Kto chce túto
+//! databázu používať ďalej, nastaví ju v `[database] path`, v `DATABASE_URL`
+//! alebo prepínačom `--db`. Kým to neurobí, programy na ňu upozornia
+//! (pozri [`Config::legacy_database`]).
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::NaiveDate;
+use chrono::format::{Item, StrftimeItems};
+use serde::Deserialize;
+
+use crate::currency::DEFAULT_CURRENCY;
+use crate::i18n::{Language, MESSAGES, fill};
+use crate::trash::DEFAULT_RETENTION_DAYS;
+
+/// Predvolený formát dátumu na zobrazenie a zadávanie.
+pub const DEFAULT_DATE_FORMAT: &str = "%d.%m.%Y";
+
+/// Predvolený počet snímok TUI za sekundu.
+pub const DEFAULT_TICK_RATE: f64 = 30.0;
+
+/// Najmenší povolený počet snímok TUI za sekundu.
+pub const MIN_TICK_RATE: f64 = 1.0;
+
+/// Najväčší povolený počet snímok TUI za sekundu.
+pub const MAX_TICK_RATE: f64 = 240.0;
+
+/// Databáza, ktorú používali staršie verzie podľa súboru `.env`,
+/// relatívne voči pracovnému adresáru.
+pub const LEGACY_DATABASE: &str = "financial_lib/database.db";
+
+/// Predvolená farebná téma TUI.
+pub const DEFAULT_THEME: &str = "dark";
+
+/// Všetky nastavenia aplikácie.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub defaults: Defaults,
+    pub trash: TrashConfig,
+    pub ui: UiConfig,
+    /// klávesové skratky TUI: režim → príkaz → klávesy, kontroluje ich TUI
+    pub keys: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+}
+
+/// Umiestnenie databázy.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// cesta k súboru SQLite, `None` pre predvolené umiestnenie
+    pub path: Option<PathBuf>,
+}
+
+/// Predvolené hodnoty nových záznamov a zobrazenia.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Defaults {
+    /// účet predvyplnený pri pridávaní záznamu
+    pub account: Option<String>,
+    /// základná mena, do ktorej sa prepočítavajú súčty
+    pub currency: String,
+    /// formát dátumu pre `chrono` na zobrazenie aj zadávanie
+    pub date_format: String,
+    /// jazyk textov pre používateľa
+    pub language: Language,
+}
+
+impl Default for Defaults {
+    fn default() -> Self {
+        Self {
+            account: None,
+            currency: DEFAULT_CURRENCY.to_string(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            language: Language::default(),
+        }
+    }
+}
+
+/// Správanie koša.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrashConfig {
+    /// koľko dní ostáva záznam v koši pred automatickým vymazaním
+    pub retention_days: u64,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_RETENTION_DAYS,
+        }
+    }
+}
+
+/// Nastavenia TUI.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    /// počet snímok za sekundu, od [`MIN_TICK_RATE`] do [`MAX_TICK_RATE`]
+    pub tick_rate: f64,
+    /// po spustení sa namiesto grafu zostatku ukáže mesačný prehľad
+    pub monthly_view: bool,
+    /// dĺžka predpovede zostatku po spustení, `None` bez predpovede
+    pub forecast_days: Option<u32>,
+    /// farebná téma: názov vstavanej alebo vlastnej témy, či cesta k súboru `.toml`
+    pub theme: String,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            tick_rate: DEFAULT_TICK_RATE,
+            monthly_view: false,
+            forecast_days: None,
+            theme: DEFAULT_THEME.to_string(),
+        }
+    }
+}
+
+/// Nastavenia zadané prepínačmi príkazového riadku, `None` ak prepínač chýba.
+///
+/// Spoločné pre všetky programy, TUI má navyše vlastné prepínače zobrazenia.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Flags {
+    /// `--db`, relatívna cesta je voči pracovnému adresáru
+    pub database: Option<PathBuf>,
+    /// `--default-account`, prázdny reťazec zruší predvolený účet
+    pub account: Option<String>,
+    /// `--base-currency`
+    pub currency: Option<String>,
+    /// `--date-format`
+    pub date_format: Option<String>,
+    /// `--language`
+    pub language: Option<String>,
+}
+
+/// Chybné nastavenie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// odkiaľ hodnota pochádza: cesta k súboru alebo názov premennej
+    pub origin: String,
+    pub message: String,
+    /// jazyk, v ktorom je chyba napísaná; nastavenia ešte nie sú
+    /// nainštalované, program ho preto použije aj pri jej výpise
+    pub language: Language,
+}
+
+impl ConfigError {
+    fn new(origin: impl fmt::Display, message: impl Into<String>, language: Language) -> Self {
+        Self {
+            origin: origin.to_string(),
+            message: message.into(),
+            language,
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = MESSAGES.text_in(self.language, "config.error");
+        f.write_str(&fill(text, &[&self.origin, &self.message]))
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Vráti cestu k súboru nastavení, `None` ak sa adresár nastavení nedá zistiť.
+pub fn config_path() -> Option<PathBuf> {
+    match env::var_os("FINANCE_CONFIG") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => dirs::config_dir().map(|d| d.join("finance").join("config.toml")),
+    }
+}
+
+impl Config {
+    /// Načíta nastavenia zo súboru a prepíše ich premennými prostredia.
+    ///
+    /// # Arguments
+    /// * `path` – súbor zadaný používateľom; ten musí existovať, inak sa
+    ///   použije [`config_path`], ktorý chýbať smie
+    ///
+    /// # Errors
+    /// Ak sa súbor nedá prečítať alebo niektorá hodnota nie je platná.
+    pub fn load(path: Option<&Path>) -> Result<Config, ConfigError> {
+        let mut config = match path {
+            Some(path) => Config::from_file(path)?,
+            None => match config_path() {
+                Some(path) if path.exists() => Config::from_file(&path)?,
+                _ => Config::default(),
+            },
+        };
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    /// Načíta nastavenia zo súboru TOML.
+    ///
+    /// Relatívna cesta k databáze a k súboru témy sa vzťahuje na adresár súboru.
+    ///
+    /// # Errors
+    /// Ak sa súbor nedá prečítať, nie je platný TOML alebo obsahuje
+    /// neznámy kľúč či neplatnú hodnotu.
+    pub fn from_file(path: &Path) -> Result<Config, ConfigError> {
+        let origin = path.display();
+        let language = Language::default();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::new(&origin, e.to_string(), language))?;
+        let mut config: Config =
+            toml::from_str(&text).map_err(|e| ConfigError::new(&origin, e.message(), language))?;
+        config.check().map_err(|e| config.error(&origin, e))?;
+        if let Some(db) = &config.database.path {
+            config.database.path = Some(resolve(path, db));
+        }
+        if config.ui.theme.ends_with(".toml") {
+            let theme = resolve(path, Path::new(&config.ui.theme));
+            config.ui.theme = theme.to_string_lossy().into_owned();
+        }
+        Ok(config)
+    }
+
+    /// Prepíše nastavenia premennými prostredia, ktoré sú nastavené.
+    ///
+    /// # Errors
+    /// Ak premenná obsahuje neplatnú hodnotu.
+    pub fn apply_env(&mut self) -> Result<(), ConfigError> {
+        let var = |key: &str| env::var(key).ok().filter(|v| !v.trim().is_empty());
+        if let Some(url) = var("DATABASE_URL") {
+            self.database.path = Some(PathBuf::from(url));
+        }
+        if let Some(account) = var("FINANCE_ACCOUNT") {
+            self.defaults.account = Some(account);
+        }
+        if let Some(currency) = var("BASE_CURRENCY") {
+            self.defaults.currency = currency;
+        }
+        if let Some(format) = var("FINANCE_DATE_FORMAT") {
+            self.defaults.date_format = format;
+        }
+        if let Some(language) = var("FINANCE_LANGUAGE") {
+            self.defaults.language = language.parse().map_err(|_| {
+                self.error("FINANCE_LANGUAGE", self.text("config.language", &[&language]))
+            })?;
+        }
+        if let Some(days) = var("TRASH_RETENTION_DAYS") {
+            self.trash.retention_days = days.trim().parse().map_err(|_| {
+                self.error("TRASH_RETENTION_DAYS", self.text("config.retention_days", &[]))
+            })?;
+        }
+        if let Some(theme) = var("FINANCE_THEME") {
+            self.ui.theme = theme;
+        }
+        if let Some(rate) = var("FINANCE_TICK_RATE") {
+            self.ui.tick_rate = rate.trim().parse().map_err(|_| {
+                self.error("FINANCE_TICK_RATE", self.text("config.tick_rate", &[]))
+            })?;
+        }
+        self.check()
+            .map_err(|e| self.error(self.text("config.environment", &[]), e))
+    }
+
+    /// Prepíše nastavenia prepínačmi príkazového riadku, ktoré sú zadané.
+    ///
+    /// Volá sa po [`Config::load`], prepínače teda majú prednosť pred
+    /// súborom aj premennými prostredia.
+    ///
+    /// # Errors
+    /// Ak prepínač obsahuje neplatnú hodnotu.
+    pub fn apply_flags(&mut self, flags: &Flags) -> Result<(), ConfigError> {
+        if let Some(db) = &flags.database {
+            self.database.path = Some(db.clone());
+        }
+        if let Some(account) = &flags.account {
+            self.defaults.account = Some(account.trim().to_string()).filter(|a| !a.is_empty());
+        }
+        if let Some(currency) = &flags.currency {
+            self.defaults.currency = currency.clone();
+        }
+        if let Some(format) = &flags.date_format {
+            self.defaults.date_format = format.clone();
+        }
+        if let Some(language) = &flags.language {
+            self.defaults.language = language.parse().map_err(|_| {
+                self.error("--language", self.text("config.language", &[language]))
+            })?;
+        }
+        self.check()
+            .map_err(|e| self.error(self.text("config.flags", &[]), e))
+    }
+
+    /// Skontroluje hodnoty a zjednotí kód meny na veľké písmená.
+    fn check(&mut self) -> Result<(), String> {
+        self.defaults.currency = self.defaults.currency.trim().to_uppercase();
+        let code = &self.defaults.currency;
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+            return Err(self.text("config.currency", &[code]));
+        }
+        if !is_valid_date_format(&self.defaults.date_format) {
+            return Err(self.text("config.date_format", &[&self.defaults.date_format]));
+        }
+        if !(MIN_TICK_RATE..=MAX_TICK_RATE).contains(&self.ui.tick_rate) {
+            return Err(self.text(
+                "config.invalid_tick_rate",
+                &[&self.ui.tick_rate, &MIN_TICK_RATE, &MAX_TICK_RATE],
+            ));
+        }
+        if self.ui.forecast_days == Some(0) {
+            return Err(self.text("config.forecast_days", &[]));
+        }
+        Ok(())
+    }
+
+    /// Vráti text chyby v jazyku týchto nastavení s doplnenými hodnotami.
+    ///
+    /// Nastavenia sa ešte len načítavajú, [`crate::i18n::trf`] by ich
+    /// preto načítal znova.
+    fn text(&self, key: &str, args: &[&dyn fmt::Display]) -> String {
+        fill(MESSAGES.text_in(self.defaults.language, key), args)
+    }
+
+    /// Vytvorí chybu nastavenia v jazyku týchto nastavení.
+    fn error(&self, origin: impl fmt::Display, message: impl Into<String>) -> ConfigError {
+        ConfigError::new(origin, message, self.defaults.language)
+    }
+
+    /// Vráti cestu k databáze pre `diesel`.
+    ///
+    /// Bez nastavenej cesty je to `finance/database.db` v adresári dát
+    /// používateľa, ak sa nedá zistiť, tak `database.db` v pracovnom adresári.
+    pub fn database_url(&self) -> String {
+        let path = match &self.database.path {
+            Some(path) => path.clone(),
+            None => dirs::data_dir()
+                .map(|d| d.join("finance").join("database.db"))
+                .unwrap_or_else(|| PathBuf::from("database.db")),
+        };
+        path.to_string_lossy().into_owned()
+    }
+
+    /// Vráti databázu starších verzií ([`LEGACY_DATABASE`]), ak existuje
+    /// v pracovnom adresári a tieto nastavenia ju nepoužijú.
+    ///
+    /// Bez nastavenej cesty by sa namiesto nej potichu otvorila prázdna
+    /// databáza v predvolenom umiestnení, programy preto na ňu upozornia.
+    pub fn legacy_database(&self) -> Option<PathBuf> {
+        let legacy = PathBuf::from(LEGACY_DATABASE);
+        (self.database.path.is_none() && legacy.is_file()).then_some(legacy)
+    }
+}
+
+/// Určí, či je formát dátumu platný a dátum sa z neho dá spätne načítať.
+pub fn is_valid_date_format(format: &str) -> bool {
+    if StrftimeItems::new(format).any(|item| item == Item::Error) {
+        return false;
+    }
+    let sample = NaiveDate::from_ymd_opt(2026, 12, 31).expect("valid date");
+    NaiveDate::parse_from_str(&sample.format(format).to_string(), format)
+        .is_ok_and(|date| date == sample)
+}
+
+/// Vráti cestu zo súboru nastavení `config` vzťahujúcu sa na jeho adresár.
+fn resolve(config: &Path, path: &Path) -> PathBuf {
+    let path = expand_home(path);
+    match config.parent() {
+        Some(dir) if path.is_relative() => dir.join(path),
+        _ => path,
+    }
+}
+
+/// Nahradí `~` na začiatku cesty domovským adresárom.
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Nastaví nastavenia pre celý program.
+///
+/// Volá sa raz na začiatku programu, po spracovaní prepínačov.
+///
+/// # Panics
+/// Ak už nastavenia boli nastavené alebo použité.
+pub fn install(config: Config) {
+    CONFIG
+        .set(config)
+        .expect("configuration must be installed before it is used");
+}
+
+/// Vráti nastavenia programu.
+///
+/// Ak ich program nenastavil cez [`install`], načítajú sa cez
+/// [`Config::load`] pri prvom použití.
+///
+/// # Panics
+/// Ak je súbor nastavení alebo premenná prostredia neplatná.
+pub fn current() -> &'static Config {
+    CONFIG.get_or_init(|| Config::load(None).unwrap_or_else(|e| panic!("{}", e)))
+}
+
+/// Vráti formát dátumu na zobrazenie a zadávanie.
+pub fn date_format() -> &'static str {
+    &current().defaults.date_format
+}
+
+/// Prepíše formát dátumu do tvaru pre používateľa, napr. `%d.%m.%Y` na `dd.mm.yyyy`.
+pub fn date_hint(format: &str) -> String {
+    [("%d", "dd"), ("%m", "mm"), ("%Y", "yyyy"), ("%y", "yy"), ("%b", "mon")]
+        .iter()
+        .fold(format.to_string(), |hint, (spec, text)| hint.replace(spec, text))
+}
@@ -0,0 +1,219 @@
+//! Meny záznamov a prepočet kurzom.
+//!
+//! Každý záznam a účet má menu (kód ISO 4217, napr. `EUR`, `USD`).
+//! Kurzy sa ukladajú lokálne ([`ExchangeRate`]) a dajú sa načítať
+//! zo súboru CSV ([`parse_rates_csv`]). [`Converter`] prepočíta sumy
+//! do základnej meny kurzom platným ku dňu záznamu.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::config;
+use crate::i18n::trf;
+use crate::models::DB_DATE_FORMAT;
+use crate::record::{Record, RecordManager};
+
+/// Mena, ktorá sa použije, ak záznam ani účet menu nemá.
+pub const DEFAULT_CURRENCY: &str = "EUR";
+
+/// Vráti základnú menu, do ktorej sa prepočítavajú súčty.
+///
+/// Berie sa z nastavení (`defaults.currency` alebo premenná prostredia
+/// `BASE_CURRENCY`), inak je to [`DEFAULT_CURRENCY`].
+pub fn base_currency() -> String {
+    config::current().defaults.currency.clone()
+}
+
+/// Účet a jeho mena.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    pub name: String,
+    pub currency: String,
+}
+
+/// Kurz k danému dňu: 1 jednotka `from` = `rate` jednotiek `to`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRate {
+    pub from: String,
+    pub to: String,
+    pub time: NaiveDate,
+    pub rate: f32,
+}
+
+/// Chýbajúci kurz pri prepočte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingRate {
+    pub from: String,
+    pub to: String,
+    pub date: NaiveDate,
+}
+
+impl fmt::Display for MissingRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&trf("currency.missing_rate", &[&self.from, &self.to, &self.date]))
+    }
+}
+
+impl std::error::Error for MissingRate {}
+
+/// Prepočet súm do základnej meny.
+///
+/// Pre každý deň sa použije posledný kurz platný k tomuto dňu. Ak kurz
+/// pred dňom neexistuje, použije sa najstarší známy kurz. Ak priamy
+/// kurz chýba, skúsi sa opačný kurz a potom prepočet cez jednu
+/// sprostredkujúcu menu.
+#[derive(Debug, Clone, Default)]
+pub struct Converter {
+    /// základná mena
+    pub base: String,
+    /// kurzy podľa páru mien, zoradené podľa dátumu
+    rates: HashMap<(String, String), Vec<(NaiveDate, f32)>>,
+}
+
+impl Converter {
+    /// Vytvorí prepočet do meny `base` so zadanými kurzami.
+    pub fn new(base: impl Into<String>, rates: &[ExchangeRate]) -> Converter {
+        let mut ret = Converter {
+            base: base.into(),
+            rates: HashMap::new(),
+        };
+        for r in rates {
+            ret.rates
+                .entry((r.from.clone(), r.to.clone()))
+                .or_default()
+                .push((r.time, r.rate));
+        }
+        ret.rates.values_mut().for_each(|v| v.sort_by_key(|(d, _)| *d));
+        ret
+    }
+
+    /// Vráti kurz priamo zo zoznamu pre pár mien.
+    fn direct(&self, from: &str, to: &str, date: NaiveDate) -> Option<f32> {
+        let rates = self.rates.get(&(from.to_string(), to.to_string()))?;
+        let index = rates.partition_point(|(d, _)| *d <= date);
+        rates.get(index.saturating_sub(1)).map(|(_, r)| *r)
+    }
+
+    /// Vráti priamy alebo opačný kurz.
+    fn pair(&self, from: &str, to: &str, date: NaiveDate) -> Option<f32> {
+        self.direct(from, to, date)
+            .or_else(|| self.direct(to, from, date).map(|r| 1.0 / r))
+    }
+
+    /// Vráti kurz z meny `from` do meny `to` platný ku dňu `date`.
+    ///
+    /// # Errors
+    /// Vráti [`MissingRate`], ak sa kurz nedá zistiť.
+    pub fn rate(&self, from: &str, to: &str, date: NaiveDate) -> Result<f32, MissingRate> {
+        if from == to {
+            return Ok(1.0);
+        }
+        if let Some(rate) = self.pair(from, to, date) {
+            return Ok(rate);
+        }
+        let mut currencies: Vec<&String> = self.rates.keys().flat_map(|(a, b)| [a, b]).collect();
+        currencies.sort();
+        currencies.dedup();
+        currencies
+            .into_iter()
+            .find_map(|via| Some(self.pair(from, via, date)? * self.pair(via, to, date)?))
+            .ok_or_else(|| MissingRate {
+                from: from.to_string(),
+                to: to.to_string(),
+                date,
+            })
+    }
+
+    /// Prepočíta sumu v mene `currency` ku dňu `date` do základnej meny.
+    ///
+    /// # Errors
+    /// Vráti [`MissingRate`], ak sa kurz nedá zistiť.
+    pub fn convert(&self, amount: f32, currency: &str, date: NaiveDate) -> Result<f32, MissingRate> {
+        Ok(amount * self.rate(currency, &self.base, date)?)
+    }
+
+    /// Vráti kópiu záznamov so sumami prepočítanými do základnej meny.
+    ///
+    /// Na výsledku fungujú všetky súčty [`RecordManager`] v základnej mene.
+    ///
+    /// # Errors
+    /// Vráti [`MissingRate`] pre prvý záznam, ktorý sa nedá prepočítať.
+    pub fn convert_all(&self, manager: &RecordManager) -> Result<RecordManager, MissingRate> {
+        manager
+            .get_all()
+            .into_iter()
+            .map(|r| {
+                Ok(Record {
+                    amount: self.convert(r.amount, &r.currency, r.time)?,
+                    currency: self.base.clone(),
+                    ..r
+                })
+            })
+            .collect()
+    }
+}
+
+/// Chyba pri načítaní kurzov zo súboru.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateImportError {
+    /// číslo riadku (od 1)
+    pub line: usize,
+    /// popis chyby
+    pub message: String,
+}
+
+impl fmt::Display for RateImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&trf("import.line", &[&self.line, &self.message]))
+    }
+}
+
+impl std::error::Error for RateImportError {}
+
+/// Načíta kurzy z CSV v tvare `z_meny,do_meny,YYYY-MM-DD,kurz`.
+///
+/// Prázdne riadky a hlavička začínajúca slovom `from` sa preskočia.
+///
+/// # Errors
+/// Vráti [`RateImportError`] s číslom prvého chybného riadku.
+pub fn parse_rates_csv(input: &str) -> Result<Vec<ExchangeRate>, RateImportError> {
+    let mut ret = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line.to_lowercase().starts_with("from")) {
+            continue;
+        }
+        let error = |message: String| RateImportError {
+            line: i + 1,
+            message,
+        };
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        let [from, to, time, rate] = fields.as_slice() else {
+            return Err(error(trf(
+                "import.field_count",
+                &[&4, &"from,to,date,rate", &fields.len()],
+            )));
+        };
+        for code in [from, to] {
+            if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(error(trf("record.invalid_currency", &[code])));
+            }
+        }
+        let time = NaiveDate::parse_from_str(time, DB_DATE_FORMAT)
+            .map_err(|_| error(trf("import.invalid_date", &[time])))?;
+        let rate: f32 = rate
+            .parse()
+            .ok()
+            .filter(|r: &f32| r.is_finite() && *r > 0.0)
+            .ok_or_else(|| error(trf("import.invalid_rate", &[rate])))?;
+        ret.push(ExchangeRate {
+            from: from.to_uppercase(),
+            to: to.to_uppercase(),
+            time,
+            rate,
+        });
+    }
+    Ok(ret)
+}
@@ -2,45 +2,87 @@
 //!
 //! Tento modul zabezpečuje:
 //! - pripojenie k SQLite databáze
-//! - vkladanie, mazanie a aktualizáciu záznamov
+//! - vkladanie, mazanie a aktualizáciu záznamov (odsúhlasené sú zamknuté)
+//! - kôš odstránených záznamov s obnovením a vymazaním po uplynutí lehoty
+//! - zmenu stavu záznamov
+//! - hromadné zmeny záznamov v jednej transakcii s históriou pre undo/redo
+//! - audit log každej zmeny záznamu s autorom a časom
 //! - načítanie záznamov do pamäte
+//! - filtrovanie záznamov priamo v SQL
+//! - agregácie (zostatok, súčty kategórií, súčty za obdobia) cez SQL `GROUP BY`,
+//!   prepočítané do základnej meny
+//! - správu účtov, ich mien a kurzov
+//! - ukladanie spätnej väzby k návrhom kategórií
+//! - správu majetku, záväzkov a ich ocenení
+//! - správu úverov
+//! - ukladanie obchodov a cien cenných papierov
 //!
 //! Používa knižnicu **Diesel** a databázu **SQLite**.
 //!
-//! Databázová cesta sa načítava z premennej prostredia
-//! `DATABASE_URL`.
+//! Cestu k databáze určujú nastavenia (`[database] path`, `DATABASE_URL`,
+//! `--db`), pozri [`crate::config`].
 
+use crate::aggregate::{Bucket, GroupBy, GroupKey, Period};
+use crate::audit::{AuditEntry, actor};
+use crate::currency::{Account, Converter, ExchangeRate, MissingRate, base_currency};
+use crate::history::{Action, Change, HISTORY_LIMIT, HistoryError};
 use crate::schema;
 use crate::schema::records::dsl::{id, records};
-use crate::schema::records::{amount, expense, money_type, time};
+use crate::schema::records::{
+    account, amount, currency, deleted_on, expense, money_type, note, status, tags, time,
+};
+use diesel::dsl::sql;
 use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Double, Integer, Nullable, Text};
 
 use diesel::sqlite::SqliteConnection;
-use dotenvy::dotenv;
-use std::env;
+use std::collections::BTreeMap;
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
+use chrono::{Local, NaiveDate};
+use std::path::Path;
 
-use crate::models::dbRecord;
-use crate::record::{Record, RecordManager};
+use crate::config;
 
+use crate::investment::{Price, Trade, TradeError};
+use crate::loan::Loan;
+use crate::models::{
+    DB_DATE_FORMAT, DbAccount, DbAsset, DbAuditEntry, DbExchangeRate, DbFeedback, DbHistory,
+    DbLoan, DbPrice, DbRecord, DbTrade, DbValuation, TAG_SEPARATOR,
+};
+use crate::networth::{Asset, Valuation};
+use crate::query::RecordFilter;
+use crate::reconcile::RecordStatus;
+use crate::record::{ExpenseType, MoneyType, Record, RecordManager};
+use crate::suggest::Feedback;
+use crate::trash::purge_before;
 
+/// Migrácie zabudované do knižnice, spúšťajú sa pri každom pripojení.
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
 
 /// Vytvorí a vráti spojenie so SQLite databázou.
 ///
-/// Cesta k databáze sa načítava z premennej prostredia
-/// `DATABASE_URL`.
+/// Cesta k databáze sa berie z nastavení ([`config::Config::database_url`]),
+/// chýbajúci adresár sa vytvorí. Pred vrátením spojenia sa spustia
+/// všetky ešte nevykonané migrácie.
 ///
 /// # Panics
-/// Ak premenná `DATABASE_URL` nie je nastavená alebo
-/// ak sa nepodarí pripojiť k databáze.
+/// Ak sa nepodarí pripojiť k databáze alebo ak zlyhá migrácia.
 pub fn establish_connection() -> SqliteConnection {
-    dotenv().ok();
-
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    SqliteConnection::establish(&database_url)
-        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url))
+    let database_url = config::current().database_url();
+    if let Some(dir) = Path::new(&database_url).parent()
+        && !database_url.starts_with("file:")
+        && !dir.as_os_str().is_empty()
+    {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let mut conn = SqliteConnection::establish(&database_url)
+        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url));
+    conn.run_pending_migrations(MIGRATIONS)
+        .expect("Error running database migrations");
+    conn
 }
 
-/// Vloží nový záznam do databázy.
+/// Vloží nový záznam do databázy a zapíše ho do audit logu.
 ///
 /// # Argumenty
 /// * `record` – záznam, ktorý sa má uložiť
@@ -49,15 +91,39 @@ pub fn establish_connection() -> SqliteConnection {
 /// Ak sa nepodarí vykonať SQL INSERT.
 pub fn insert_record(record: &Record) {
     let conn = &mut establish_connection();
-    let insert_record: dbRecord = record.into();
+    let insert_record: DbRecord = record.into();
 
-    diesel::insert_into(schema::records::table)
-        .values(insert_record)
-        .execute(conn)
-        .expect("Error inserting into db");
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        diesel::insert_into(schema::records::table)
+            .values(&insert_record)
+            .execute(conn)?;
+        write_audit(conn, None, Some(&insert_record), &actor())
+    })
+    .expect("Error inserting into db");
+}
+
+/// Načíta jeden riadok tabuľky `records` v rámci otvoreného pripojenia.
+fn load_row(conn: &mut SqliteConnection, record_id: i32) -> QueryResult<Option<DbRecord>> {
+    records.filter(id.eq(record_id)).first::<DbRecord>(conn).optional()
+}
+
+/// Zapíše zmenu záznamu do audit logu.
+///
+/// Operácia sa určí podľa toho, či záznam existoval pred zmenou a po nej.
+fn write_audit(
+    conn: &mut SqliteConnection,
+    before: Option<&DbRecord>,
+    after: Option<&DbRecord>,
+    actor: &str,
+) -> QueryResult<()> {
+    let entry = DbAuditEntry::new(before, after, Local::now().naive_local(), actor);
+    diesel::insert_into(schema::audit_log::table)
+        .values(entry)
+        .execute(conn)?;
+    Ok(())
 }
 
-/// Načíta všetky záznamy z databázy.
+/// Načíta všetky záznamy z databázy okrem záznamov v koši.
 ///
 /// Záznamy sú prevedené na typ [`Record`] a uložené
 /// do [`RecordManager`].
@@ -66,76 +132,938 @@ pub fn insert_record(record: &Record) {
 /// `RecordManager` obsahujúci všetky záznamy.
 pub fn load_records() -> RecordManager {
     let conn = &mut establish_connection();
-    let mut Rm = RecordManager::new();
-    let vec: Vec<dbRecord> = records.load(conn).expect("Error loading from db");
+    let mut rm = RecordManager::new();
+    let vec: Vec<DbRecord> = records
+        .filter(deleted_on.is_null())
+        .load(conn)
+        .expect("Error loading from db");
     let normal = vec.iter().map(|r| r.into());
     for r in normal {
-        Rm.add_record(r);
+        rm.add_record(r);
     }
-    Rm
+    rm
+}
+/// Načíta z databázy záznamy vyhovujúce filtru.
+///
+/// Filtrovanie, triedenie aj limit sa vykonajú priamo v SQL,
+/// výsledok zodpovedá [`RecordFilter::apply`] nad všetkými záznamami.
+///
+/// # Argumenty
+/// * `filter` – podmienky výberu
+pub fn query_records(filter: &RecordFilter) -> Vec<Record> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbRecord> = filter
+        .to_query()
+        .load(conn)
+        .expect("Error querying records from db");
+    vec.iter().map(|r| r.into()).collect()
 }
 /// Aktualizuje existujúci záznam v databáze.
 ///
-/// Záznam je identifikovaný pomocou jeho `id`.
+/// Záznam je identifikovaný pomocou jeho `id`. Stav záznamu sa nemení,
+/// na to slúži [`set_status`]. Odsúhlasený záznam sa neupraví.
+/// Zmena sa zapíše do audit logu.
 ///
 /// # Argumenty
 /// * `record` – záznam s novými hodnotami
-pub fn update_record(record: &Record) {
+///
+/// # Returns
+/// `true`, ak sa záznam upravil, `false`, ak neexistuje alebo je zamknutý.
+pub fn update_record(record: &Record) -> bool {
     let conn = &mut establish_connection();
-    let update_record: dbRecord = record.into();
+    let update_record: DbRecord = record.into();
 
-    diesel::update(records.filter(id.eq(record.id)))
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let before = load_row(conn, record.id)?;
+        let updated = diesel::update(
+            records
+                .filter(id.eq(record.id))
+                .filter(status.ne(RecordStatus::Reconciled.code())),
+        )
         .set((
             money_type.eq(update_record.money_type),
             amount.eq(update_record.amount),
             expense.eq(update_record.expense),
             time.eq(update_record.time),
+            account.eq(update_record.account),
+            tags.eq(update_record.tags),
+            note.eq(update_record.note),
+            currency.eq(update_record.currency),
         ))
-        .execute(conn)
-        .expect("Error updating db");
+        .execute(conn)?
+            > 0;
+        if updated {
+            let after = load_row(conn, record.id)?;
+            write_audit(conn, before.as_ref(), after.as_ref(), &actor())?;
+        }
+        Ok(updated)
+    })
+    .expect("Error updating db")
 }
-/// Odstráni záznam z databázy.
+/// Presunie záznam do koša a zapíše to do audit logu.
+/// Odsúhlasený záznam sa neodstráni.
+///
+/// Záznam ostane v databáze s dátumom odstránenia, dá sa obnoviť cez
+/// [`restore_record`] a natrvalo sa vymaže cez [`purge_record`] alebo
+/// po uplynutí lehoty cez [`purge_expired_trash`].
 ///
 /// # Argumenty
 /// * `record` – záznam, ktorý sa má odstrániť
-pub fn delete_record(record: Record) {
+///
+/// # Returns
+/// `true`, ak sa záznam odstránil, `false`, ak neexistuje, je už v koši
+/// alebo je zamknutý.
+pub fn delete_record(record: Record) -> bool {
+    let today = Local::now().date_naive();
+    set_deleted_on(record.id, Some(today))
+}
+/// Nastaví alebo zruší dátum odstránenia záznamu a zapíše zmenu do audit logu.
+///
+/// Odsúhlasené záznamy sa nemenia.
+fn set_deleted_on(record_id: i32, day: Option<NaiveDate>) -> bool {
     let conn = &mut establish_connection();
-    diesel::delete(records.filter(id.eq(record.id)))
-        .execute(conn)
-        .expect("Error deleting record from db");
+    let day = day.map(|d| d.format(DB_DATE_FORMAT).to_string());
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let before = load_row(conn, record_id)?;
+        let target = records
+            .filter(id.eq(record_id))
+            .filter(status.ne(RecordStatus::Reconciled.code()));
+        let changed = match &day {
+            Some(_) => diesel::update(target.filter(deleted_on.is_null()))
+                .set(deleted_on.eq(&day))
+                .execute(conn)?,
+            None => diesel::update(target.filter(deleted_on.is_not_null()))
+                .set(deleted_on.eq(&day))
+                .execute(conn)?,
+        } > 0;
+        if changed {
+            let after = load_row(conn, record_id)?;
+            write_audit(conn, before.as_ref(), after.as_ref(), &actor())?;
+        }
+        Ok(changed)
+    })
+    .expect("Error moving record to trash in db")
+}
+/// Načíta záznamy v koši, naposledy odstránené ako prvé.
+pub fn load_trash() -> Vec<Record> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbRecord> = records
+        .filter(deleted_on.is_not_null())
+        .order((deleted_on.desc(), id.desc()))
+        .load(conn)
+        .expect("Error loading trash from db");
+    vec.iter().map(|r| r.into()).collect()
+}
+/// Obnoví záznam z koša a zapíše to do audit logu.
+///
+/// # Argumenty
+/// * `record_id` – ID záznamu v koši
+///
+/// # Returns
+/// `true`, ak sa záznam obnovil, `false`, ak v koši nie je.
+pub fn restore_record(record_id: i32) -> bool {
+    set_deleted_on(record_id, None)
+}
+/// Natrvalo vymaže záznam z koša a zapíše to do audit logu.
+///
+/// Záznamy mimo koša sa nevymažú, najprv sa musia odstrániť
+/// cez [`delete_record`].
+///
+/// # Argumenty
+/// * `record_id` – ID záznamu v koši
+///
+/// # Returns
+/// `true`, ak sa záznam vymazal, `false`, ak v koši nie je.
+pub fn purge_record(record_id: i32) -> bool {
+    let conn = &mut establish_connection();
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let before = load_row(conn, record_id)?;
+        let purged = diesel::delete(
+            records
+                .filter(id.eq(record_id))
+                .filter(deleted_on.is_not_null()),
+        )
+        .execute(conn)?
+            > 0;
+        if purged {
+            write_audit(conn, before.as_ref(), None, &actor())?;
+        }
+        Ok(purged)
+    })
+    .expect("Error purging record from db")
+}
+/// Natrvalo vymaže záznamy, ktoré sú v koši dlhšie než lehota.
+///
+/// Každé vymazanie sa zapíše do audit logu.
+///
+/// # Argumenty
+/// * `today` – dnešný dátum
+/// * `retention` – lehota v dňoch, pozri [`crate::trash::retention_days`]
+///
+/// # Returns
+/// Počet vymazaných záznamov.
+pub fn purge_expired_trash(today: NaiveDate, retention: u64) -> usize {
+    let Some(limit) = purge_before(today, retention) else {
+        return 0;
+    };
+    let limit = limit.format(DB_DATE_FORMAT).to_string();
+    let conn = &mut establish_connection();
+    let actor = actor();
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        let expired: Vec<DbRecord> = records
+            .filter(deleted_on.lt(limit))
+            .load(conn)?;
+        for row in &expired {
+            diesel::delete(records.filter(id.eq(row.id))).execute(conn)?;
+            write_audit(conn, Some(row), None, &actor)?;
+        }
+        Ok(expired.len())
+    })
+    .expect("Error purging expired trash from db")
+}
+/// Nastaví stav záznamom. Jediný spôsob, ako odomknúť odsúhlasený záznam.
+///
+/// Každá skutočná zmena stavu sa zapíše do audit logu.
+///
+/// # Argumenty
+/// * `ids` – ID záznamov
+/// * `new_status` – nový stav
+pub fn set_status(ids: &[i32], new_status: RecordStatus) {
+    let conn = &mut establish_connection();
+    let actor = actor();
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        for record_id in ids {
+            let before = load_row(conn, *record_id)?;
+            if before.as_ref().is_none_or(|r| r.status == new_status.code()) {
+                continue;
+            }
+            diesel::update(records.filter(id.eq(record_id)))
+                .set(status.eq(new_status.code()))
+                .execute(conn)?;
+            let after = load_row(conn, *record_id)?;
+            write_audit(conn, before.as_ref(), after.as_ref(), &actor)?;
+        }
+        Ok(())
+    })
+    .expect("Error updating record status in db");
 }
-/// Prečísluje všetky záznamy v databáze.
+/// Vráti ďalšie dostupné ID záznamu.
 ///
-/// Záznamy sú:
-/// 1. Načítané do pamäte
-/// 2. Vymazané z databázy
-/// 3. Znovu vložené s novým ID od 1
+/// ID je o jedna väčšie než najväčšie ID v databáze. Záznamy sa
+/// neprečíslovávajú, aby sa na ne dala odvolávať história zmien.
 ///
-///  Používa sa pri mazaniach na zachovanie
-/// postupného číslovania.
-pub fn renumber_records_db() {
-    let old = load_records();
+/// # Returns
+/// Nové ID záznamu.
+pub fn get_next_id() -> i32 {
+    next_id(&mut establish_connection()).expect("Error reading next record id from db")
+}
+/// [`get_next_id`] v rámci otvoreného pripojenia.
+fn next_id(conn: &mut SqliteConnection) -> QueryResult<i32> {
+    diesel::select(sql::<Integer>("COALESCE((SELECT MAX(id) FROM records), 0) + 1")).get_result(conn)
+}
 
-    for r in old.get_all() {
-        delete_record(r);
+/// Uplatní zmeny záznamov v rámci otvorenej transakcie.
+///
+/// Každý záznam musí byť v stave pred zmenou, inak sa vráti
+/// [`HistoryError::Conflict`]. Zmena odsúhlaseného záznamu (okrem zmeny
+/// stavu) vráti [`HistoryError::Locked`], chyba databázy
+/// [`HistoryError::Database`].
+///
+/// # Argumenty
+/// * `inserted` – ID záznamov, ktoré pridala vracaná akcia; ich
+///   odstránenie zámok neblokuje
+fn apply_changes(
+    conn: &mut SqliteConnection,
+    changes: &[Change],
+    inserted: &[i32],
+) -> Result<(), HistoryError> {
+    let actor = actor();
+    for change in changes {
+        let change_id = change.id();
+        let removes_inserted = change.after.is_none() && inserted.contains(&change_id);
+        if change.touches_locked() && !removes_inserted {
+            return Err(HistoryError::Locked { id: change_id });
+        }
+        let current_row = load_row(conn, change_id)?;
+        let current: Option<Record> = current_row.as_ref().map(|r| r.into());
+        if current != change.before {
+            return Err(HistoryError::Conflict { id: change_id });
+        }
+        let new_row: Option<DbRecord> = change.after.as_ref().map(|r| r.into());
+        match &new_row {
+            Some(row) => diesel::replace_into(records).values(row).execute(conn)?,
+            None => diesel::delete(records.filter(id.eq(change_id))).execute(conn)?,
+        };
+        write_audit(conn, current_row.as_ref(), new_row.as_ref(), &actor)?;
     }
+    Ok(())
+}
 
-    let old_records = old.get_all();
-    let mut indexer = 1;
-    for mut r in old_records {
-        r.id = indexer;
-        indexer += 1;
-        insert_record(&r);
+impl From<diesel::result::Error> for HistoryError {
+    fn from(error: diesel::result::Error) -> HistoryError {
+        HistoryError::Database {
+            message: error.to_string(),
+        }
     }
 }
-/// Vráti ďalšie dostupné ID záznamu.
+
+/// Spustí `body` v transakcii, ktorá sa pri chybe z `body` vráti späť.
 ///
-/// ID je určené na základe počtu záznamov
-/// uložených v databáze.
+/// Chyba sa vráti volajúcemu, chyba samotnej databázy skončí panikou.
+fn rollback_transaction<T, E>(
+    body: impl FnOnce(&mut SqliteConnection) -> Result<T, E>,
+) -> Result<T, E> {
+    let conn = &mut establish_connection();
+    let mut outcome = None;
+    let result = conn.transaction(|conn| match body(conn) {
+        Ok(value) => {
+            outcome = Some(Ok(value));
+            Ok(())
+        }
+        Err(e) => {
+            outcome = Some(Err(e));
+            Err(diesel::result::Error::RollbackTransaction)
+        }
+    });
+    match result {
+        Ok(()) | Err(diesel::result::Error::RollbackTransaction) => {}
+        Err(e) => panic!("Error running transaction in db: {}", e),
+    }
+    outcome.expect("transaction body always runs")
+}
+
+/// Uplatní akciu na záznamoch a uloží ju do histórie.
+///
+/// Je to jediné rozhranie na hromadné zmeny záznamov: všetky zmeny akcie
+/// (vloženia, úpravy, presuny do koša aj trvalé vymazania) sa uplatnia
+/// v jednej transakcii a každá sa zapíše do audit logu. Ak niektorá zlyhá,
+/// aj pre chybu samotnej databázy, nezmení sa nič. Vrátené akcie,
+/// ktoré sa ešte dali zopakovať, sa z histórie zahodia a uchová sa
+/// najviac [`HISTORY_LIMIT`] posledných akcií.
+///
+/// # Argumenty
+/// * `action` – akcia používateľa so zmenami záznamov
+///
+/// # Errors
+/// Vráti [`HistoryError`], ak sa niektorá zmena nedá uplatniť; vtedy
+/// sa nezmení nič.
+pub fn commit(action: &Action) -> Result<(), HistoryError> {
+    rollback_transaction(|conn| {
+        apply_changes(conn, &action.changes, &[])?;
+        let row: DbHistory = action.into();
+        diesel::delete(schema::history::table.filter(schema::history::undone.eq(true)))
+            .execute(conn)?;
+        diesel::insert_into(schema::history::table)
+            .values(row)
+            .execute(conn)?;
+        // najstaršia akcia, ktorá sa ešte uchová
+        let oldest_kept: Option<Option<i32>> = schema::history::table
+            .select(schema::history::id)
+            .order(schema::history::id.desc())
+            .offset(HISTORY_LIMIT as i64 - 1)
+            .first(conn)
+            .optional()?;
+        if let Some(Some(oldest)) = oldest_kept {
+            diesel::delete(schema::history::table.filter(schema::history::id.lt(oldest)))
+                .execute(conn)?;
+        }
+        Ok(())
+    })
+}
+
+/// Vráti späť poslednú akciu, ktorá ešte nebola vrátená.
+///
+/// Záznamy, ktoré akcia pridala, sa odstránia aj vtedy, keď boli pridané
+/// ako odsúhlasené (napr. importom).
 ///
 /// # Returns
-/// Nové ID záznamu.
-pub fn get_next_id() -> i32 {
-    let ret = load_records().get_all().len() + 1;
-    ret as i32
+/// Vrátenú akciu, alebo `None`, ak nie je čo vrátiť.
+///
+/// # Errors
+/// Vráti [`HistoryError`], ak sa záznamy medzičasom zmenili.
+pub fn undo() -> Result<Option<Action>, HistoryError> {
+    rollback_transaction(|conn| {
+        let Some(row) = schema::history::table
+            .filter(schema::history::undone.eq(false))
+            .order(schema::history::id.desc())
+            .first::<DbHistory>(conn)
+            .optional()?
+        else {
+            return Ok(None);
+        };
+        let action: Action = (&row).into();
+        apply_changes(conn, &action.inverse(), &action.inserted_ids())?;
+        set_undone(conn, action.id, true)?;
+        Ok(Some(action))
+    })
+}
+
+/// Zopakuje naposledy vrátenú akciu.
+///
+/// # Returns
+/// Zopakovanú akciu, alebo `None`, ak nie je čo zopakovať.
+///
+/// # Errors
+/// Vráti [`HistoryError`], ak sa záznamy medzičasom zmenili.
+pub fn redo() -> Result<Option<Action>, HistoryError> {
+    rollback_transaction(|conn| {
+        let Some(row) = schema::history::table
+            .filter(schema::history::undone.eq(true))
+            .order(schema::history::id.asc())
+            .first::<DbHistory>(conn)
+            .optional()?
+        else {
+            return Ok(None);
+        };
+        let action: Action = (&row).into();
+        apply_changes(conn, &action.changes, &[])?;
+        set_undone(conn, action.id, false)?;
+        Ok(Some(action))
+    })
+}
+
+/// Označí akciu v histórii ako vrátenú alebo zopakovanú.
+fn set_undone(conn: &mut SqliteConnection, action_id: i32, value: bool) -> QueryResult<()> {
+    diesel::update(schema::history::table.filter(schema::history::id.eq(action_id)))
+        .set(schema::history::undone.eq(value))
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Načíta celú históriu akcií od najstaršej.
+pub fn load_history() -> Vec<Action> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbHistory> = schema::history::table
+        .order(schema::history::id)
+        .load(conn)
+        .expect("Error loading history from db");
+    vec.iter().map(|h| h.into()).collect()
+}
+
+/// Vráti históriu zmien záznamu z audit logu od najstaršej.
+///
+/// # Argumenty
+/// * `record_id` – ID záznamu
+pub fn record_history(record_id: i32) -> Vec<AuditEntry> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbAuditEntry> = schema::audit_log::table
+        .filter(schema::audit_log::record_id.eq(record_id))
+        .order(schema::audit_log::id)
+        .load(conn)
+        .expect("Error loading audit log from db");
+    vec.iter().map(|e| e.into()).collect()
+}
+
+/// Načíta celý audit log od najstaršej zmeny.
+pub fn load_audit_log() -> Vec<AuditEntry> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbAuditEntry> = schema::audit_log::table
+        .order(schema::audit_log::id)
+        .load(conn)
+        .expect("Error loading audit log from db");
+    vec.iter().map(|e| e.into()).collect()
+}
+
+/// Uloží spätnú väzbu používateľa k navrhnutej kategórii.
+///
+/// # Argumenty
+/// * `feedback` – prijatý alebo odmietnutý návrh
+pub fn insert_feedback(feedback: &Feedback) {
+    let conn = &mut establish_connection();
+    let insert_feedback: DbFeedback = feedback.into();
+
+    diesel::insert_into(schema::suggestion_feedback::table)
+        .values(insert_feedback)
+        .execute(conn)
+        .expect("Error inserting feedback into db");
+}
+
+/// Načíta všetku uloženú spätnú väzbu k návrhom kategórií.
+pub fn load_feedback() -> Vec<Feedback> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbFeedback> = schema::suggestion_feedback::table
+        .load(conn)
+        .expect("Error loading feedback from db");
+    vec.iter().map(|f| f.into()).collect()
+}
+
+/// Uloží nový majetok alebo záväzok a vráti jeho ID.
+///
+/// # Argumenty
+/// * `asset` – položka na uloženie
+pub fn insert_asset(asset: &Asset) -> i32 {
+    let conn = &mut establish_connection();
+    let insert_asset: DbAsset = asset.into();
+
+    diesel::insert_into(schema::assets::table)
+        .values(insert_asset)
+        .execute(conn)
+        .expect("Error inserting asset into db");
+    diesel::select(sql::<Integer>("last_insert_rowid()"))
+        .get_result(conn)
+        .expect("Error reading asset id from db")
+}
+
+/// Načíta všetok majetok a záväzky zoradené podľa ID.
+pub fn load_assets() -> Vec<Asset> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbAsset> = schema::assets::table
+        .order(schema::assets::id)
+        .load(conn)
+        .expect("Error loading assets from db");
+    vec.iter().map(|a| a.into()).collect()
+}
+
+/// Vymaže majetok alebo záväzok spolu s jeho oceneniami.
+///
+/// Ocenenia aj položka sa vymažú v jednej transakcii.
+///
+/// # Argumenty
+/// * `asset_id` – ID položky
+///
+/// # Errors
+/// Vráti chybu databázy; vtedy sa nevymaže nič.
+pub fn delete_asset(asset_id: i32) -> QueryResult<()> {
+    let conn = &mut establish_connection();
+    conn.transaction(|conn| {
+        diesel::delete(
+            schema::valuations::table.filter(schema::valuations::asset_id.eq(asset_id)),
+        )
+        .execute(conn)?;
+        diesel::delete(schema::assets::table.filter(schema::assets::id.eq(asset_id)))
+            .execute(conn)?;
+        Ok(())
+    })
+}
+
+/// Uloží nové ocenenie majetku alebo záväzku.
+///
+/// # Argumenty
+/// * `valuation` – ocenenie na uloženie
+pub fn insert_valuation(valuation: &Valuation) {
+    let conn = &mut establish_connection();
+    let insert_valuation: DbValuation = valuation.into();
+
+    diesel::insert_into(schema::valuations::table)
+        .values(insert_valuation)
+        .execute(conn)
+        .expect("Error inserting valuation into db");
+}
+
+/// Načíta všetky ocenenia zoradené podľa dátumu.
+pub fn load_valuations() -> Vec<Valuation> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbValuation> = schema::valuations::table
+        .order((schema::valuations::time, schema::valuations::id))
+        .load(conn)
+        .expect("Error loading valuations from db");
+    vec.iter().map(|v| v.into()).collect()
+}
+
+/// Uloží nový úver a vráti jeho ID.
+///
+/// # Argumenty
+/// * `loan` – úver na uloženie
+pub fn insert_loan(loan: &Loan) -> i32 {
+    let conn = &mut establish_connection();
+    let insert_loan: DbLoan = loan.into();
+
+    diesel::insert_into(schema::loans::table)
+        .values(insert_loan)
+        .execute(conn)
+        .expect("Error inserting loan into db");
+    diesel::select(sql::<Integer>("last_insert_rowid()"))
+        .get_result(conn)
+        .expect("Error reading loan id from db")
+}
+
+/// Načíta všetky úvery zoradené podľa ID.
+pub fn load_loans() -> Vec<Loan> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbLoan> = schema::loans::table
+        .order(schema::loans::id)
+        .load(conn)
+        .expect("Error loading loans from db");
+    vec.iter().map(|l| l.into()).collect()
+}
+
+/// Vymaže úver. Záznamy splátok ostávajú.
+///
+/// # Argumenty
+/// * `loan_id` – ID úveru
+pub fn delete_loan(loan_id: i32) {
+    let conn = &mut establish_connection();
+    diesel::delete(schema::loans::table.filter(schema::loans::id.eq(loan_id)))
+        .execute(conn)
+        .expect("Error deleting loan from db");
+}
+
+/// Uloží nový obchod s cenným papierom a vráti jeho ID.
+///
+/// # Argumenty
+/// * `trade` – obchod na uloženie
+///
+/// # Errors
+/// Vráti [`TradeError`] a nič neuloží, ak obchod neprejde [`Trade::check`].
+pub fn insert_trade(trade: &Trade) -> Result<i32, TradeError> {
+    trade.check()?;
+    let conn = &mut establish_connection();
+    let insert_trade: DbTrade = trade.into();
+
+    diesel::insert_into(schema::trades::table)
+        .values(insert_trade)
+        .execute(conn)
+        .expect("Error inserting trade into db");
+    Ok(diesel::select(sql::<Integer>("last_insert_rowid()"))
+        .get_result(conn)
+        .expect("Error reading trade id from db"))
+}
+
+/// Načíta všetky obchody zoradené podľa dátumu.
+pub fn load_trades() -> Vec<Trade> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbTrade> = schema::trades::table
+        .order((schema::trades::time, schema::trades::id))
+        .load(conn)
+        .expect("Error loading trades from db");
+    vec.iter().map(|t| t.into()).collect()
+}
+
+/// Vymaže obchod.
+///
+/// # Argumenty
+/// * `trade_id` – ID obchodu
+pub fn delete_trade(trade_id: i32) {
+    let conn = &mut establish_connection();
+    diesel::delete(schema::trades::table.filter(schema::trades::id.eq(trade_id)))
+        .execute(conn)
+        .expect("Error deleting trade from db");
+}
+
+/// Uloží ceny cenných papierov. Existujúca cena k rovnakému dňu sa prepíše.
+///
+/// # Argumenty
+/// * `new_prices` – ručne zadané alebo importované ceny
+pub fn insert_prices(new_prices: &[Price]) {
+    let conn = &mut establish_connection();
+    let rows: Vec<DbPrice> = new_prices.iter().map(|p| p.into()).collect();
+
+    diesel::replace_into(schema::prices::table)
+        .values(rows)
+        .execute(conn)
+        .expect("Error inserting prices into db");
+}
+
+/// Načíta všetky ceny zoradené podľa dátumu.
+pub fn load_prices() -> Vec<Price> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbPrice> = schema::prices::table
+        .order((schema::prices::time, schema::prices::symbol))
+        .load(conn)
+        .expect("Error loading prices from db");
+    vec.iter().map(|p| p.into()).collect()
+}
+
+/// Uloží účet a jeho menu. Existujúci účet s rovnakým názvom sa prepíše.
+///
+/// # Argumenty
+/// * `new_account` – účet na uloženie
+pub fn save_account(new_account: &Account) {
+    let conn = &mut establish_connection();
+    let row: DbAccount = new_account.into();
+
+    diesel::replace_into(schema::accounts::table)
+        .values(row)
+        .execute(conn)
+        .expect("Error saving account into db");
+}
+
+/// Načíta všetky účty zoradené podľa názvu.
+pub fn load_accounts() -> Vec<Account> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbAccount> = schema::accounts::table
+        .order(schema::accounts::name)
+        .load(conn)
+        .expect("Error loading accounts from db");
+    vec.iter().map(|a| a.into()).collect()
+}
+
+/// Uloží kurzy. Existujúci kurz pre rovnaký pár a deň sa prepíše.
+///
+/// # Argumenty
+/// * `rates` – ručne zadané alebo importované kurzy
+pub fn insert_rates(rates: &[ExchangeRate]) {
+    let conn = &mut establish_connection();
+    let rows: Vec<DbExchangeRate> = rates.iter().map(|r| r.into()).collect();
+
+    diesel::replace_into(schema::exchange_rates::table)
+        .values(rows)
+        .execute(conn)
+        .expect("Error inserting exchange rates into db");
+}
+
+/// Načíta všetky kurzy zoradené podľa dátumu.
+pub fn load_rates() -> Vec<ExchangeRate> {
+    let conn = &mut establish_connection();
+    let vec: Vec<DbExchangeRate> = schema::exchange_rates::table
+        .order((schema::exchange_rates::time, schema::exchange_rates::id))
+        .load(conn)
+        .expect("Error loading exchange rates from db");
+    vec.iter().map(|r| r.into()).collect()
+}
+
+/// Vytvorí prepočet do základnej meny ([`base_currency`]) s uloženými kurzami.
+pub fn load_converter() -> Converter {
+    Converter::new(base_currency(), &load_rates())
+}
+
+/// SQL výraz pre prvý deň obdobia, do ktorého patrí záznam.
+///
+/// Zodpovedá [`Period::start_of`] nad dátumami v tvare `YYYY-MM-DD`.
+fn period_start_sql(period: Period) -> &'static str {
+    match period {
+        Period::Day => "time",
+        Period::Week => {
+            "date(time, '-' || ((CAST(strftime('%w', time) AS INTEGER) + 6) % 7) || ' days')"
+        }
+        Period::Month => "substr(time, 1, 7) || '-01'",
+        Period::Quarter => {
+            "substr(time, 1, 5) || printf('%02d', ((CAST(substr(time, 6, 2) AS INTEGER) - 1) / 3) * 3 + 1) || '-01'"
+        }
+        Period::Year => "substr(time, 1, 4) || '-01-01'",
+    }
+}
+
+/// Prevedie text dátumu z databázy na dátum.
+fn parse_db_date(text: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(text, DB_DATE_FORMAT).expect("Error parsing date from db")
+}
+
+/// Začiatok SQL výrazu pre deň kurzu, za ním nasleduje naviazaná základná mena.
+///
+/// Sumy v základnej mene sa neprepočítavajú, preto sa pre ne vráti `NULL`
+/// a v SQL sa nedelia po dňoch. Ostatné meny sa delia po dňoch, lebo kurz
+/// sa môže zmeniť každý deň.
+const RATE_DAY_SQL: &str = "CASE WHEN currency = ";
+
+/// Koniec SQL výrazu pre deň kurzu, pozri [`RATE_DAY_SQL`].
+const RATE_DAY_END_SQL: &str = " THEN NULL ELSE time END AS rate_day";
+
+/// Prepočíta súčet z riadku SQL do základnej meny.
+///
+/// Riadky bez dňa kurzu sú už v základnej mene.
+fn convert_sum(
+    converter: &Converter,
+    sum: f64,
+    code: &str,
+    day: Option<&str>,
+) -> Result<f32, MissingRate> {
+    match day {
+        Some(day) => converter.convert(sum as f32, code, parse_db_date(day)),
+        None => Ok(sum as f32),
+    }
+}
+
+/// Vráti zostatok záznamov vyhovujúcich filtru vypočítaný v SQL.
+///
+/// Sumy v základnej mene sa v SQL sčítajú naraz, ostatné meny po dňoch
+/// a prepočítajú sa do základnej meny kurzom platným k danému dňu. Výsledok zodpovedá
+/// [`RecordManager::get_balance`] nad záznamami z [`Converter::convert_all`].
+///
+/// # Argumenty
+/// * `filter` – podmienky výberu záznamov
+/// * `converter` – prepočet do základnej meny
+///
+/// # Errors
+/// Vráti [`MissingRate`], ak pre niektorú menu chýba kurz.
+pub fn balance(filter: &RecordFilter, converter: &Converter) -> Result<f32, MissingRate> {
+    let conn = &mut establish_connection();
+    let rows: Vec<(String, Option<String>, f64)> = records
+        .filter(id.eq_any(filter.to_query().select(id)))
+        .select(
+            sql::<(Text, Nullable<Text>, Double)>(&format!("currency, {}", RATE_DAY_SQL))
+                .bind::<Text, _>(&converter.base)
+                .sql(RATE_DAY_END_SQL)
+                .sql(", SUM(CASE WHEN money_type = 'EXPENSE' THEN -amount ELSE amount END)"),
+        )
+        .group_by(sql::<Text>("currency, rate_day"))
+        .load(conn)
+        .expect("Error computing balance in db");
+
+    rows.iter().try_fold(0.0, |sum, (code, day, value)| {
+        Ok(sum + convert_sum(converter, *value, code, day.as_deref())?)
+    })
+}
+
+/// Vráti súčet výdavkov podľa kategórií vypočítaný v SQL.
+///
+/// Výsledok je v základnej mene, obsahuje všetky kategórie spotreby
+/// a zodpovedá [`RecordManager::categories_to_hash`] nad záznamami
+/// z [`Converter::convert_all`].
+///
+/// # Argumenty
+/// * `filter` – podmienky výberu záznamov
+/// * `converter` – prepočet do základnej meny
+///
+/// # Errors
+/// Vráti [`MissingRate`], ak pre niektorú menu chýba kurz.
+pub fn category_totals(
+    filter: &RecordFilter,
+    converter: &Converter,
+) -> Result<BTreeMap<ExpenseType, f32>, MissingRate> {
+    let conn = &mut establish_connection();
+    let rows: Vec<(Option<String>, String, Option<String>, f64)> = records
+        .filter(id.eq_any(filter.to_query().select(id)))
+        .filter(money_type.eq(MoneyType::EXPENSE.code()))
+        .select(
+            sql::<(Nullable<Text>, Text, Nullable<Text>, Double)>(&format!(
+                "expense, currency, {}",
+                RATE_DAY_SQL
+            ))
+            .bind::<Text, _>(&converter.base)
+            .sql(RATE_DAY_END_SQL)
+            .sql(", SUM(amount)"),
+        )
+        .group_by(sql::<Text>("expense, currency, rate_day"))
+        .load(conn)
+        .expect("Error computing category totals in db");
+
+    let mut ret: BTreeMap<ExpenseType, f32> = ExpenseType::ALL
+        .iter()
+        .filter(|c| c.is_spending())
+        .map(|c| (*c, 0.0))
+        .collect();
+    for (category, code, day, sum) in rows {
+        if let Some(category) = category.and_then(|c| c.parse::<ExpenseType>().ok())
+            && category.is_spending()
+        {
+            *ret.entry(category).or_default() +=
+                convert_sum(converter, sum, &code, day.as_deref())?;
+        }
+    }
+    Ok(ret)
+}
+
+/// Riadok SQL agregácie: začiatok obdobia, hodnota skupiny, typ, kategória, ktorá nie je
+/// spotrebou, mena, deň kurzu, súčet a počet.
+type AggregateRow = (
+    String,
+    Option<String>,
+    String,
+    Option<String>,
+    String,
+    Option<String>,
+    f64,
+    i64,
+);
+
+/// SQL výraz, ktorý vráti kategóriu záznamu, ak nie je spotrebou, inak `NULL`.
+///
+/// Výdavky v kategóriách spotreby sa tak v SQL sčítajú spolu.
+fn non_spending_sql() -> String {
+    let codes: Vec<String> = ExpenseType::ALL
+        .iter()
+        .filter(|c| !c.is_spending())
+        .map(|c| format!("'{}'", c.code()))
+        .collect();
+    format!("CASE WHEN expense IN ({}) THEN expense END", codes.join(", "))
+}
+
+/// Zoskupí záznamy vyhovujúce filtru podľa obdobia pomocou SQL `GROUP BY`.
+///
+/// Súčty sú v základnej mene a zodpovedajú [`crate::aggregate::aggregate`]
+/// nad záznamami z [`Converter::convert_all`].
+///
+/// # Argumenty
+/// * `filter` – podmienky výberu záznamov
+/// * `period` – dĺžka obdobia
+/// * `group` – ďalšie delenie v rámci obdobia
+/// * `converter` – prepočet do základnej meny
+///
+/// # Errors
+/// Vráti [`MissingRate`], ak pre niektorú menu chýba kurz.
+pub fn aggregate(
+    filter: &RecordFilter,
+    period: Period,
+    group: Option<GroupBy>,
+    converter: &Converter,
+) -> Result<Vec<Bucket>, MissingRate> {
+    let conn = &mut establish_connection();
+    let group_column = match group {
+        None => "NULL",
+        Some(GroupBy::Category) => "expense",
+        Some(GroupBy::Account) => "account",
+        // štítky sa v SQL zoskupia podľa celého zoznamu a rozdelia až nižšie
+        Some(GroupBy::Tag) => "tags",
+    };
+    // kurz sa mení po dňoch, preto sa cudzie meny v SQL delia aj podľa dňa
+    let rows: Vec<AggregateRow> = records
+        .filter(id.eq_any(filter.to_query().select(id)))
+        .select(
+            sql::<(
+                Text,
+                Nullable<Text>,
+                Text,
+                Nullable<Text>,
+                Text,
+                Nullable<Text>,
+                Double,
+                BigInt,
+            )>(&format!(
+                "{} AS period_start, {} AS group_value, money_type, {} AS other_category, \
+                 currency, {}",
+                period_start_sql(period),
+                group_column,
+                non_spending_sql(),
+                RATE_DAY_SQL
+            ))
+            .bind::<Text, _>(&converter.base)
+            .sql(RATE_DAY_END_SQL)
+            .sql(", SUM(amount), COUNT(*)"),
+        )
+        .group_by(sql::<Text>(
+            "period_start, group_value, money_type, other_category, currency, rate_day",
+        ))
+        .load(conn)
+        .expect("Error computing aggregates in db");
+
+    let mut buckets: BTreeMap<(NaiveDate, GroupKey), Bucket> = BTreeMap::new();
+    for (start, value, kind, other, code, day, sum, count) in rows {
+        let start = parse_db_date(&start);
+        let sum = convert_sum(converter, sum, &code, day.as_deref())?;
+        let category: Option<ExpenseType> = other.and_then(|c| c.parse().ok());
+        let kind = match kind.as_str() {
+            "INCOME" => MoneyType::INCOME,
+            "EXPENSE" => MoneyType::EXPENSE,
+            _ => panic!("Error while parsing moneyType from db"),
+        };
+        let keys = match group {
+            None => vec![GroupKey::All],
+            Some(GroupBy::Category) => {
+                vec![GroupKey::Category(value.and_then(|c| c.parse().ok()))]
+            }
+            Some(GroupBy::Account) => vec![GroupKey::Account(value)],
+            Some(GroupBy::Tag) => {
+                let tag_list: Vec<String> = value
+                    .unwrap_or_default()
+                    .split(TAG_SEPARATOR)
+                    .filter(|t| !t.is_empty())
+                    .map(|t| t.to_string())
+                    .collect();
+                if tag_list.is_empty() {
+                    vec![GroupKey::Tag(None)]
+                } else {
+                    tag_list.into_iter().map(|t| GroupKey::Tag(Some(t))).collect()
+                }
+            }
+        };
+        for key in keys {
+            buckets
+                .entry((start, key.clone()))
+                .or_insert_with(|| Bucket::empty(start, key))
+                .add_sum(kind, category, sum, count as usize);
+        }
+    }
+
+    Ok(buckets.into_values().collect())
 }
@@ -0,0 +1,269 @@
+//! Odhad budúceho zostatku.
+//!
+//! Predpoveď spája tri zdroje:
+//! - známe záznamy s dátumom v budúcnosti,
+//! - pravidelné platby rozpoznané z histórie ([`detect_recurring`]),
+//!   napr. výplata alebo nájom,
+//! - priemerné denné výdavky podľa kategórií za posledné obdobie,
+//!   do ktorých sa nerátajú pravidelné platby.
+//!
+//! Nepravidelné príjmy sa do predpovede nerátajú, odhad je teda skôr opatrný.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{Days, Months, NaiveDate};
+
+use crate::record::{ExpenseType, MoneyType, Record};
+
+/// Koľko výskytov treba, aby sa platba považovala za pravidelnú.
+const MIN_OCCURRENCES: usize = 3;
+/// O koľko (pomerne) sa môže suma alebo interval odchýliť od mediánu.
+const TOLERANCE: f32 = 0.2;
+/// Počet dní, z ktorých sa počítajú priemerné výdavky.
+const LOOKBACK_DAYS: u64 = 90;
+/// Po koľkých intervaloch bez platby sa séria považuje za ukončenú.
+const STALE_INTERVALS: u32 = 2;
+
+/// Ako často sa pravidelná platba opakuje.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    Days(u32),
+    Months(u32),
+}
+
+impl Interval {
+    /// Rozpozná interval z mediánu počtu dní medzi platbami.
+    ///
+    /// Mesačné, štvrťročné a ročné platby sa posúvajú o kalendárne mesiace.
+    fn from_days(days: i64) -> Interval {
+        match days {
+            27..=32 => Interval::Months(1),
+            88..=95 => Interval::Months(3),
+            360..=370 => Interval::Months(12),
+            _ => Interval::Days(days as u32),
+        }
+    }
+
+    /// Vráti dátum nasledujúcej platby.
+    pub fn next(&self, date: NaiveDate) -> NaiveDate {
+        self.nth(date, 1)
+    }
+
+    /// Vráti dátum platby o `n` intervalov po dátume `anchor`.
+    ///
+    /// Mesačné platby sa počítajú vždy od kotvy, takže platba k 31. januáru
+    /// pokračuje 28. februára a potom znova 31. marca.
+    pub fn nth(&self, anchor: NaiveDate, n: u32) -> NaiveDate {
+        match self {
+            Interval::Days(d) => anchor + Days::new(*d as u64 * n as u64),
+            Interval::Months(m) => anchor + Months::new(*m * n),
+        }
+    }
+}
+
+/// Pravidelná platba rozpoznaná z histórie.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurringSeries {
+    pub money_type: MoneyType,
+    pub expense: Option<ExpenseType>,
+    pub note: Option<String>,
+    /// typická (mediánová) suma
+    pub amount: f32,
+    pub interval: Interval,
+    /// dátum prvej platby, od ktorého sa počítajú ďalšie platby
+    pub anchor: NaiveDate,
+    /// dátum poslednej známej platby
+    pub last: NaiveDate,
+    /// ID záznamov, ktoré do série patria
+    pub record_ids: Vec<i32>,
+}
+
+/// Vráti medián neprázdneho zoznamu.
+fn median<T: Copy + PartialOrd>(values: &mut [T]) -> T {
+    values.sort_by(|a, b| a.partial_cmp(b).expect("comparable values"));
+    values[values.len() / 2]
+}
+
+/// Vráti `true`, ak sa hodnota líši od mediánu najviac o [`TOLERANCE`].
+fn close_to(value: f32, median: f32) -> bool {
+    (value - median).abs() <= median.abs() * TOLERANCE
+}
+
+impl RecurringSeries {
+    /// Vráti dátumy platieb po poslednej známej platbe až do `end` vrátane.
+    pub fn upcoming(&self, end: NaiveDate) -> Vec<NaiveDate> {
+        (1..)
+            .map(|n| self.interval.nth(self.anchor, n))
+            .skip_while(|d| *d <= self.last)
+            .take_while(|d| *d <= end)
+            .collect()
+    }
+}
+
+/// Rozpozná pravidelné platby.
+///
+/// Záznamy sa zoskupia podľa typu, kategórie a poznámky. Skupina je
+/// pravidelná, ak má aspoň [`MIN_OCCURRENCES`] záznamov, ich sumy
+/// sa líšia od mediánu najviac o 20 % a rovnako aj odstupy medzi nimi.
+/// Séria bez platby za posledné [`STALE_INTERVALS`] intervaly pred
+/// dňom `today` sa považuje za ukončenú a vynechá sa.
+///
+/// # Arguments
+/// * `records` – história záznamov
+/// * `today` – deň, ku ktorému sa posudzuje, či séria ešte pokračuje
+pub fn detect_recurring<'a>(
+    records: impl IntoIterator<Item = &'a Record>,
+    today: NaiveDate,
+) -> Vec<RecurringSeries> {
+    type SeriesKey = (&'static str, Option<ExpenseType>, Option<String>);
+    let mut groups: BTreeMap<SeriesKey, Vec<&Record>> = BTreeMap::new();
+    for r in records {
+        let note = r.note.as_ref().map(|n| n.trim().to_lowercase());
+        groups
+            .entry((r.money_type.code(), r.expense, note))
+            .or_default()
+            .push(r);
+    }
+
+    let mut ret = Vec::new();
+    for (_, mut group) in groups {
+        if group.len() < MIN_OCCURRENCES {
+            continue;
+        }
+        group.sort_by_key(|r| (r.time, r.id));
+
+        let mut amounts: Vec<f32> = group.iter().map(|r| r.amount).collect();
+        let amount = median(&mut amounts);
+        if !group.iter().all(|r| close_to(r.amount, amount)) {
+            continue;
+        }
+
+        let gaps: Vec<i64> = group
+            .windows(2)
+            .map(|w| (w[1].time - w[0].time).num_days())
+            .collect();
+        let mut sorted_gaps = gaps.clone();
+        let gap = median(&mut sorted_gaps);
+        if gap < 1 || !gaps.iter().all(|g| close_to(*g as f32, gap as f32)) {
+            continue;
+        }
+
+        let interval = Interval::from_days(gap);
+        let last = group.last().expect("group is not empty");
+        if interval.nth(last.time, STALE_INTERVALS) < today {
+            continue;
+        }
+        ret.push(RecurringSeries {
+            money_type: last.money_type,
+            expense: last.expense,
+            note: last.note.clone(),
+            amount,
+            interval,
+            anchor: group[0].time,
+            last: last.time,
+            record_ids: group.iter().map(|r| r.id).collect(),
+        });
+    }
+    ret
+}
+
+/// Odhadnutý zostatok na konci dňa.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ForecastPoint {
+    pub date: NaiveDate,
+    pub balance: f32,
+}
+
+/// Výsledok predpovede spolu s údajmi, z ktorých vznikla.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forecast {
+    /// zostatok na konci dnešného dňa
+    pub start_balance: f32,
+    /// odhad pre každý deň po dnešku
+    pub points: Vec<ForecastPoint>,
+    /// rozpoznané pravidelné platby
+    pub recurring: Vec<RecurringSeries>,
+    /// priemerné denné nepravidelné výdavky podľa kategórie
+    pub daily_spending: BTreeMap<Option<ExpenseType>, f32>,
+}
+
+impl Forecast {
+    /// Vráti odhadnutý zostatok na konci predpovede.
+    pub fn end_balance(&self) -> f32 {
+        self.points.last().map_or(self.start_balance, |p| p.balance)
+    }
+}
+
+/// Odhadne zostatok na najbližších `days` dní po dátume `today`.
+///
+/// # Arguments
+/// * `records` – všetky záznamy, vrátane tých s budúcim dátumom
+/// * `today` – posledný deň, ktorý sa berie ako skutočnosť
+/// * `days` – dĺžka predpovede v dňoch
+pub fn forecast<'a>(
+    records: impl IntoIterator<Item = &'a Record>,
+    today: NaiveDate,
+    days: u32,
+) -> Forecast {
+    let records: Vec<&Record> = records.into_iter().collect();
+    let end = today + Days::new(days as u64);
+
+    let start_balance: f32 = records
+        .iter()
+        .filter(|r| r.time <= today)
+        .map(|r| r.signed_amount())
+        .sum();
+
+    // zmeny zostatku podľa dňa v predpovedi
+    let mut changes: HashMap<NaiveDate, f32> = HashMap::new();
+
+    for r in records.iter().filter(|r| r.time > today && r.time <= end) {
+        *changes.entry(r.time).or_default() += r.signed_amount();
+    }
+
+    let recurring = detect_recurring(records.iter().copied(), today);
+    for series in &recurring {
+        let signed = match series.money_type {
+            MoneyType::INCOME => series.amount,
+            MoneyType::EXPENSE => -series.amount,
+        };
+        // séria môže pokračovať známymi budúcimi záznamami, tie sa nezdvojujú
+        for date in series.upcoming(end).into_iter().filter(|d| *d > today) {
+            *changes.entry(date).or_default() += signed;
+        }
+    }
+
+    let in_series: HashSet<i32> = recurring
+        .iter()
+        .flat_map(|s| s.record_ids.iter().copied())
+        .collect();
+    let first = records.iter().map(|r| r.time).min().unwrap_or(today);
+    let window_start = (today - Days::new(LOOKBACK_DAYS - 1)).max(first);
+    let window_days = ((today - window_start).num_days() + 1).max(1) as f32;
+    let mut daily_spending: BTreeMap<Option<ExpenseType>, f32> = BTreeMap::new();
+    for r in records.iter().filter(|r| {
+        r.money_type == MoneyType::EXPENSE
+            && r.time >= window_start
+            && r.time <= today
+            && !in_series.contains(&r.id)
+    }) {
+        *daily_spending.entry(r.expense).or_default() += r.amount / window_days;
+    }
+    let daily_total: f32 = daily_spending.values().sum();
+
+    let mut balance = start_balance;
+    let points = (1..=days as u64)
+        .map(|d| {
+            let date = today + Days::new(d);
+            balance += changes.get(&date).copied().unwrap_or(0.0) - daily_total;
+            ForecastPoint { date, balance }
+        })
+        .collect();
+
+    Forecast {
+        start_balance,
+        points,
+        recurring,
+        daily_spending,
+    }
+}
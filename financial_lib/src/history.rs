@@ -0,0 +1,148 @@
+//! História zmien záznamov pre undo/redo.
+//!
+//! Každá zmena záznamov ([`Change`]) pozná stav záznamu pred ňou aj po nej,
+//! takže sa dá vrátiť aj zopakovať. Zmeny jednej akcie používateľa – aj
+//! hromadnej – tvoria [`Action`], ktorá sa uplatní v jednej transakcii
+//! a vracia sa ako celok. Import, hromadné úpravy aj pridanie záznamov
+//! cez socket preto idú cez akciu, ktorá sa pri chybe neuloží vôbec.
+//!
+//! Akcie sa ukladajú do databázy (pozri [`crate::db::commit`],
+//! [`crate::db::undo`] a [`crate::db::redo`]), preto sa dajú vrátiť
+//! aj po reštarte aplikácie.
+
+use std::fmt;
+
+use crate::i18n::trf;
+use crate::record::Record;
+
+/// Koľko posledných akcií sa v histórii uchováva.
+pub const HISTORY_LIMIT: usize = 100;
+
+/// Zmena jedného záznamu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// záznam pred zmenou, `None` ak ešte neexistoval
+    pub before: Option<Record>,
+    /// záznam po zmene, `None` ak bol odstránený
+    pub after: Option<Record>,
+}
+
+impl Change {
+    /// Pridanie nového záznamu.
+    pub fn insert(record: Record) -> Change {
+        Change {
+            before: None,
+            after: Some(record),
+        }
+    }
+
+    /// Úprava existujúceho záznamu.
+    pub fn update(before: Record, after: Record) -> Change {
+        Change {
+            before: Some(before),
+            after: Some(after),
+        }
+    }
+
+    /// Odstránenie záznamu.
+    pub fn delete(record: Record) -> Change {
+        Change {
+            before: Some(record),
+            after: None,
+        }
+    }
+
+    /// Vráti zmenu, ktorá túto zmenu vráti späť.
+    pub fn inverse(&self) -> Change {
+        Change {
+            before: self.after.clone(),
+            after: self.before.clone(),
+        }
+    }
+
+    /// Vráti ID zmeneného záznamu.
+    pub fn id(&self) -> i32 {
+        self.before
+            .as_ref()
+            .or(self.after.as_ref())
+            .map(|r| r.id)
+            .expect("change without record")
+    }
+
+    /// Určí, či zmena mení odsúhlasený záznam inak než zmenou stavu.
+    ///
+    /// Také zmeny sa neuplatnia, odsúhlasené záznamy sú zamknuté.
+    pub fn touches_locked(&self) -> bool {
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) if before.status.is_locked() => {
+                let mut unlocked = after.clone();
+                unlocked.status = before.status;
+                unlocked != *before
+            }
+            (Some(before), None) => before.status.is_locked(),
+            _ => false,
+        }
+    }
+}
+
+/// Jedna akcia používateľa, ktorá sa vracia a opakuje ako celok.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Action {
+    /// ID akcie v histórii, `0` pre akciu, ktorá ešte nie je uložená
+    pub id: i32,
+    /// krátky popis pre používateľa (napr. `delete record 12`)
+    pub description: String,
+    /// zmeny v poradí, v akom sa uplatnili
+    pub changes: Vec<Change>,
+    /// akcia je vrátená a dá sa zopakovať
+    pub undone: bool,
+}
+
+impl Action {
+    /// Vytvorí novú akciu zo zmien.
+    pub fn new(description: impl Into<String>, changes: Vec<Change>) -> Action {
+        Action {
+            id: 0,
+            description: description.into(),
+            changes,
+            undone: false,
+        }
+    }
+
+    /// Vráti ID záznamov, ktoré akcia pridala.
+    pub fn inserted_ids(&self) -> Vec<i32> {
+        self.changes
+            .iter()
+            .filter(|c| c.before.is_none())
+            .map(|c| c.id())
+            .collect()
+    }
+
+    /// Vráti zmeny, ktoré akciu vrátia späť, v opačnom poradí.
+    pub fn inverse(&self) -> Vec<Change> {
+        self.changes.iter().rev().map(|c| c.inverse()).collect()
+    }
+}
+
+/// Chyba pri uplatnení zmien.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    /// záznam sa medzičasom zmenil a nezodpovedá stavu pred zmenou
+    Conflict { id: i32 },
+    /// zmena by upravila alebo odstránila odsúhlasený záznam
+    Locked { id: i32 },
+    /// databáza zmenu odmietla, napr. pre plný disk
+    Database { message: String },
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::Conflict { id } => f.write_str(&trf("history.conflict", &[id])),
+            HistoryError::Locked { id } => f.write_str(&trf("history.locked", &[id])),
+            HistoryError::Database { message } => f.write_str(&trf("db.error", &[message])),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
@@ -0,0 +1,407 @@
+//! Preklady textov pre používateľa.
+//!
+//! Jazyk sa vyberá v nastaveniach (`defaults.language`, premenná
+//! `FINANCE_LANGUAGE`), podporované sú `en` (predvolený) a `sk`.
+//! Texty sú v katalógoch ([`Catalogue`]) pod kľúčmi, každý jazyk má
+//! vlastnú tabuľku. Chýbajúci preklad sa nahradí anglickým textom.
+//! Knižnica má katalóg [`MESSAGES`] s názvami kategórií a stavov
+//! a chybovými hláseniami, aplikácie si vedú vlastné katalógy.
+//!
+//! Miesta na doplnenie hodnôt sú v texte označené `{}` a vypĺňajú sa
+//! v poradí, napr. `trf("search.unknown_field", &[&key])`.
+//!
+//! Strojovo čitateľné kódy (kategórie v databáze, kľúčové slová
+//! vyhľadávania, názvy polí v JSON) sa neprekladajú.
+//!
+//! Testy prekladov knižnice aj aplikácií hľadajú použité kľúče
+//! v zdrojových súboroch cez [`source_keys`] a tabuľky kontrolujú cez
+//! [`Catalogue::inconsistencies`].
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use serde::Deserialize;
+
+use crate::config;
+
+/// Jazyk textov pre používateľa.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// angličtina
+    #[default]
+    En,
+    /// slovenčina
+    Sk,
+}
+
+impl Language {
+    /// Všetky podporované jazyky.
+    pub const ALL: [Language; 2] = [Language::En, Language::Sk];
+
+    /// Kód jazyka v nastaveniach.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Sk => "sk",
+        }
+    }
+}
+
+/// Prevod kódu jazyka (napr. `sk`) na jazyk.
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .into_iter()
+            .find(|l| l.code() == s.trim().to_lowercase())
+            .ok_or_else(|| format!("unknown language '{}', expected en or sk", s))
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Vráti jazyk z nastavení programu.
+pub fn language() -> Language {
+    config::current().defaults.language
+}
+
+/// Tabuľka prekladov: kľúč → text.
+pub type Table = &'static [(&'static str, &'static str)];
+
+/// Katalóg textov s tabuľkou pre každý jazyk.
+#[derive(Debug)]
+pub struct Catalogue {
+    /// anglické texty, musia obsahovať všetky kľúče
+    pub en: Table,
+    /// slovenské texty
+    pub sk: Table,
+}
+
+impl Catalogue {
+    /// Vráti text v jazyku z nastavení.
+    ///
+    /// # Panics
+    /// V ladiacej verzii, ak kľúč chýba aj v anglickej tabuľke.
+    pub fn text(&self, key: &str) -> &'static str {
+        self.text_in(language(), key)
+    }
+
+    /// Vráti text v zadanom jazyku, bez prekladu v angličtine.
+    ///
+    /// Ak kľúč chýba aj v anglickej tabuľke, vráti sa v release verzii
+    /// samotný kľúč, aby chýbajúci text nezhodil program.
+    ///
+    /// # Panics
+    /// V ladiacej verzii, ak kľúč chýba aj v anglickej tabuľke.
+    pub fn text_in(&self, language: Language, key: &str) -> &'static str {
+        lookup(self.table(language), key)
+            .or_else(|| lookup(self.en, key))
+            .unwrap_or_else(|| missing(key))
+    }
+
+    /// Či má kľúč text priamo v tabuľke jazyka, bez náhrady angličtinou.
+    pub fn has(&self, language: Language, key: &str) -> bool {
+        lookup(self.table(language), key).is_some()
+    }
+
+    /// Vráti chyby tabuliek: kľúče iba v slovenskej tabuľke a preklady
+    /// s iným počtom `{}` než anglický text.
+    pub fn inconsistencies(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (key, _) in self.sk {
+            if !self.has(Language::En, key) {
+                errors.push(format!("'{}' is only in the Slovak table", key));
+            }
+        }
+        for (key, text) in self.en {
+            let translated = self.text_in(Language::Sk, key);
+            if translated.matches("{}").count() != text.matches("{}").count() {
+                errors.push(format!("'{}' has different placeholders", key));
+            }
+        }
+        errors
+    }
+
+    fn table(&self, language: Language) -> Table {
+        match language {
+            Language::En => self.en,
+            Language::Sk => self.sk,
+        }
+    }
+
+    /// Vráti text v jazyku z nastavení s doplnenými hodnotami namiesto `{}`.
+    ///
+    /// # Panics
+    /// V ladiacej verzii, ak kľúč chýba aj v anglickej tabuľke.
+    pub fn format(&self, key: &str, args: &[&dyn fmt::Display]) -> String {
+        fill(self.text(key), args)
+    }
+}
+
+fn lookup(table: Table, key: &str) -> Option<&'static str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, text)| *text)
+}
+
+/// Náhrada chýbajúceho textu: samotný kľúč, každý sa uloží iba raz.
+fn missing(key: &str) -> &'static str {
+    if cfg!(debug_assertions) {
+        panic!("missing text '{}'", key);
+    }
+    static MISSING: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+    let mut missing = MISSING.lock().unwrap_or_else(|e| e.into_inner());
+    match missing.get(key) {
+        Some(key) => key,
+        None => {
+            let key: &'static str = Box::leak(key.into());
+            missing.insert(key);
+            key
+        }
+    }
+}
+
+/// Doplní do textu hodnoty namiesto `{}` v poradí. Prebytočné `{}` ostanú.
+pub fn fill(text: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = text.split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        match args.next() {
+            Some(arg) => filled.push_str(&arg.to_string()),
+            None => filled.push_str("{}"),
+        }
+        filled.push_str(part);
+    }
+    filled
+}
+
+/// Vráti text knižnice v jazyku z nastavení.
+pub fn tr(key: &str) -> &'static str {
+    MESSAGES.text(key)
+}
+
+/// Vráti text knižnice s doplnenými hodnotami.
+pub fn trf(key: &str, args: &[&dyn fmt::Display]) -> String {
+    MESSAGES.format(key, args)
+}
+
+/// Vráti reťazce zapísané hneď za `opener` v zdrojovom texte.
+///
+/// Testy prekladov knižnice aj aplikácií ňou hľadajú použité kľúče, napr.
+/// za `tr(`. Výskyt, pred ktorým je písmeno alebo `_`, je iné volanie
+/// (napr. `attr(`) a preskočí sa.
+///
+/// # Panics
+/// Ak sa reťazec za `opener` neukončí.
+pub fn literals_after(source: &str, opener: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    for (start, _) in source.match_indices(opener) {
+        let before = source[..start].chars().next_back();
+        if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+        let rest = source[start + opener.len()..].trim_start();
+        if let Some(literal) = rest.strip_prefix('"') {
+            let end = literal.find('"').expect("terminated string literal");
+            literals.push(literal[..end].to_string());
+        }
+    }
+    literals
+}
+
+/// Vráti kľúče zapísané za niektorým z `openers` v súboroch `.rs`
+/// adresára `dir` spolu s cestou k súboru, pozri [`literals_after`].
+///
+/// # Panics
+/// Ak sa adresár alebo niektorý súbor nedá prečítať.
+pub fn source_keys(dir: &Path, openers: &[&str]) -> Vec<(PathBuf, String)> {
+    let mut keys = Vec::new();
+    for entry in fs::read_dir(dir).expect("source directory") {
+        let path = entry.expect("source file").path();
+        if path.extension().is_none_or(|e| e != "rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&path).expect("readable source file");
+        for opener in openers {
+            keys.extend(literals_after(&source, opener).into_iter().map(|key| (path.clone(), key)));
+        }
+    }
+    keys
+}
+
+/// Texty knižnice.
+pub static MESSAGES: Catalogue = Catalogue { en: EN, sk: SK };
+
+const EN: Table = &[
+    ("category.FUN", "Fun"),
+    ("category.RESTAURANT", "Restaurant"),
+    ("category.SHOPPING", "Shopping"),
+    ("category.INVESTMENT", "Investment"),
+    ("category.FREETIME", "Freetime"),
+    ("category.HOME", "Home"),
+    ("category.CLOTH", "Cloth"),
+    ("category.CAR", "Car"),
+    ("category.TRAVEL", "Travel"),
+    ("category.OTHER", "Other"),
+    ("status.PENDING", "pending"),
+    ("status.CLEARED", "cleared"),
+    ("status.RECONCILED", "reconciled"),
+    ("search.position", "column {}: {}"),
+    ("search.expected_expression", "expected expression after '-'"),
+    ("search.unterminated_quote", "unterminated quote"),
+    ("search.negated", "{} cannot be negated"),
+    ("search.missing_value", "missing value"),
+    ("search.unknown_category", "unknown category '{}'"),
+    ("search.unknown_status", "unknown status '{}'"),
+    ("search.unknown_type", "unknown type '{}', expected + or -"),
+    ("search.unknown_sort", "unknown sort key '{}'"),
+    ("search.invalid_limit", "invalid limit '{}'"),
+    ("search.unknown_field", "unknown field '{}'"),
+    ("search.invalid_amount", "invalid amount '{}'"),
+    ("search.invalid_date", "invalid date '{}', expected YYYY, YYYY-MM or YYYY-MM-DD"),
+    ("record.invalid_amount", "invalid amount '{}', expected a non-negative number"),
+    ("record.invalid_currency", "invalid currency '{}'"),
+    ("record.invalid_tag", "invalid tag '{}', it must not be empty or contain ','"),
+    ("history.conflict", "record {} was changed in the meantime"),
+    ("history.locked", "record {} is reconciled and locked"),
+    ("action.add", "add record {}"),
+    ("action.add_many", "add {} records"),
+    ("action.edit", "edit record {}"),
+    ("action.delete", "delete record {}"),
+    ("action.restore", "restore record {}"),
+    ("action.status", "mark record {} {}"),
+    ("action.unlock", "unlock record {}"),
+    ("action.reconcile", "reconcile {} records"),
+    ("action.import", "import {} records from {}"),
+    ("db.error", "database error: {}"),
+    ("currency.missing_rate", "missing exchange rate {} -> {} for {}"),
+    ("record.unknown_category", "unknown category '{}'"),
+    ("record.unknown_status", "unknown status '{}'"),
+    ("asset.unknown_kind", "unknown asset kind '{}'"),
+    ("loan.unknown_frequency", "unknown payment frequency '{}'"),
+    ("trade.unknown_kind", "unknown trade kind '{}'"),
+    ("trade.invalid", "invalid trade: {}"),
+    ("trade.missing_symbol", "missing symbol"),
+    ("trade.invalid_quantity", "quantity {} must be positive"),
+    ("trade.invalid_price", "price {} must not be negative"),
+    ("trade.invalid_fee", "fee {} must not be negative"),
+    ("import.unknown_format", "unknown format '{}', expected csv or json"),
+    ("import.line", "line {}: {}"),
+    ("import.record", "record {}: {}"),
+    ("import.field_count", "expected {} fields ({}), found {}"),
+    ("import.unterminated_quote", "unterminated quoted field"),
+    ("import.invalid_date", "invalid date '{}', expected YYYY-MM-DD"),
+    ("import.invalid_type", "invalid type '{}', expected INCOME or EXPENSE"),
+    ("import.invalid_rate", "invalid rate '{}'"),
+    ("import.invalid_price", "invalid price '{}'"),
+    ("message.error", "error: {}"),
+    ("message.warning", "warning: {}"),
+    ("config.error", "config {}: {}"),
+    ("config.environment", "environment"),
+    ("config.flags", "command line"),
+    ("config.language", "unknown language '{}', expected en or sk"),
+    ("config.retention_days", "expected a number of days"),
+    ("config.tick_rate", "expected frames per second"),
+    ("config.currency", "invalid currency '{}', expected a code like EUR"),
+    (
+        "config.date_format",
+        "invalid date format '{}', it must contain the day, month and year",
+    ),
+    ("config.invalid_tick_rate", "invalid tick rate {}, expected a number from {} to {}"),
+    (
+        "config.legacy_database",
+        "the database {} from older versions is no longer used, set it in [database] path or DATABASE_URL to keep it",
+    ),
+    ("config.forecast_days", "forecast_days must be at least 1"),
+];
+
+const SK: Table = &[
+    ("category.FUN", "Zábava"),
+    ("category.RESTAURANT", "Reštaurácia"),
+    ("category.SHOPPING", "Nákupy"),
+    ("category.INVESTMENT", "Investície"),
+    ("category.FREETIME", "Voľný čas"),
+    ("category.HOME", "Domácnosť"),
+    ("category.CLOTH", "Oblečenie"),
+    ("category.CAR", "Auto"),
+    ("category.TRAVEL", "Cestovanie"),
+    ("category.OTHER", "Ostatné"),
+    ("status.PENDING", "čaká"),
+    ("status.CLEARED", "zúčtovaný"),
+    ("status.RECONCILED", "odsúhlasený"),
+    ("search.position", "stĺpec {}: {}"),
+    ("search.expected_expression", "po '-' chýba výraz"),
+    ("search.unterminated_quote", "neukončené úvodzovky"),
+    ("search.negated", "{} sa nedá negovať"),
+    ("search.missing_value", "chýba hodnota"),
+    ("search.unknown_category", "neznáma kategória '{}'"),
+    ("search.unknown_status", "neznámy stav '{}'"),
+    ("search.unknown_type", "neznámy typ '{}', očakáva sa + alebo -"),
+    ("search.unknown_sort", "neznámy kľúč triedenia '{}'"),
+    ("search.invalid_limit", "neplatný limit '{}'"),
+    ("search.unknown_field", "neznáme pole '{}'"),
+    ("search.invalid_amount", "neplatná suma '{}'"),
+    ("search.invalid_date", "neplatný dátum '{}', očakáva sa RRRR, RRRR-MM alebo RRRR-MM-DD"),
+    ("record.invalid_amount", "neplatná suma '{}', očakáva sa nezáporné číslo"),
+    ("record.invalid_currency", "neplatná mena '{}'"),
+    ("record.invalid_tag", "neplatný štítok '{}', nesmie byť prázdny ani obsahovať ','"),
+    ("history.conflict", "záznam {} sa medzitým zmenil"),
+    ("history.locked", "záznam {} je odsúhlasený a zamknutý"),
+    ("action.add", "pridanie záznamu {}"),
+    ("action.add_many", "pridanie {} záznamov"),
+    ("action.edit", "úprava záznamu {}"),
+    ("action.delete", "odstránenie záznamu {}"),
+    ("action.restore", "obnovenie záznamu {}"),
+    ("action.status", "záznam {} je {}"),
+    ("action.unlock", "odomknutie záznamu {}"),
+    ("action.reconcile", "odsúhlasenie {} záznamov"),
+    ("action.import", "import {} záznamov z {}"),
+    ("db.error", "chyba databázy: {}"),
+    ("currency.missing_rate", "chýba kurz {} -> {} k {}"),
+    ("record.unknown_category", "neznáma kategória '{}'"),
+    ("record.unknown_status", "neznámy stav '{}'"),
+    ("asset.unknown_kind", "neznámy druh majetku '{}'"),
+    ("loan.unknown_frequency", "neznáma frekvencia splátok '{}'"),
+    ("trade.unknown_kind", "neznámy druh obchodu '{}'"),
+    ("trade.invalid", "neplatný obchod: {}"),
+    ("trade.missing_symbol", "chýba symbol"),
+    ("trade.invalid_quantity", "počet kusov {} musí byť kladný"),
+    ("trade.invalid_price", "cena {} nesmie byť záporná"),
+    ("trade.invalid_fee", "poplatok {} nesmie byť záporný"),
+    ("import.unknown_format", "neznámy formát '{}', očakáva sa csv alebo json"),
+    ("import.line", "riadok {}: {}"),
+    ("import.record", "záznam {}: {}"),
+    ("import.field_count", "očakáva sa {} polí ({}), nájdených {}"),
+    ("import.unterminated_quote", "neukončené pole v úvodzovkách"),
+    ("import.invalid_date", "neplatný dátum '{}', očakáva sa RRRR-MM-DD"),
+    ("import.invalid_type", "neplatný typ '{}', očakáva sa INCOME alebo EXPENSE"),
+    ("import.invalid_rate", "neplatný kurz '{}'"),
+    ("import.invalid_price", "neplatná cena '{}'"),
+    ("message.error", "chyba: {}"),
+    ("message.warning", "upozornenie: {}"),
+    ("config.error", "nastavenia {}: {}"),
+    ("config.environment", "premenné prostredia"),
+    ("config.flags", "príkazový riadok"),
+    ("config.language", "neznámy jazyk '{}', očakáva sa en alebo sk"),
+    ("config.retention_days", "očakáva sa počet dní"),
+    ("config.tick_rate", "očakáva sa počet snímok za sekundu"),
+    ("config.currency", "neplatná mena '{}', očakáva sa kód ako EUR"),
+    (
+        "config.date_format",
+        "neplatný formát dátumu '{}', musí obsahovať deň, mesiac aj rok",
+    ),
+    ("config.invalid_tick_rate", "neplatný počet snímok {}, očakáva sa číslo od {} do {}"),
+    (
+        "config.legacy_database",
+        "databáza {} zo starších verzií sa už nepoužíva, ak ju chcete ponechať, nastavte ju v [database] path alebo DATABASE_URL",
+    ),
+    ("config.forecast_days", "forecast_days musí byť aspoň 1"),
+];
@@ -0,0 +1,314 @@
+//! Investičné pozície, loty a zisky.
+//!
+//! Nákupy a predaje cenných papierov sa zapisujú ako obchody ([`Trade`]).
+//! Každý nákup otvorí nový lot, predaj uzatvára loty od najstaršieho
+//! (FIFO) a rozdiel medzi predajnou cenou a nákladmi lotu je realizovaný
+//! zisk. Z poslednej známej ceny ([`Price`]) sa počíta trhová hodnota
+//! a nerealizovaný zisk otvorených lotov.
+//!
+//! Ceny sa dajú zadať ručne alebo načítať zo súboru CSV
+//! ([`parse_prices_csv`]).
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+
+use crate::i18n::{tr, trf};
+use crate::models::DB_DATE_FORMAT;
+
+/// Druh obchodu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TradeKind {
+    Buy,
+    Sell,
+}
+
+impl TradeKind {
+    /// Vráti kód druhu, ktorý sa ukladá do databázy.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TradeKind::Buy => "BUY",
+            TradeKind::Sell => "SELL",
+        }
+    }
+}
+
+/// Prevod kódu (`BUY`, `SELL`) na druh obchodu.
+impl FromStr for TradeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BUY" => Ok(TradeKind::Buy),
+            "SELL" => Ok(TradeKind::Sell),
+            _ => Err(trf("trade.unknown_kind", &[&s])),
+        }
+    }
+}
+
+/// Nákup alebo predaj cenného papiera.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trade {
+    /// ID v databáze, `None` pred uložením
+    pub id: Option<i32>,
+    /// označenie cenného papiera, napr. `VWCE`
+    pub symbol: String,
+    pub time: NaiveDate,
+    pub kind: TradeKind,
+    /// počet kusov (kladné číslo)
+    pub quantity: f32,
+    /// cena za kus
+    pub price: f32,
+    /// poplatok za obchod
+    pub fee: f32,
+}
+
+impl Trade {
+    /// Vytvorí obchod a skontroluje ho ([`Trade::check`]).
+    ///
+    /// # Errors
+    /// Vráti [`TradeError`], ak počet kusov nie je kladný alebo cena
+    /// či poplatok sú záporné.
+    pub fn new(
+        symbol: impl Into<String>,
+        time: NaiveDate,
+        kind: TradeKind,
+        quantity: f32,
+        price: f32,
+        fee: f32,
+    ) -> Result<Trade, TradeError> {
+        let trade = Trade {
+            id: None,
+            symbol: symbol.into(),
+            time,
+            kind,
+            quantity,
+            price,
+            fee,
+        };
+        trade.check()?;
+        Ok(trade)
+    }
+
+    /// Skontroluje symbol, počet kusov, cenu a poplatok obchodu.
+    ///
+    /// # Errors
+    /// Vráti [`TradeError`] s popisom prvej chyby.
+    pub fn check(&self) -> Result<(), TradeError> {
+        let error = |message: String| Err(TradeError { message });
+        if self.symbol.trim().is_empty() {
+            return error(tr("trade.missing_symbol").to_string());
+        }
+        if !(self.quantity.is_finite() && self.quantity > 0.0) {
+            return error(trf("trade.invalid_quantity", &[&self.quantity]));
+        }
+        if !(self.price.is_finite() && self.price >= 0.0) {
+            return error(trf("trade.invalid_price", &[&self.price]));
+        }
+        if !(self.fee.is_finite() && self.fee >= 0.0) {
+            return error(trf("trade.invalid_fee", &[&self.fee]));
+        }
+        Ok(())
+    }
+}
+
+/// Neplatný obchod.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TradeError {
+    /// popis chyby
+    pub message: String,
+}
+
+impl fmt::Display for TradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&trf("trade.invalid", &[&self.message]))
+    }
+}
+
+impl std::error::Error for TradeError {}
+
+/// Cena cenného papiera k danému dňu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Price {
+    pub symbol: String,
+    pub time: NaiveDate,
+    pub price: f32,
+}
+
+/// Otvorený lot, teda zvyšok jedného nákupu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lot {
+    /// dátum nákupu
+    pub opened: NaiveDate,
+    /// zostávajúci počet kusov
+    pub quantity: f32,
+    /// náklady na kus vrátane poplatku
+    pub unit_cost: f32,
+}
+
+/// Pozícia v jednom cennom papieri.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holding {
+    pub symbol: String,
+    /// otvorené loty od najstaršieho
+    pub lots: Vec<Lot>,
+    /// realizovaný zisk z predajov (po poplatkoch)
+    pub realised_gain: f32,
+    /// posledná známa cena, ak nejaká je
+    pub price: Option<Price>,
+}
+
+impl Holding {
+    /// Počet držaných kusov.
+    pub fn quantity(&self) -> f32 {
+        self.lots.iter().map(|l| l.quantity).sum()
+    }
+
+    /// Nákupné náklady otvorených lotov.
+    pub fn cost_basis(&self) -> f32 {
+        self.lots.iter().map(|l| l.quantity * l.unit_cost).sum()
+    }
+
+    /// Trhová hodnota podľa poslednej ceny, `None` ak cena nie je známa.
+    pub fn market_value(&self) -> Option<f32> {
+        self.price.as_ref().map(|p| p.price * self.quantity())
+    }
+
+    /// Nerealizovaný zisk otvorených lotov, `None` ak cena nie je známa.
+    pub fn unrealised_gain(&self) -> Option<f32> {
+        self.market_value().map(|v| v - self.cost_basis())
+    }
+}
+
+/// Vypočíta pozície k danému dňu z obchodov a cien.
+///
+/// Obchody a ceny po dátume `date` sa ignorujú. Predaj väčšieho počtu
+/// kusov, než je držaný, uzavrie iba dostupné loty. Obchody, ktoré
+/// neprejdú kontrolou ([`Trade::check`]), napr. s nulovým počtom kusov,
+/// sa preskočia.
+///
+/// # Arguments
+/// * `trades` – všetky obchody
+/// * `prices` – všetky známe ceny
+/// * `date` – deň výpočtu
+pub fn holdings(trades: &[Trade], prices: &[Price], date: NaiveDate) -> Vec<Holding> {
+    let mut sorted: Vec<&Trade> = trades
+        .iter()
+        .filter(|t| t.time <= date && t.check().is_ok())
+        .collect();
+    sorted.sort_by_key(|t| (t.time, t.id));
+
+    let mut ret: BTreeMap<&str, Holding> = BTreeMap::new();
+    for trade in sorted {
+        let holding = ret.entry(&trade.symbol).or_insert_with(|| Holding {
+            symbol: trade.symbol.clone(),
+            lots: Vec::new(),
+            realised_gain: 0.0,
+            price: None,
+        });
+        match trade.kind {
+            TradeKind::Buy => holding.lots.push(Lot {
+                opened: trade.time,
+                quantity: trade.quantity,
+                unit_cost: (trade.quantity * trade.price + trade.fee) / trade.quantity,
+            }),
+            TradeKind::Sell => {
+                let mut remaining = trade.quantity;
+                let mut cost = 0.0;
+                for lot in holding.lots.iter_mut() {
+                    let taken = lot.quantity.min(remaining);
+                    lot.quantity -= taken;
+                    remaining -= taken;
+                    cost += taken * lot.unit_cost;
+                    if remaining <= 0.0 {
+                        break;
+                    }
+                }
+                holding.lots.retain(|l| l.quantity > 0.0);
+                let sold = trade.quantity - remaining;
+                holding.realised_gain += sold * trade.price - trade.fee - cost;
+            }
+        }
+    }
+
+    for holding in ret.values_mut() {
+        holding.price = prices
+            .iter()
+            .filter(|p| p.symbol == holding.symbol && p.time <= date)
+            .max_by_key(|p| p.time)
+            .cloned();
+    }
+    ret.into_values().collect()
+}
+
+/// Vráti trhovú hodnotu všetkých pozícií k danému dňu.
+///
+/// Pozície bez známej ceny sa započítajú nákupnými nákladmi.
+pub fn portfolio_value(trades: &[Trade], prices: &[Price], date: NaiveDate) -> f32 {
+    holdings(trades, prices, date)
+        .iter()
+        .map(|h| h.market_value().unwrap_or_else(|| h.cost_basis()))
+        .sum()
+}
+
+/// Chyba pri načítaní cien zo súboru.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PriceImportError {
+    /// číslo riadku (od 1)
+    pub line: usize,
+    /// popis chyby
+    pub message: String,
+}
+
+impl fmt::Display for PriceImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&trf("import.line", &[&self.line, &self.message]))
+    }
+}
+
+impl std::error::Error for PriceImportError {}
+
+/// Načíta ceny z CSV v tvare `symbol,YYYY-MM-DD,cena`.
+///
+/// Prázdne riadky a hlavička začínajúca slovom `symbol` sa preskočia.
+///
+/// # Errors
+/// Vráti [`PriceImportError`] s číslom prvého chybného riadku.
+pub fn parse_prices_csv(input: &str) -> Result<Vec<Price>, PriceImportError> {
+    let mut ret = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || (i == 0 && line.to_lowercase().starts_with("symbol")) {
+            continue;
+        }
+        let error = |message: String| PriceImportError {
+            line: i + 1,
+            message,
+        };
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        let [symbol, time, price] = fields.as_slice() else {
+            return Err(error(trf(
+                "import.field_count",
+                &[&3, &"symbol,date,price", &fields.len()],
+            )));
+        };
+        if symbol.is_empty() {
+            return Err(error(tr("trade.missing_symbol").to_string()));
+        }
+        let time = NaiveDate::parse_from_str(time, DB_DATE_FORMAT)
+            .map_err(|_| error(trf("import.invalid_date", &[time])))?;
+        let price = price
+            .parse::<f32>()
+            .ok()
+            .filter(|p| p.is_finite() && *p >= 0.0)
+            .ok_or_else(|| error(trf("import.invalid_price", &[price])))?;
+        ret.push(Price {
+            symbol: symbol.to_string(),
+            time,
+            price,
+        });
+    }
+    Ok(ret)
+}
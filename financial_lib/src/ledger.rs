@@ -0,0 +1,36 @@
+//! Chronologický prehľad záznamov s priebežným zostatkom.
+//!
+//! Záznamy sa zoradia podľa dátumu a v rámci jedného dňa podľa ID.
+//! Ku každému záznamu sa pripočíta zostatok po jeho zaúčtovaní,
+//! kde príjmy zostatok zvyšujú a výdavky znižujú.
+
+use crate::record::Record;
+
+/// Jeden riadok prehľadu: záznam a zostatok po ňom.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerEntry {
+    pub record: Record,
+    /// zostatok po zaúčtovaní záznamu
+    pub balance: f32,
+}
+
+/// Zoradí záznamy chronologicky a vypočíta priebežný zostatok.
+///
+/// # Arguments
+/// * `records` – záznamy na zoradenie
+pub fn ledger<'a>(records: impl IntoIterator<Item = &'a Record>) -> Vec<LedgerEntry> {
+    let mut sorted: Vec<&Record> = records.into_iter().collect();
+    sorted.sort_by_key(|r| (r.time, r.id));
+
+    let mut balance = 0.0;
+    sorted
+        .into_iter()
+        .map(|r| {
+            balance += r.signed_amount();
+            LedgerEntry {
+                record: r.clone(),
+                balance,
+            }
+        })
+        .collect()
+}
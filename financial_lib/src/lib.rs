@@ -8,28 +8,83 @@
 //! - dátové modely (`models`)
 //! - definície záznamov a ich správu (`record`)
 //! - manažéra na správu (`RecordManager`)
+//! - návrhy kategórií z histórie (`suggest`)
+//! - skladanie filtrov nad záznamami (`query`)
+//! - textový jazyk na vyhľadávanie (`search`)
+//! - agregáciu podľa obdobia (`aggregate`)
+//! - chronologický prehľad s priebežným zostatkom (`ledger`)
+//! - mesačný prehľad a porovnanie období (`report`)
+//! - odhad budúceho zostatku (`forecast`)
+//! - majetok, záväzky a čistú hodnotu v čase (`networth`)
+//! - úvery a splátkové kalendáre (`loan`)
+//! - investičné pozície, loty a zisky (`investment`)
+//! - meny, kurzy a prepočet do základnej meny (`currency`)
+//! - stav záznamov a odsúhlasenie s výpisom (`reconcile`)
+//! - históriu zmien záznamov pre undo/redo, uplatnenú v jednej transakcii (`history`)
+//! - audit log zmien záznamov s autorom a časom (`audit`)
+//! - kôš odstránených záznamov s automatickým vymazaním (`trash`)
+//! - import a export záznamov v CSV a JSON (`transfer`)
+//! - nastavenia zo súboru TOML a premenných prostredia (`config`)
+//! - preklady textov pre používateľa do angličtiny a slovenčiny (`i18n`)
  
+pub mod aggregate;
+pub mod audit;
+pub mod config;
+pub mod currency;
 pub mod db;
+pub mod forecast;
+pub mod history;
+pub mod i18n;
+pub mod investment;
+pub mod ledger;
+pub mod loan;
 pub mod models;
+pub mod networth;
+pub mod query;
+pub mod reconcile;
+pub mod report;
 pub mod schema;
+pub mod search;
+pub mod suggest;
+pub mod transfer;
+pub mod trash;
 /// Modul obsahujúci dátové štruktúry pre finančné záznamy
 /// a ich správu v pamäti.
 pub mod record {
-    use std::{collections::BTreeMap, vec};
+    use std::{collections::BTreeMap, ops::RangeInclusive, str::FromStr, vec};
 
     use chrono::NaiveDate;
+    use serde::{Deserialize, Serialize};
+
+    use crate::aggregate::{Bucket, GroupBy, Period, aggregate};
+    use crate::currency::DEFAULT_CURRENCY;
+    use crate::forecast::{Forecast, forecast};
+    use crate::ledger::{LedgerEntry, ledger};
+    use crate::query::RecordFilter;
+    use crate::reconcile::RecordStatus;
+    use crate::report::{CategoryChange, MonthSummary, compare_categories, monthly_report};
     /// Typ finančnej operácie.
     ///
     /// Určuje, či ide o príjem alebo výdavok.
-    #[derive(Debug, PartialEq, Clone, Copy)]
+    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
     pub enum MoneyType {
         INCOME,
         EXPENSE,
     }
+
+    impl MoneyType {
+        /// Vráti kód typu, ktorý sa ukladá do databázy.
+        pub fn code(&self) -> &'static str {
+            match self {
+                MoneyType::INCOME => "INCOME",
+                MoneyType::EXPENSE => "EXPENSE",
+            }
+        }
+    }
      /// Kategória výdavku.
     ///
     /// Používa sa iba pri výdavkoch.
-    #[derive(Debug, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord)]
+    #[derive(Debug, PartialEq, Hash, Eq, Clone, Copy, PartialOrd, Ord, Serialize, Deserialize)]
     pub enum ExpenseType {
         FUN,
         RESTAURANT,
@@ -42,32 +97,106 @@ pub mod record {
         TRAVEL,
         OTHER,
     }
-/// Prevod typu výdavku na textovú reprezentáciu.
+
+    impl ExpenseType {
+        /// Všetky kategórie výdavkov v poradí, v akom sú deklarované.
+        pub const ALL: [ExpenseType; 10] = [
+            ExpenseType::FUN,
+            ExpenseType::RESTAURANT,
+            ExpenseType::SHOPPING,
+            ExpenseType::INVESTMENT,
+            ExpenseType::FREETIME,
+            ExpenseType::HOME,
+            ExpenseType::CLOTH,
+            ExpenseType::CAR,
+            ExpenseType::TRAVEL,
+            ExpenseType::OTHER,
+        ];
+
+        /// Vráti kód kategórie, ktorý sa ukladá do databázy a zadáva vo formulári.
+        pub fn code(&self) -> &'static str {
+            match self {
+                ExpenseType::FUN => "FUN",
+                ExpenseType::RESTAURANT => "RESTAURANT",
+                ExpenseType::SHOPPING => "SHOPPING",
+                ExpenseType::INVESTMENT => "INVESTMENT",
+                ExpenseType::FREETIME => "FREETIME",
+                ExpenseType::HOME => "HOME",
+                ExpenseType::CLOTH => "CLOTH",
+                ExpenseType::CAR => "CAR",
+                ExpenseType::TRAVEL => "TRAVEL",
+                ExpenseType::OTHER => "OTHER",
+            }
+        }
+
+        /// Vráti názov kategórie pre používateľa v jazyku z nastavení.
+        pub fn name(&self) -> &'static str {
+            crate::i18n::tr(&format!("category.{}", self.code()))
+        }
+    }
+
+    impl ExpenseType {
+        /// Vráti `true`, ak sa kategória počíta do výdavkov na spotrebu.
+        ///
+        /// Nákup investícií nie je spotreba, peniaze ostávajú v majetku
+        /// (pozri [`crate::investment`]), preto sa do súčtov výdavkov nepočíta.
+        pub fn is_spending(&self) -> bool {
+            *self != ExpenseType::INVESTMENT
+        }
+    }
+
+    /// Prevod kódu kategórie (napr. `RESTAURANT`) na typ výdavku.
+    impl FromStr for ExpenseType {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            ExpenseType::ALL
+                .into_iter()
+                .find(|e| e.code() == s)
+                .ok_or_else(|| crate::i18n::trf("record.unknown_category", &[&s]))
+        }
+    }
+/// Prevod typu výdavku na textovú reprezentáciu, pozri [`ExpenseType::name`].
     impl From<ExpenseType> for &str {
         fn from(value: ExpenseType) -> Self {
-            let ret = match value {
-                ExpenseType::FUN => "Fun",
-                ExpenseType::RESTAURANT => "Restaurant",
-                ExpenseType::SHOPPING => "Shopping",
-                ExpenseType::INVESTMENT => "Investment",
-                ExpenseType::FREETIME => "Freetime",
-                ExpenseType::HOME => "Home",
-                ExpenseType::CLOTH => "Cloth",
-                ExpenseType::CAR => "Car",
-                ExpenseType::TRAVEL => "Travel",
-                ExpenseType::OTHER => "Other",
-            };
-            ret
+            value.name()
         }
     }
 /// Reprezentuje jeden finančný záznam.
-    #[derive(Debug, Clone, Copy)]
+    ///
+    /// V JSON sú povinné iba `money_type`, `amount` a `time`, ostatné
+    /// položky majú predvolené hodnoty ako v [`Record::new`].
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
     pub struct Record {
+        #[serde(default)]
         pub id: i32,
         pub money_type: MoneyType,
         pub amount: f32,
+        #[serde(default)]
         pub expense: Option<ExpenseType>,
         pub time: NaiveDate,
+        /// účet, z ktorého alebo na ktorý peniaze išli
+        #[serde(default)]
+        pub account: Option<String>,
+        /// voľné štítky záznamu
+        #[serde(default)]
+        pub tags: Vec<String>,
+        /// poznámka (napr. obchodník alebo popis)
+        #[serde(default)]
+        pub note: Option<String>,
+        /// mena sumy, kód ISO 4217 (napr. `EUR`)
+        #[serde(default = "default_currency")]
+        pub currency: String,
+        /// stav voči banke, odsúhlasené záznamy sú zamknuté
+        #[serde(default)]
+        pub status: RecordStatus,
+        /// kedy bol záznam presunutý do koša, `None` pre bežné záznamy
+        #[serde(default)]
+        pub deleted_on: Option<NaiveDate>,
+    }
+
+    fn default_currency() -> String {
+        DEFAULT_CURRENCY.to_string()
     }
 
     impl Record {
@@ -91,42 +220,93 @@ pub mod record {
                
             time: NaiveDate,
         ) -> Record {
-            let ret = Record {
-                id: id,
+            Record {
+                id,
                 money_type: mon_type,
-                amount: amount,
-                expense: expense,
-                time: time,
-            };
-            ret
+                amount,
+                expense,
+                time,
+                account: None,
+                tags: Vec::new(),
+                note: None,
+                currency: DEFAULT_CURRENCY.to_string(),
+                status: RecordStatus::Pending,
+                deleted_on: None,
+            }
+        }
+        /// Nastaví účet záznamu.
+        pub fn with_account(mut self, account: impl Into<String>) -> Record {
+            self.account = Some(account.into());
+            self
+        }
+        /// Nastaví štítky záznamu.
+        pub fn with_tags<I, S>(mut self, tags: I) -> Record
+        where
+            I: IntoIterator<Item = S>,
+            S: Into<String>,
+        {
+            self.tags = tags.into_iter().map(|t| t.into()).collect();
+            self
+        }
+        /// Nastaví menu záznamu.
+        pub fn with_currency(mut self, currency: impl Into<String>) -> Record {
+            self.currency = currency.into();
+            self
+        }
+        /// Nastaví stav záznamu.
+        pub fn with_status(mut self, status: RecordStatus) -> Record {
+            self.status = status;
+            self
+        }
+        /// Nastaví poznámku záznamu.
+        pub fn with_note(mut self, note: impl Into<String>) -> Record {
+            self.note = Some(note.into());
+            self
+        }
+        /// Vráti sumu so znamienkom: príjem kladne, výdavok záporne.
+        pub fn signed_amount(&self) -> f32 {
+            match self.money_type {
+                MoneyType::INCOME => self.amount,
+                MoneyType::EXPENSE => -self.amount,
+            }
+        }
+        /// Vráti `true`, ak má záznam daný štítok.
+        pub fn has_tag(&self, tag: &str) -> bool {
+            self.tags.iter().any(|t| t == tag)
         }
         /// Vráti formátovaný textový zápis záznamu.
         pub fn format_record(&self) -> String {
+            self.format_columns(None)
+        }
+        /// Vráti formátovaný textový zápis záznamu s priebežným zostatkom pred poznámkou.
+        pub fn format_with_balance(&self, balance: f32) -> String {
+            self.format_columns(Some(balance))
+        }
+        fn format_columns(&self, balance: Option<f32>) -> String {
             let mon_type = match self.money_type {
                 MoneyType::INCOME => "+",
                 MoneyType::EXPENSE => "-",
             };
-            let expenseType = match self.expense {
-                Some(e) => match e {
-                    ExpenseType::FUN => "Fun",
-                    ExpenseType::RESTAURANT => "Restaurant",
-                    ExpenseType::SHOPPING => "Shopping",
-                    ExpenseType::INVESTMENT => "Investment",
-                    ExpenseType::FREETIME => "Freetime",
-                    ExpenseType::HOME => "Home",
-                    ExpenseType::CLOTH => "Cloth",
-                    ExpenseType::CAR => "Car",
-                    ExpenseType::TRAVEL => "Travel",
-                    ExpenseType::OTHER => "Other",
-                },
+            let expense_type = match self.expense {
+                Some(e) => e.name(),
                 None => "-",
             };
-            let timeFormat = self.time.format("%d.%m.%Y").to_string();
-            format! {"{:>3}  {:>1} {:>8.2}  {:<12}  {:<10}",self.id, mon_type, self.amount,expenseType,timeFormat}
+            let time_format = self.time.format(crate::config::date_format()).to_string();
+            let account = self.account.as_deref().unwrap_or("-");
+            let note = self.note.as_deref().unwrap_or("");
+            let balance = match balance {
+                Some(b) => format!("{:>10.2}  ", b),
+                None => String::new(),
+            };
+            format! {"{:>3} {} {:>1} {:>8.2} {:<3}  {:<12}  {:<10}  {:<10}  {}{}",self.id, self.status.symbol(), mon_type, self.amount,self.currency,expense_type,time_format,account,balance,note}
         }
     }
   /// Správca finančných záznamov.
-    #[derive(Debug)]
+    ///
+    /// Súčty a prehľady sčítavajú sumy záznamov bez ohľadu na ich menu.
+    /// Záznamy v rôznych menách treba najprv prepočítať do základnej meny
+    /// cez [`crate::currency::Converter::convert_all`].
+    #[derive(Debug, Default)]
     pub struct RecordManager {
         records: Vec<Record>,
     }
@@ -137,31 +317,77 @@ pub mod record {
         }
 ///vráti naformátované všetky záznamy
         pub fn format_all(&self) -> Vec<String> {
-            let ret = self.get_all().iter().map(|r| r.format_record()).collect();
-            ret
+            self.get_all().iter().map(|r| r.format_record()).collect()
         }
  /// Pridá nový záznam.
         pub fn add_record(&mut self, record: Record) {
             self.records.push(record);
         }
+        /// Vráti záznamy vyhovujúce filtru, zoradené a obmedzené podľa neho.
+        pub fn query(&self, filter: &RecordFilter) -> Vec<Record> {
+            filter.apply(&self.records)
+        }
+        /// Zoskupí záznamy podľa obdobia a voliteľne podľa kategórie, účtu alebo štítku.
+        ///
+        /// Pozri [`aggregate`].
+        /// Záznamy musia byť v jednej mene, pozri [`RecordManager`].
+        pub fn aggregate(&self, period: Period, group: Option<GroupBy>) -> Vec<Bucket> {
+            aggregate(&self.records, period, group)
+        }
+        /// Vráti príjmy, výdavky a úspory po mesiacoch v rozsahu `from..=to`.
+        ///
+        /// Pozri [`monthly_report`].
+        /// Záznamy musia byť v jednej mene, pozri [`RecordManager`].
+        pub fn monthly_report(&self, from: NaiveDate, to: NaiveDate) -> Vec<MonthSummary> {
+            monthly_report(&self.aggregate(Period::Month, None), from, to)
+        }
+        /// Porovná výdavky podľa kategórií v dvoch obdobiach.
+        ///
+        /// Pozri [`compare_categories`].
+        /// Záznamy musia byť v jednej mene, pozri [`RecordManager`].
+        pub fn compare_periods(
+            &self,
+            previous: RangeInclusive<NaiveDate>,
+            current: RangeInclusive<NaiveDate>,
+        ) -> Vec<CategoryChange> {
+            let totals = |range: RangeInclusive<NaiveDate>| -> BTreeMap<ExpenseType, f32> {
+                self.records_between(*range.start(), *range.end())
+                    .into_iter()
+                    .cloned()
+                    .collect::<RecordManager>()
+                    .categories_to_hash()
+            };
+            compare_categories(&totals(previous), &totals(current))
+        }
+        /// Odhadne zostatok na `days` dní po dátume `today`.
+        ///
+        /// Pozri [`forecast`].
+        /// Záznamy musia byť v jednej mene, pozri [`RecordManager`].
+        pub fn forecast(&self, today: NaiveDate, days: u32) -> Forecast {
+            forecast(&self.records, today, days)
+        }
+        /// Vráti záznamy zoradené podľa dátumu (a ID v rámci dňa) s priebežným zostatkom.
+        ///
+        /// Pozri [`ledger`].
+        /// Záznamy musia byť v jednej mene, pozri [`RecordManager`].
+        pub fn ledger(&self) -> Vec<LedgerEntry> {
+            ledger(&self.records)
+        }
         /// Vráti záznam podľa ID.
         pub fn get_record_id(&self, id: i32) -> Option<&Record> {
-            let ret = self.records.iter().find(|r| r.id == id);
-            ret
+            self.records.iter().find(|r| r.id == id)
         }
- /// Vráti celkový zostatok.
+ /// Vráti celkový zostatok, teda príjmy mínus výdavky.
+        /// Záznamy musia byť v jednej mene, pozri [`RecordManager`].
         pub fn get_balance(&self) -> f32 {
-            let mut ret = 0.00;
-            for r in &self.records {
-                ret += r.amount;
-            }
-            ret
+            self.records.iter().map(|r| r.signed_amount()).sum()
         }
-     /// Vráti súčet výdavkov.
+     /// Vráti súčet výdavkov bez nákupu investícií.
         pub fn get_expanses(&self) -> f32 {
             self.records
                 .iter()
                 .filter(|r| r.money_type == MoneyType::EXPENSE)
+                .filter(|r| r.expense.is_none_or(|e| e.is_spending()))
                 .map(|r| r.amount)
                 .sum()
         }
@@ -186,99 +412,25 @@ pub mod record {
             ret
         }
   /// Vráti súčet výdavkov rozdelený podľa kategórií.
+        ///
+        /// Výsledok obsahuje všetky kategórie spotreby, aj tie bez výdavkov.
+        /// Nákup investícií sa nezapočíta (pozri [`ExpenseType::is_spending`]).
+        /// Záznamy musia byť v jednej mene, pozri [`RecordManager`].
         pub fn categories_to_hash(&self) -> BTreeMap<ExpenseType, f32> {
-            let mut ret = BTreeMap::new();
-            let fun = self
-                .records
+            let mut ret: BTreeMap<ExpenseType, f32> = ExpenseType::ALL
                 .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::FUN) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::FUN, fun);
+                .filter(|c| c.is_spending())
+                .map(|c| (*c, 0.0))
+                .collect();
 
-            let restaurant = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::RESTAURANT) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::RESTAURANT, restaurant);
-            let shopping = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::SHOPPING) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::SHOPPING, shopping);
-            let investment = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::INVESTMENT) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::INVESTMENT, investment);
-            let freetime = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::FREETIME) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::FREETIME, freetime);
-            let home = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::HOME) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::HOME, home);
-            let cloth = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::CLOTH) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::CLOTH, cloth);
-            let travel = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::TRAVEL) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::TRAVEL, travel);
-            let other = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::OTHER) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::OTHER, other);
-            let car = self
-                .records
-                .iter()
-                .filter(|r| {
-                    r.expense == Some(ExpenseType::CAR) && r.money_type == MoneyType::EXPENSE
-                })
-                .map(|r| r.amount)
-                .sum();
-            ret.insert(ExpenseType::CAR, car);
+            for r in &self.records {
+                if r.money_type == MoneyType::EXPENSE
+                    && let Some(category) = r.expense
+                    && category.is_spending()
+                {
+                    *ret.entry(category).or_default() += r.amount;
+                }
+            }
 
             ret
         }
@@ -286,10 +438,17 @@ pub mod record {
         pub fn get_all(&self) -> Vec<Record> {
             let mut ret = Vec::new();
             for r in &self.records {
-                let pusher = r.clone();
-                ret.push(pusher);
+                ret.push(r.clone());
             }
             ret
         }
     }
+    /// Vytvorí správcu zo zoznamu záznamov.
+    impl FromIterator<Record> for RecordManager {
+        fn from_iter<I: IntoIterator<Item = Record>>(iter: I) -> Self {
+            RecordManager {
+                records: iter.into_iter().collect(),
+            }
+        }
+    }
 }
@@ -0,0 +1,277 @@
+//! Úvery a ich splátkové kalendáre.
+//!
+//! [`Loan`] popisuje úver istinou, ročnou úrokovou sadzbou, počtom splátok
+//! a ich frekvenciou. Z toho sa vypočíta anuitná splátka a splátkový
+//! kalendár ([`Loan::schedule`]).
+//!
+//! Skutočné splátky sa hľadajú medzi výdavkami podľa štítku úveru.
+//! [`Loan::status`] každú z nich rozdelí na úrok a istinu, vráti zostatok
+//! dlhu a odhadne dátum splatenia pri pokračovaní pravidelnými splátkami.
+//! Istina je v základnej mene, splátky v iných menách prepočíta
+//! [`Loan::converted_status`].
+
+use std::str::FromStr;
+
+use chrono::{Days, Months, NaiveDate};
+
+use crate::currency::{Converter, MissingRate};
+use crate::i18n::trf;
+use crate::record::{MoneyType, Record};
+
+/// Najväčší počet splátok, ktorý sa pri odhade splatenia skúša.
+const MAX_PROJECTED_PAYMENTS: u32 = 1200;
+/// Zvyšok dlhu, ktorý pri odhade doplatí posledná splátka, ako podiel splátky.
+const ROUNDING_SHARE: f32 = 0.01;
+
+/// Ako často sa úver spláca.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaymentFrequency {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl PaymentFrequency {
+    /// Vráti kód frekvencie, ktorý sa ukladá do databázy.
+    pub fn code(&self) -> &'static str {
+        match self {
+            PaymentFrequency::Weekly => "WEEKLY",
+            PaymentFrequency::Monthly => "MONTHLY",
+            PaymentFrequency::Quarterly => "QUARTERLY",
+            PaymentFrequency::Yearly => "YEARLY",
+        }
+    }
+
+    /// Počet splátok za rok.
+    pub fn per_year(&self) -> u32 {
+        match self {
+            PaymentFrequency::Weekly => 52,
+            PaymentFrequency::Monthly => 12,
+            PaymentFrequency::Quarterly => 4,
+            PaymentFrequency::Yearly => 1,
+        }
+    }
+
+    /// Vráti dátum nasledujúcej splátky.
+    pub fn next(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            PaymentFrequency::Weekly => date + Days::new(7),
+            PaymentFrequency::Monthly => date + Months::new(1),
+            PaymentFrequency::Quarterly => date + Months::new(3),
+            PaymentFrequency::Yearly => date + Months::new(12),
+        }
+    }
+
+    /// Vráti dátum `n`-tej splátky, ak prvá je `start` (n = 0).
+    fn nth(&self, start: NaiveDate, n: u32) -> NaiveDate {
+        match self {
+            PaymentFrequency::Weekly => start + Days::new(7 * n as u64),
+            PaymentFrequency::Monthly => start + Months::new(n),
+            PaymentFrequency::Quarterly => start + Months::new(3 * n),
+            PaymentFrequency::Yearly => start + Months::new(12 * n),
+        }
+    }
+}
+
+/// Prevod kódu frekvencie (napr. `MONTHLY`) na frekvenciu.
+impl FromStr for PaymentFrequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "WEEKLY" => Ok(PaymentFrequency::Weekly),
+            "MONTHLY" => Ok(PaymentFrequency::Monthly),
+            "QUARTERLY" => Ok(PaymentFrequency::Quarterly),
+            "YEARLY" => Ok(PaymentFrequency::Yearly),
+            _ => Err(trf("loan.unknown_frequency", &[&s])),
+        }
+    }
+}
+
+/// Úver alebo iný splácaný dlh.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loan {
+    /// ID v databáze, `None` pred uložením
+    pub id: Option<i32>,
+    pub name: String,
+    /// požičaná suma v základnej mene
+    pub principal: f32,
+    /// ročná úroková sadzba v percentách
+    pub annual_rate: f32,
+    /// počet splátok
+    pub term: u32,
+    pub frequency: PaymentFrequency,
+    /// dátum prvej splátky
+    pub start: NaiveDate,
+    /// štítok, ktorým sú označené výdavky splácajúce tento úver
+    pub tag: String,
+}
+
+/// Jedna splátka splátkového kalendára alebo skutočná splátka.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Installment {
+    pub date: NaiveDate,
+    /// celá splátka
+    pub payment: f32,
+    /// časť splátky, ktorá znížila dlh
+    pub principal: f32,
+    /// časť splátky, ktorá pokryla úrok
+    pub interest: f32,
+    /// zostatok dlhu po splátke
+    pub balance: f32,
+    /// ID záznamu, ak ide o skutočnú splátku
+    pub record_id: Option<i32>,
+}
+
+/// Stav úveru podľa skutočných splátok.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoanStatus {
+    /// skutočné splátky rozdelené na istinu a úrok
+    pub payments: Vec<Installment>,
+    /// zostatok dlhu po poslednej splátke
+    pub remaining: f32,
+    /// doteraz zaplatený úrok
+    pub interest_paid: f32,
+    /// odhadovaný dátum poslednej splátky, `None` ak splátka nepokrýva úrok
+    pub payoff_date: Option<NaiveDate>,
+}
+
+impl Loan {
+    /// Úroková sadzba za jedno obdobie medzi splátkami.
+    fn period_rate(&self) -> f32 {
+        self.annual_rate / 100.0 / self.frequency.per_year() as f32
+    }
+
+    /// Vráti pravidelnú anuitnú splátku.
+    pub fn payment(&self) -> f32 {
+        let rate = self.period_rate();
+        let n = self.term.max(1) as i32;
+        if rate == 0.0 {
+            return self.principal / n as f32;
+        }
+        self.principal * rate / (1.0 - (1.0 + rate).powi(-n))
+    }
+
+    /// Rozdelí splátku na úrok a istinu pri danom zostatku dlhu.
+    fn split(&self, balance: f32, payment: f32, date: NaiveDate) -> Installment {
+        let interest = balance * self.period_rate();
+        let principal = (payment - interest).min(balance);
+        Installment {
+            date,
+            payment,
+            principal,
+            interest,
+            balance: balance - principal,
+            record_id: None,
+        }
+    }
+
+    /// Vypočíta splátkový kalendár podľa zmluvných podmienok.
+    pub fn schedule(&self) -> Vec<Installment> {
+        let payment = self.payment();
+        let mut balance = self.principal;
+        (0..self.term)
+            .map(|n| {
+                let date = self.frequency.nth(self.start, n);
+                // posledná splátka doplatí zaokrúhľovacie rozdiely
+                let payment = if n + 1 == self.term {
+                    balance * (1.0 + self.period_rate())
+                } else {
+                    payment
+                };
+                let installment = self.split(balance, payment, date);
+                balance = installment.balance;
+                installment
+            })
+            .collect()
+    }
+
+    /// Vráti `true`, ak záznam je splátkou tohto úveru.
+    pub fn is_payment(&self, record: &Record) -> bool {
+        record.money_type == MoneyType::EXPENSE && record.has_tag(&self.tag)
+    }
+
+    /// Rozdelí skutočné splátky na úrok a istinu a odhadne splatenie.
+    ///
+    /// Každá splátka sa počíta ako jedno obdobie úročenia. Sumy záznamov
+    /// sa berú bez prepočtu, musia byť v základnej mene; inak treba použiť
+    /// [`Loan::converted_status`].
+    ///
+    /// # Arguments
+    /// * `records` – záznamy, medzi ktorými sa hľadajú výdavky so štítkom úveru
+    pub fn status<'a>(&self, records: impl IntoIterator<Item = &'a Record>) -> LoanStatus {
+        let mut paid: Vec<&Record> = records.into_iter().filter(|r| self.is_payment(r)).collect();
+        paid.sort_by_key(|r| (r.time, r.id));
+
+        let mut balance = self.principal;
+        let payments: Vec<Installment> = paid
+            .iter()
+            .map(|r| {
+                let installment = Installment {
+                    record_id: Some(r.id),
+                    ..self.split(balance, r.amount, r.time)
+                };
+                balance = installment.balance;
+                installment
+            })
+            .collect();
+        let interest_paid = payments.iter().map(|p| p.interest).sum();
+
+        LoanStatus {
+            payoff_date: self.payoff_date(balance, payments.last().map(|p| p.date)),
+            remaining: balance,
+            interest_paid,
+            payments,
+        }
+    }
+
+    /// Ako [`Loan::status`], splátky v iných menách sa však najprv
+    /// prepočítajú do základnej meny.
+    ///
+    /// # Errors
+    /// Vráti [`MissingRate`] pre prvú splátku, ktorá sa nedá prepočítať.
+    pub fn converted_status<'a>(
+        &self,
+        records: impl IntoIterator<Item = &'a Record>,
+        converter: &Converter,
+    ) -> Result<LoanStatus, MissingRate> {
+        let paid = records
+            .into_iter()
+            .filter(|r| self.is_payment(r))
+            .map(|r| {
+                Ok(Record {
+                    amount: converter.convert(r.amount, &r.currency, r.time)?,
+                    currency: converter.base.clone(),
+                    ..r.clone()
+                })
+            })
+            .collect::<Result<Vec<Record>, MissingRate>>()?;
+        Ok(self.status(&paid))
+    }
+
+    /// Odhadne dátum poslednej splátky pri pokračovaní pravidelnými splátkami.
+    ///
+    /// Ďalšie splátky pripadnú na dni splátkového kalendára po poslednej
+    /// skutočnej splátke, počítané od dátumu prvej splátky. Zaokrúhľovací
+    /// zvyšok dlhu doplatí posledná splátka, ako v [`Loan::schedule`].
+    fn payoff_date(&self, balance: f32, last_payment: Option<NaiveDate>) -> Option<NaiveDate> {
+        let first = match last_payment {
+            Some(d) if balance <= 0.0 => return Some(d),
+            Some(d) => (0..)
+                .find(|n| self.frequency.nth(self.start, *n) > d)
+                .expect("payment dates grow without bound"),
+            None => 0,
+        };
+        let payment = self.payment();
+        let mut balance = balance;
+        for n in first..first + MAX_PROJECTED_PAYMENTS {
+            let date = self.frequency.nth(self.start, n);
+            balance = self.split(balance, payment, date).balance;
+            if balance <= payment * ROUNDING_SHARE {
+                return Some(date);
+            }
+        }
+        None
+    }
+}
@@ -1,33 +1,58 @@
-//! Modul databázového modelu pre tabuľku `records`.
+//! Modul databázového modelu pre tabuľky `records`, `suggestion_feedback`,
+//! `assets`, `valuations`, `loans`, `trades`, `prices`, `accounts`,
+//! `exchange_rates`, `history` a `audit_log`.
 //!
-//! Obsahuje štruktúru `dbRecord`, ktorá reprezentuje jeden záznam
+//! Obsahuje štruktúru `DbRecord`, ktorá reprezentuje jeden záznam
 //! uložený v SQLite databáze, a konverzie medzi databázovým modelom
 //! a aplikačným modelom `Record`.
 
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::audit::{AUDIT_TIME_FORMAT, AuditEntry, Operation};
+use crate::currency::{Account, ExchangeRate};
+use crate::history::{Action, Change};
+use crate::investment::{Price, Trade};
+use crate::loan::Loan;
+use crate::networth::{Asset, Valuation};
 use crate::record::Record;
+use crate::suggest::Feedback;
+
+/// Formát, v ktorom sa dátumy ukladajú do databázy.
+///
+/// Tvar `YYYY-MM-DD` umožňuje porovnávať a triediť dátumy priamo v SQL.
+pub const DB_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Oddeľovač štítkov v stĺpci `tags`.
+pub const TAG_SEPARATOR: char = ',';
 /// Databázová reprezentácia finančného záznamu.
 ///
 /// Táto štruktúra sa používa na čítanie a zápis dát
-/// do SQLite databázy pomocou knižnice Diesel.
-#[derive(Queryable, Selectable, Insertable)]
+/// do SQLite databázy pomocou knižnice Diesel. V histórii zmien
+/// sa ukladá ako JSON.
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize)]
 #[diesel(table_name = crate::schema::records)]
 #[diesel(check_for_backend(diesel::sqlite::Sqlite))]
-pub struct dbRecord {
+pub struct DbRecord {
     pub id: Option<i32>,
     pub money_type: String,
     pub amount: f32,
     pub expense: Option<String>,
     pub time: String,
+    pub account: Option<String>,
+    pub tags: String,
+    pub note: Option<String>,
+    pub currency: String,
+    pub status: String,
+    pub deleted_on: Option<String>,
 }
-/// Konverzia aplikačného záznamu `Record` na databázový model `dbRecord`.
+/// Konverzia aplikačného záznamu `Record` na databázový model `DbRecord`.
 ///
 /// Používa sa pri vkladaní alebo aktualizácii záznamov v databáze.
-impl From<&Record> for dbRecord {
+impl From<&Record> for DbRecord {
     fn from(value: &Record) -> Self {
-        dbRecord {
+        DbRecord {
             id: Some(value.id),
             money_type: match value.money_type {
                 crate::record::MoneyType::INCOME => "INCOME".to_string(),
@@ -49,15 +74,23 @@ impl From<&Record> for dbRecord {
                 },
                 None => Some("NONE".to_string()),
             },
-            time: value.time.format("%d.%m.%Y").to_string(),
+            time: value.time.format(DB_DATE_FORMAT).to_string(),
+            account: value.account.clone(),
+            tags: value.tags.join(&TAG_SEPARATOR.to_string()),
+            note: value.note.clone(),
+            currency: value.currency.clone(),
+            status: value.status.code().to_string(),
+            deleted_on: value
+                .deleted_on
+                .map(|d| d.format(DB_DATE_FORMAT).to_string()),
         }
     }
 }
-/// Konverzia databázového modelu `dbRecord` na aplikačný model `Record`.
+/// Konverzia databázového modelu `DbRecord` na aplikačný model `Record`.
 ///
 /// Používa sa pri načítaní dát z databázy do aplikácie.
-impl From<&dbRecord> for Record {
-    fn from(value: &dbRecord) -> Self {
+impl From<&DbRecord> for Record {
+    fn from(value: &DbRecord) -> Self {
         Record {
             id: match value.id {
                 Some(i) => i,
@@ -86,8 +119,444 @@ impl From<&dbRecord> for Record {
                 },
                 None => None,
             },
-            time: NaiveDate::parse_from_str(&value.time, "%d.%m.%Y")
+            time: NaiveDate::parse_from_str(&value.time, DB_DATE_FORMAT)
                 .expect("Error while parsing time from db"),
+            account: value.account.clone(),
+            tags: value
+                .tags
+                .split(TAG_SEPARATOR)
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string())
+                .collect(),
+            note: value.note.clone(),
+            currency: value.currency.clone(),
+            status: value
+                .status
+                .parse()
+                .expect("Error while parsing status from db"),
+            deleted_on: value.deleted_on.as_ref().map(|d| {
+                NaiveDate::parse_from_str(d, DB_DATE_FORMAT)
+                    .expect("Error while parsing deleted_on from db")
+            }),
+        }
+    }
+}
+/// Databázová reprezentácia spätnej väzby k návrhu kategórie.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::suggestion_feedback)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbFeedback {
+    pub id: Option<i32>,
+    pub amount: f32,
+    pub time: String,
+    pub category: String,
+    pub accepted: bool,
+    pub account: Option<String>,
+    pub note: Option<String>,
+}
+/// Konverzia spätnej väzby na databázový model `DbFeedback`.
+impl From<&Feedback> for DbFeedback {
+    fn from(value: &Feedback) -> Self {
+        DbFeedback {
+            id: None,
+            amount: value.amount,
+            time: value.time.format(DB_DATE_FORMAT).to_string(),
+            category: value.category.code().to_string(),
+            accepted: value.accepted,
+            account: value.account.clone(),
+            note: value.note.clone(),
+        }
+    }
+}
+/// Konverzia databázového modelu `DbFeedback` na spätnú väzbu.
+impl From<&DbFeedback> for Feedback {
+    fn from(value: &DbFeedback) -> Self {
+        Feedback {
+            amount: value.amount,
+            time: NaiveDate::parse_from_str(&value.time, DB_DATE_FORMAT)
+                .expect("Error while parsing feedback time from db"),
+            account: value.account.clone(),
+            note: value.note.clone(),
+            category: value
+                .category
+                .parse()
+                .expect("Error while parsing feedback category from db"),
+            accepted: value.accepted,
+        }
+    }
+}
+/// Databázová reprezentácia majetku alebo záväzku.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::assets)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbAsset {
+    pub id: Option<i32>,
+    pub name: String,
+    pub kind: String,
+}
+/// Konverzia majetku na databázový model `DbAsset`.
+impl From<&Asset> for DbAsset {
+    fn from(value: &Asset) -> Self {
+        DbAsset {
+            id: value.id,
+            name: value.name.clone(),
+            kind: value.kind.code().to_string(),
+        }
+    }
+}
+/// Konverzia databázového modelu `DbAsset` na majetok.
+impl From<&DbAsset> for Asset {
+    fn from(value: &DbAsset) -> Self {
+        Asset {
+            id: value.id,
+            name: value.name.clone(),
+            kind: value
+                .kind
+                .parse()
+                .expect("Error while parsing asset kind from db"),
+        }
+    }
+}
+/// Databázová reprezentácia ocenenia majetku alebo záväzku.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::valuations)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbValuation {
+    pub id: Option<i32>,
+    pub asset_id: i32,
+    pub time: String,
+    pub value: f32,
+}
+/// Konverzia ocenenia na databázový model `DbValuation`.
+impl From<&Valuation> for DbValuation {
+    fn from(value: &Valuation) -> Self {
+        DbValuation {
+            id: None,
+            asset_id: value.asset_id,
+            time: value.time.format(DB_DATE_FORMAT).to_string(),
+            value: value.value,
+        }
+    }
+}
+/// Konverzia databázového modelu `DbValuation` na ocenenie.
+impl From<&DbValuation> for Valuation {
+    fn from(value: &DbValuation) -> Self {
+        Valuation {
+            asset_id: value.asset_id,
+            time: NaiveDate::parse_from_str(&value.time, DB_DATE_FORMAT)
+                .expect("Error while parsing valuation time from db"),
+            value: value.value,
+        }
+    }
+}
+/// Databázová reprezentácia úveru.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::loans)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbLoan {
+    pub id: Option<i32>,
+    pub name: String,
+    pub principal: f32,
+    pub annual_rate: f32,
+    pub term: i32,
+    pub frequency: String,
+    pub start: String,
+    pub tag: String,
+}
+/// Konverzia úveru na databázový model `DbLoan`.
+impl From<&Loan> for DbLoan {
+    fn from(value: &Loan) -> Self {
+        DbLoan {
+            id: value.id,
+            name: value.name.clone(),
+            principal: value.principal,
+            annual_rate: value.annual_rate,
+            term: value.term as i32,
+            frequency: value.frequency.code().to_string(),
+            start: value.start.format(DB_DATE_FORMAT).to_string(),
+            tag: value.tag.clone(),
+        }
+    }
+}
+/// Konverzia databázového modelu `DbLoan` na úver.
+impl From<&DbLoan> for Loan {
+    fn from(value: &DbLoan) -> Self {
+        Loan {
+            id: value.id,
+            name: value.name.clone(),
+            principal: value.principal,
+            annual_rate: value.annual_rate,
+            term: value.term as u32,
+            frequency: value
+                .frequency
+                .parse()
+                .expect("Error while parsing loan frequency from db"),
+            start: NaiveDate::parse_from_str(&value.start, DB_DATE_FORMAT)
+                .expect("Error while parsing loan start from db"),
+            tag: value.tag.clone(),
+        }
+    }
+}
+/// Databázová reprezentácia obchodu s cenným papierom.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::trades)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbTrade {
+    pub id: Option<i32>,
+    pub symbol: String,
+    pub time: String,
+    pub kind: String,
+    pub quantity: f32,
+    pub price: f32,
+    pub fee: f32,
+}
+/// Konverzia obchodu na databázový model `DbTrade`.
+impl From<&Trade> for DbTrade {
+    fn from(value: &Trade) -> Self {
+        DbTrade {
+            id: value.id,
+            symbol: value.symbol.clone(),
+            time: value.time.format(DB_DATE_FORMAT).to_string(),
+            kind: value.kind.code().to_string(),
+            quantity: value.quantity,
+            price: value.price,
+            fee: value.fee,
+        }
+    }
+}
+/// Konverzia databázového modelu `DbTrade` na obchod.
+impl From<&DbTrade> for Trade {
+    fn from(value: &DbTrade) -> Self {
+        Trade {
+            id: value.id,
+            symbol: value.symbol.clone(),
+            time: NaiveDate::parse_from_str(&value.time, DB_DATE_FORMAT)
+                .expect("Error while parsing trade time from db"),
+            kind: value
+                .kind
+                .parse()
+                .expect("Error while parsing trade kind from db"),
+            quantity: value.quantity,
+            price: value.price,
+            fee: value.fee,
+        }
+    }
+}
+/// Databázová reprezentácia ceny cenného papiera.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::prices)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbPrice {
+    pub id: Option<i32>,
+    pub symbol: String,
+    pub time: String,
+    pub price: f32,
+}
+/// Konverzia ceny na databázový model `DbPrice`.
+impl From<&Price> for DbPrice {
+    fn from(value: &Price) -> Self {
+        DbPrice {
+            id: None,
+            symbol: value.symbol.clone(),
+            time: value.time.format(DB_DATE_FORMAT).to_string(),
+            price: value.price,
+        }
+    }
+}
+/// Konverzia databázového modelu `DbPrice` na cenu.
+impl From<&DbPrice> for Price {
+    fn from(value: &DbPrice) -> Self {
+        Price {
+            symbol: value.symbol.clone(),
+            time: NaiveDate::parse_from_str(&value.time, DB_DATE_FORMAT)
+                .expect("Error while parsing price time from db"),
+            price: value.price,
+        }
+    }
+}
+/// Databázová reprezentácia účtu a jeho meny.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::accounts)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbAccount {
+    pub name: String,
+    pub currency: String,
+}
+/// Konverzia účtu na databázový model `DbAccount`.
+impl From<&Account> for DbAccount {
+    fn from(value: &Account) -> Self {
+        DbAccount {
+            name: value.name.clone(),
+            currency: value.currency.clone(),
+        }
+    }
+}
+/// Konverzia databázového modelu `DbAccount` na účet.
+impl From<&DbAccount> for Account {
+    fn from(value: &DbAccount) -> Self {
+        Account {
+            name: value.name.clone(),
+            currency: value.currency.clone(),
+        }
+    }
+}
+/// Databázová reprezentácia kurzu.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::exchange_rates)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbExchangeRate {
+    pub id: Option<i32>,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub time: String,
+    pub rate: f32,
+}
+/// Konverzia kurzu na databázový model `DbExchangeRate`.
+impl From<&ExchangeRate> for DbExchangeRate {
+    fn from(value: &ExchangeRate) -> Self {
+        DbExchangeRate {
+            id: None,
+            from_currency: value.from.clone(),
+            to_currency: value.to.clone(),
+            time: value.time.format(DB_DATE_FORMAT).to_string(),
+            rate: value.rate,
+        }
+    }
+}
+/// Konverzia databázového modelu `DbExchangeRate` na kurz.
+impl From<&DbExchangeRate> for ExchangeRate {
+    fn from(value: &DbExchangeRate) -> Self {
+        ExchangeRate {
+            from: value.from_currency.clone(),
+            to: value.to_currency.clone(),
+            time: NaiveDate::parse_from_str(&value.time, DB_DATE_FORMAT)
+                .expect("Error while parsing exchange rate time from db"),
+            rate: value.rate,
+        }
+    }
+}
+/// Databázová reprezentácia akcie v histórii zmien.
+///
+/// Zmeny sa ukladajú v stĺpci `changes` ako JSON pole objektov
+/// so stavom záznamu pred zmenou a po nej.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::history)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbHistory {
+    pub id: Option<i32>,
+    pub description: String,
+    pub changes: String,
+    pub undone: bool,
+}
+/// Zmena záznamu v tvare, v akom sa ukladá do JSON.
+#[derive(Serialize, Deserialize)]
+struct StoredChange {
+    before: Option<DbRecord>,
+    after: Option<DbRecord>,
+}
+/// Konverzia akcie na databázový model `DbHistory`.
+impl From<&Action> for DbHistory {
+    fn from(value: &Action) -> Self {
+        let changes: Vec<StoredChange> = value
+            .changes
+            .iter()
+            .map(|c| StoredChange {
+                before: c.before.as_ref().map(|r| r.into()),
+                after: c.after.as_ref().map(|r| r.into()),
+            })
+            .collect();
+        DbHistory {
+            id: (value.id != 0).then_some(value.id),
+            description: value.description.clone(),
+            changes: serde_json::to_string(&changes).expect("Error serializing history changes"),
+            undone: value.undone,
+        }
+    }
+}
+/// Konverzia databázového modelu `DbHistory` na akciu.
+impl From<&DbHistory> for Action {
+    fn from(value: &DbHistory) -> Self {
+        let changes: Vec<StoredChange> = serde_json::from_str(&value.changes)
+            .expect("Error while parsing history changes from db");
+        Action {
+            id: value.id.expect("Wrong value from database"),
+            description: value.description.clone(),
+            changes: changes
+                .iter()
+                .map(|c| Change {
+                    before: c.before.as_ref().map(|r| r.into()),
+                    after: c.after.as_ref().map(|r| r.into()),
+                })
+                .collect(),
+            undone: value.undone,
+        }
+    }
+}
+/// Databázová reprezentácia riadku audit logu.
+///
+/// Hodnoty záznamu pred zmenou a po nej sa ukladajú ako JSON `DbRecord`.
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crate::schema::audit_log)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct DbAuditEntry {
+    pub id: Option<i32>,
+    pub record_id: i32,
+    pub operation: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub time: String,
+    pub actor: String,
+}
+impl DbAuditEntry {
+    /// Vytvorí riadok audit logu zo stavu záznamu pred zmenou a po nej.
+    pub fn new(
+        before: Option<&DbRecord>,
+        after: Option<&DbRecord>,
+        time: NaiveDateTime,
+        actor: &str,
+    ) -> DbAuditEntry {
+        let json = |r: &DbRecord| serde_json::to_string(r).expect("Error serializing record");
+        let operation = match (before, after) {
+            (None, _) => Operation::Insert,
+            (Some(_), Some(_)) => Operation::Update,
+            (Some(_), None) => Operation::Delete,
+        };
+        DbAuditEntry {
+            id: None,
+            record_id: before
+                .or(after)
+                .and_then(|r| r.id)
+                .expect("audit entry without record id"),
+            operation: operation.code().to_string(),
+            before: before.map(json),
+            after: after.map(json),
+            time: time.format(AUDIT_TIME_FORMAT).to_string(),
+            actor: actor.to_string(),
+        }
+    }
+}
+/// Konverzia databázového modelu `DbAuditEntry` na riadok audit logu.
+impl From<&DbAuditEntry> for AuditEntry {
+    fn from(value: &DbAuditEntry) -> Self {
+        let record = |json: &String| -> Record {
+            let row: DbRecord =
+                serde_json::from_str(json).expect("Error while parsing audit record from db");
+            (&row).into()
+        };
+        AuditEntry {
+            id: value.id.expect("Wrong value from database"),
+            record_id: value.record_id,
+            operation: match value.operation.as_str() {
+                "INSERT" => Operation::Insert,
+                "UPDATE" => Operation::Update,
+                "DELETE" => Operation::Delete,
+                _ => panic!("Error while parsing audit operation from db"),
+            },
+            before: value.before.as_ref().map(record),
+            after: value.after.as_ref().map(record),
+            time: NaiveDateTime::parse_from_str(&value.time, AUDIT_TIME_FORMAT)
+                .expect("Error while parsing audit time from db"),
+            actor: value.actor.clone(),
         }
     }
 }
@@ -0,0 +1,176 @@
+//! Čistá hodnota majetku v čase.
+//!
+//! Majetok ([`Asset`]) je napr. nehnuteľnosť, auto alebo investičný účet,
+//! záväzok je napr. úver alebo dlh na kreditnej karte. Hodnota každej
+//! položky sa zadáva datovanými oceneniami ([`Valuation`]) a medzi nimi
+//! platí posledné známe ocenenie. Čistá hodnota je súčet majetku mínus
+//! súčet záväzkov.
+//!
+//! Investičné pozície sa dajú pripočítať k majetku ich trhovou hodnotou
+//! ([`net_worth_with_investments`]).
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+
+use crate::i18n::trf;
+use crate::investment::{Price, Trade, portfolio_value};
+
+/// Či položka hodnotu pridáva alebo uberá.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetKind {
+    Asset,
+    Liability,
+}
+
+impl AssetKind {
+    /// Vráti kód druhu, ktorý sa ukladá do databázy.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AssetKind::Asset => "ASSET",
+            AssetKind::Liability => "LIABILITY",
+        }
+    }
+}
+
+/// Prevod kódu (`ASSET`, `LIABILITY`) na druh položky.
+impl FromStr for AssetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ASSET" => Ok(AssetKind::Asset),
+            "LIABILITY" => Ok(AssetKind::Liability),
+            _ => Err(trf("asset.unknown_kind", &[&s])),
+        }
+    }
+}
+
+/// Majetok alebo záväzok.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Asset {
+    /// ID v databáze, `None` pred uložením
+    pub id: Option<i32>,
+    pub name: String,
+    pub kind: AssetKind,
+}
+
+impl Asset {
+    /// Vytvorí novú položku majetku alebo záväzku.
+    pub fn new(name: impl Into<String>, kind: AssetKind) -> Asset {
+        Asset {
+            id: None,
+            name: name.into(),
+            kind,
+        }
+    }
+}
+
+/// Hodnota položky k danému dňu.
+///
+/// Pri záväzkoch sa zadáva dlžná suma ako kladné číslo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Valuation {
+    pub asset_id: i32,
+    pub time: NaiveDate,
+    pub value: f32,
+}
+
+/// Čistá hodnota k jednému dňu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetWorthPoint {
+    pub date: NaiveDate,
+    /// súčet majetku
+    pub assets: f32,
+    /// súčet záväzkov (kladné číslo)
+    pub liabilities: f32,
+    /// majetok mínus záväzky
+    pub net: f32,
+}
+
+/// Vráti posledné ocenenie položky k danému dňu, ak nejaké existuje.
+///
+/// # Arguments
+/// * `valuations` – všetky ocenenia
+/// * `asset_id` – ID položky
+/// * `date` – deň, ku ktorému sa hodnota zisťuje
+pub fn value_on(valuations: &[Valuation], asset_id: i32, date: NaiveDate) -> Option<Valuation> {
+    valuations
+        .iter()
+        .filter(|v| v.asset_id == asset_id && v.time <= date)
+        .max_by_key(|v| v.time)
+        .copied()
+}
+
+/// Vypočíta čistú hodnotu k danému dňu.
+///
+/// # Arguments
+/// * `assets` – majetok a záväzky
+/// * `valuations` – ich ocenenia
+/// * `date` – deň výpočtu
+pub fn net_worth_on(assets: &[Asset], valuations: &[Valuation], date: NaiveDate) -> NetWorthPoint {
+    let mut ret = NetWorthPoint {
+        date,
+        assets: 0.0,
+        liabilities: 0.0,
+        net: 0.0,
+    };
+    for asset in assets {
+        let Some(value) = asset.id.and_then(|id| value_on(valuations, id, date)) else {
+            continue;
+        };
+        match asset.kind {
+            AssetKind::Asset => ret.assets += value.value,
+            AssetKind::Liability => ret.liabilities += value.value,
+        }
+    }
+    ret.net = ret.assets - ret.liabilities;
+    ret
+}
+
+/// Vypočíta čistú hodnotu ku každému dňu, kedy sa zmenilo niektoré ocenenie.
+///
+/// # Arguments
+/// * `assets` – majetok a záväzky
+/// * `valuations` – ich ocenenia
+pub fn net_worth_over_time(assets: &[Asset], valuations: &[Valuation]) -> Vec<NetWorthPoint> {
+    let dates: BTreeSet<NaiveDate> = valuations.iter().map(|v| v.time).collect();
+    dates
+        .into_iter()
+        .map(|date| net_worth_on(assets, valuations, date))
+        .collect()
+}
+
+/// Vypočíta čistú hodnotu v čase vrátane trhovej hodnoty investícií.
+///
+/// Body sú ku každému dňu, kedy sa zmenilo ocenenie, obchod alebo cena.
+///
+/// # Arguments
+/// * `assets` – majetok a záväzky
+/// * `valuations` – ich ocenenia
+/// * `trades` – obchody s cennými papiermi
+/// * `prices` – ceny cenných papierov
+pub fn net_worth_with_investments(
+    assets: &[Asset],
+    valuations: &[Valuation],
+    trades: &[Trade],
+    prices: &[Price],
+) -> Vec<NetWorthPoint> {
+    let dates: BTreeSet<NaiveDate> = valuations
+        .iter()
+        .map(|v| v.time)
+        .chain(trades.iter().map(|t| t.time))
+        .chain(prices.iter().map(|p| p.time))
+        .collect();
+    dates
+        .into_iter()
+        .map(|date| {
+            let mut point = net_worth_on(assets, valuations, date);
+            let investments = portfolio_value(trades, prices, date);
+            point.assets += investments;
+            point.net += investments;
+            point
+        })
+        .collect()
+}
@@ -0,0 +1,576 @@
+//! Skladanie filtrov nad finančnými záznamami.
+//!
+//! [`RecordFilter`] popisuje, ktoré záznamy nás zaujímajú (rozsah dátumov,
+//! typ peňazí, kategórie, rozsah súm, účet, štítky, stav a hľadaný text), ako
+//! ich zoradiť a koľko ich vrátiť. Ten istý filter sa dá vyhodnotiť
+//! v pamäti cez [`RecordFilter::apply`] alebo preložiť na Diesel dopyt
+//! cez [`RecordFilter::to_query`].
+
+use std::collections::BTreeSet;
+
+use chrono::{Datelike, NaiveDate};
+use diesel::dsl::{not, sql};
+use diesel::prelude::*;
+use diesel::sql_types::{Bool, Text};
+use diesel::sqlite::Sqlite;
+
+use crate::models::{DB_DATE_FORMAT, TAG_SEPARATOR};
+use crate::reconcile::RecordStatus;
+use crate::record::{ExpenseType, MoneyType, Record};
+use crate::schema::records;
+
+/// Roky, ktorých dátumy sa dajú porovnať v databáze.
+///
+/// Dátumy sa v SQL porovnávajú ako text `YYYY-MM-DD`, čo zodpovedá
+/// poradiu dátumov iba pri rokoch so štyrmi číslicami.
+pub const YEARS: std::ops::RangeInclusive<i32> = 0..=9999;
+
+/// Podľa čoho sa majú záznamy zoradiť.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Id,
+    Date,
+    Amount,
+    Category,
+}
+
+/// Smer triedenia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Filter nad finančnými záznamami.
+///
+/// Filter sa skladá postupným volaním metód, napr.:
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use financial_lib::query::{RecordFilter, SortKey, SortOrder};
+/// use financial_lib::record::{ExpenseType, MoneyType};
+///
+/// let filter = RecordFilter::new()
+///     .between(
+///         NaiveDate::from_ymd_opt(2026, 9, 1).unwrap(),
+///         NaiveDate::from_ymd_opt(2026, 9, 30).unwrap(),
+///     )
+///     .money_type(MoneyType::EXPENSE)
+///     .category(ExpenseType::RESTAURANT)
+///     .min_amount(20.0)
+///     .sort_by(SortKey::Amount, SortOrder::Descending)
+///     .limit(10);
+/// ```
+///
+/// Nenastavené podmienky neobmedzujú nič, prázdny filter vráti všetky
+/// záznamy zoradené podľa ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordFilter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub money_type: Option<MoneyType>,
+    /// povolené kategórie, `None` znamená ľubovoľnú
+    pub categories: Option<BTreeSet<ExpenseType>>,
+    pub min_amount: Option<f32>,
+    pub max_amount: Option<f32>,
+    pub account: Option<String>,
+    /// štítky, ktoré musí záznam mať všetky
+    pub tags: Vec<String>,
+    /// texty hľadané v poznámke, účte, štítkoch a kóde kategórie,
+    /// záznam musí obsahovať každý z nich
+    pub text: Vec<String>,
+    /// texty hľadané iba v poznámke
+    pub notes: Vec<String>,
+    /// povolené stavy, `None` znamená ľubovoľný
+    pub statuses: Option<BTreeSet<RecordStatus>>,
+    /// vylúčené kategórie
+    pub exclude_categories: BTreeSet<ExpenseType>,
+    /// vylúčené účty
+    pub exclude_accounts: Vec<String>,
+    /// štítky, ktoré záznam nesmie mať
+    pub exclude_tags: Vec<String>,
+    /// texty, ktoré záznam nesmie obsahovať
+    pub exclude_text: Vec<String>,
+    /// texty, ktoré poznámka záznamu nesmie obsahovať
+    pub exclude_notes: Vec<String>,
+    pub sort: (SortKey, SortOrder),
+    pub limit: Option<usize>,
+}
+
+impl Default for RecordFilter {
+    fn default() -> Self {
+        RecordFilter {
+            from: None,
+            to: None,
+            money_type: None,
+            categories: None,
+            min_amount: None,
+            max_amount: None,
+            account: None,
+            tags: Vec::new(),
+            text: Vec::new(),
+            notes: Vec::new(),
+            statuses: None,
+            exclude_categories: BTreeSet::new(),
+            exclude_accounts: Vec::new(),
+            exclude_tags: Vec::new(),
+            exclude_text: Vec::new(),
+            exclude_notes: Vec::new(),
+            sort: (SortKey::Id, SortOrder::Ascending),
+            limit: None,
+        }
+    }
+}
+
+impl RecordFilter {
+    /// Vytvorí prázdny filter, ktorému vyhovujú všetky záznamy.
+    pub fn new() -> RecordFilter {
+        RecordFilter::default()
+    }
+
+    /// Ponechá záznamy od daného dátumu (vrátane).
+    ///
+    /// # Panics
+    /// Ak rok dátumu nie je v rozsahu [`YEARS`].
+    pub fn from(mut self, from: NaiveDate) -> RecordFilter {
+        assert!(YEARS.contains(&from.year()), "Date {} is out of range", from);
+        self.from = Some(from);
+        self
+    }
+
+    /// Ponechá záznamy do daného dátumu (vrátane).
+    ///
+    /// # Panics
+    /// Ak rok dátumu nie je v rozsahu [`YEARS`].
+    pub fn to(mut self, to: NaiveDate) -> RecordFilter {
+        assert!(YEARS.contains(&to.year()), "Date {} is out of range", to);
+        self.to = Some(to);
+        self
+    }
+
+    /// Ponechá záznamy medzi dvoma dátumami (vrátane oboch).
+    pub fn between(self, from: NaiveDate, to: NaiveDate) -> RecordFilter {
+        self.from(from).to(to)
+    }
+
+    /// Ponechá iba príjmy alebo iba výdavky.
+    pub fn money_type(mut self, money_type: MoneyType) -> RecordFilter {
+        self.money_type = Some(money_type);
+        self
+    }
+
+    /// Pridá kategóriu medzi povolené kategórie.
+    pub fn category(mut self, category: ExpenseType) -> RecordFilter {
+        self.categories
+            .get_or_insert_with(BTreeSet::new)
+            .insert(category);
+        self
+    }
+
+    /// Pridá viac kategórií medzi povolené kategórie.
+    pub fn categories(self, categories: impl IntoIterator<Item = ExpenseType>) -> RecordFilter {
+        categories.into_iter().fold(self, |f, c| f.category(c))
+    }
+
+    /// Ponechá záznamy so sumou aspoň `min`.
+    pub fn min_amount(mut self, min: f32) -> RecordFilter {
+        self.min_amount = Some(min);
+        self
+    }
+
+    /// Ponechá záznamy so sumou najviac `max`.
+    pub fn max_amount(mut self, max: f32) -> RecordFilter {
+        self.max_amount = Some(max);
+        self
+    }
+
+    /// Ponechá záznamy so sumou v rozsahu `min..=max`.
+    pub fn amount_between(self, min: f32, max: f32) -> RecordFilter {
+        self.min_amount(min).max_amount(max)
+    }
+
+    /// Ponechá záznamy daného účtu.
+    pub fn account(mut self, account: impl Into<String>) -> RecordFilter {
+        self.account = Some(account.into());
+        self
+    }
+
+    /// Ponechá záznamy, ktoré majú daný štítok.
+    pub fn tag(mut self, tag: impl Into<String>) -> RecordFilter {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Ponechá záznamy, ktoré obsahujú text.
+    ///
+    /// Veľké a malé písmená sa nerozlišujú iba v ASCII (`A` = `a`, ale
+    /// `Č` ≠ `č`), rovnako ako pri SQLite `LIKE`.
+    pub fn text(mut self, text: impl Into<String>) -> RecordFilter {
+        self.text.push(text.into());
+        self
+    }
+
+    /// Vylúči záznamy danej kategórie.
+    pub fn exclude_category(mut self, category: ExpenseType) -> RecordFilter {
+        self.exclude_categories.insert(category);
+        self
+    }
+
+    /// Ponechá záznamy, ktorých poznámka obsahuje text.
+    ///
+    /// Veľké a malé písmená sa porovnávajú ako pri [`RecordFilter::text`].
+    pub fn note(mut self, text: impl Into<String>) -> RecordFilter {
+        self.notes.push(text.into());
+        self
+    }
+
+    /// Vylúči záznamy daného účtu.
+    pub fn exclude_account(mut self, account: impl Into<String>) -> RecordFilter {
+        self.exclude_accounts.push(account.into());
+        self
+    }
+
+    /// Vylúči záznamy s daným štítkom.
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> RecordFilter {
+        self.exclude_tags.push(tag.into());
+        self
+    }
+
+    /// Vylúči záznamy, ktoré obsahujú text.
+    pub fn exclude_text(mut self, text: impl Into<String>) -> RecordFilter {
+        self.exclude_text.push(text.into());
+        self
+    }
+
+    /// Vylúči záznamy, ktorých poznámka obsahuje text.
+    pub fn exclude_note(mut self, text: impl Into<String>) -> RecordFilter {
+        self.exclude_notes.push(text.into());
+        self
+    }
+
+    /// Pridá stav medzi povolené stavy.
+    pub fn status(mut self, status: RecordStatus) -> RecordFilter {
+        self.statuses
+            .get_or_insert_with(BTreeSet::new)
+            .insert(status);
+        self
+    }
+
+    /// Vylúči záznamy v danom stave.
+    pub fn exclude_status(mut self, status: RecordStatus) -> RecordFilter {
+        self.statuses
+            .get_or_insert_with(|| RecordStatus::ALL.into_iter().collect())
+            .remove(&status);
+        self
+    }
+
+    /// Vráti `true`, ak filter nemá žiadnu podmienku výberu.
+    ///
+    /// Triedenie a limit sa neberú do úvahy.
+    pub fn is_empty(&self) -> bool {
+        *self
+            == RecordFilter {
+                sort: self.sort,
+                limit: self.limit,
+                ..RecordFilter::default()
+            }
+    }
+
+    /// Nastaví triedenie výsledku.
+    pub fn sort_by(mut self, key: SortKey, order: SortOrder) -> RecordFilter {
+        self.sort = (key, order);
+        self
+    }
+
+    /// Obmedzí počet vrátených záznamov.
+    pub fn limit(mut self, limit: usize) -> RecordFilter {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Vráti `true`, ak záznam vyhovuje všetkým podmienkam filtra.
+    ///
+    /// Triedenie a limit sa pri tom neuplatňujú. Záznamy v koši
+    /// nevyhovujú žiadnemu filtru.
+    pub fn matches(&self, record: &Record) -> bool {
+        if record.deleted_on.is_some() {
+            return false;
+        }
+        if self.from.is_some_and(|from| record.time < from)
+            || self.to.is_some_and(|to| record.time > to)
+        {
+            return false;
+        }
+        if self.money_type.is_some_and(|t| record.money_type != t) {
+            return false;
+        }
+        if let Some(categories) = &self.categories {
+            match record.expense {
+                Some(e) if categories.contains(&e) => {}
+                _ => return false,
+            }
+        }
+        if self.min_amount.is_some_and(|min| record.amount < min)
+            || self.max_amount.is_some_and(|max| record.amount > max)
+        {
+            return false;
+        }
+        if let Some(account) = &self.account
+            && record.account.as_deref() != Some(account.as_str())
+        {
+            return false;
+        }
+        if !self.tags.iter().all(|t| record.has_tag(t)) {
+            return false;
+        }
+        if !self.text.iter().all(|t| contains_text(record, t)) {
+            return false;
+        }
+        if !self.notes.iter().all(|t| note_contains(record, t)) {
+            return false;
+        }
+        if let Some(statuses) = &self.statuses
+            && !statuses.contains(&record.status)
+        {
+            return false;
+        }
+        if record
+            .expense
+            .is_some_and(|e| self.exclude_categories.contains(&e))
+        {
+            return false;
+        }
+        if let Some(account) = &record.account
+            && self.exclude_accounts.contains(account)
+        {
+            return false;
+        }
+        if self.exclude_tags.iter().any(|t| record.has_tag(t)) {
+            return false;
+        }
+        if self.exclude_text.iter().any(|t| contains_text(record, t)) {
+            return false;
+        }
+        if self.exclude_notes.iter().any(|t| note_contains(record, t)) {
+            return false;
+        }
+        true
+    }
+
+    /// Vyhodnotí filter nad záznamami v pamäti.
+    ///
+    /// Vráti kópie vyhovujúcich záznamov zoradené podľa filtra
+    /// (pri zhode podľa ID) a obmedzené limitom.
+    pub fn apply<'a>(&self, records: impl IntoIterator<Item = &'a Record>) -> Vec<Record> {
+        let mut ret: Vec<Record> = records
+            .into_iter()
+            .filter(|r| self.matches(r))
+            .cloned()
+            .collect();
+
+        let (key, order) = self.sort;
+        ret.sort_by(|a, b| {
+            let ordering = match key {
+                SortKey::Id => a.id.cmp(&b.id),
+                SortKey::Date => a.time.cmp(&b.time),
+                SortKey::Amount => a.amount.total_cmp(&b.amount),
+                SortKey::Category => a.expense.cmp(&b.expense),
+            };
+            let ordering = match order {
+                SortOrder::Ascending => ordering,
+                SortOrder::Descending => ordering.reverse(),
+            };
+            ordering.then(a.id.cmp(&b.id))
+        });
+
+        if let Some(limit) = self.limit {
+            ret.truncate(limit);
+        }
+        ret
+    }
+
+    /// Preloží filter na Diesel dopyt nad tabuľkou `records`.
+    ///
+    /// Výsledok dopytu zodpovedá výsledku [`RecordFilter::apply`]
+    /// nad rovnakými záznamami. Záznamy v koši sa vynechajú.
+    pub fn to_query(&self) -> records::BoxedQuery<'static, Sqlite> {
+        let mut query = records::table
+            .filter(records::deleted_on.is_null())
+            .into_boxed();
+
+        if let Some(from) = self.from {
+            query = query.filter(records::time.ge(from.format(DB_DATE_FORMAT).to_string()));
+        }
+        if let Some(to) = self.to {
+            query = query.filter(records::time.le(to.format(DB_DATE_FORMAT).to_string()));
+        }
+        if let Some(money_type) = self.money_type {
+            query = query.filter(records::money_type.eq(money_type.code()));
+        }
+        if let Some(categories) = &self.categories {
+            let codes: Vec<&str> = categories.iter().map(|c| c.code()).collect();
+            query = query.filter(records::expense.eq_any(codes));
+        }
+        if let Some(min) = self.min_amount {
+            query = query.filter(records::amount.ge(min));
+        }
+        if let Some(max) = self.max_amount {
+            query = query.filter(records::amount.le(max));
+        }
+        if let Some(account) = &self.account {
+            query = query.filter(records::account.eq(account.clone()));
+        }
+        for tag in &self.tags {
+            query = query.filter(tag_condition(tag));
+        }
+        for text in &self.text {
+            query = query.filter(text_condition(text));
+        }
+        for text in &self.notes {
+            query = query.filter(like("coalesce(note, '')", text));
+        }
+        if let Some(statuses) = &self.statuses {
+            let codes: Vec<&str> = statuses.iter().map(|s| s.code()).collect();
+            query = query.filter(records::status.eq_any(codes));
+        }
+        if !self.exclude_categories.is_empty() {
+            let codes: Vec<&str> = self.exclude_categories.iter().map(|c| c.code()).collect();
+            query = query.filter(
+                records::expense
+                    .ne_all(codes)
+                    .or(records::expense.is_null()),
+            );
+        }
+        for account in &self.exclude_accounts {
+            query = query.filter(
+                records::account
+                    .ne(account.clone())
+                    .or(records::account.is_null()),
+            );
+        }
+        for tag in &self.exclude_tags {
+            query = query.filter(not(tag_condition(tag)));
+        }
+        for text in &self.exclude_text {
+            query = query.filter(not(text_condition(text)));
+        }
+        for text in &self.exclude_notes {
+            query = query.filter(not(like("coalesce(note, '')", text)));
+        }
+
+        let (key, order) = self.sort;
+        query = match (key, order) {
+            (SortKey::Id, SortOrder::Ascending) => query.order(records::id.asc()),
+            (SortKey::Id, SortOrder::Descending) => query.order(records::id.desc()),
+            (SortKey::Date, SortOrder::Ascending) => {
+                query.order((records::time.asc(), records::id.asc()))
+            }
+            (SortKey::Date, SortOrder::Descending) => {
+                query.order((records::time.desc(), records::id.asc()))
+            }
+            (SortKey::Amount, SortOrder::Ascending) => {
+                query.order((records::amount.asc(), records::id.asc()))
+            }
+            (SortKey::Amount, SortOrder::Descending) => {
+                query.order((records::amount.desc(), records::id.asc()))
+            }
+            (SortKey::Category, SortOrder::Ascending) => {
+                query.order((category_rank().asc(), records::id.asc()))
+            }
+            (SortKey::Category, SortOrder::Descending) => {
+                query.order((category_rank().desc(), records::id.asc()))
+            }
+        };
+
+        if let Some(limit) = self.limit {
+            query = query.limit(limit as i64);
+        }
+        query
+    }
+}
+
+/// Podmienka nad tabuľkou `records` použiteľná v zloženom dopyte.
+type SqlCondition = Box<dyn BoxableExpression<records::table, Sqlite, SqlType = Bool>>;
+
+/// Vráti `true`, ak záznam obsahuje text v poznámke, účte, štítku
+/// alebo kóde kategórie.
+///
+/// Veľké a malé písmená sa nerozlišujú iba v ASCII, rovnako ako pri
+/// SQLite `LIKE` v [`text_condition`]. Prázdny text obsahuje každý záznam.
+fn contains_text(record: &Record, text: &str) -> bool {
+    text.is_empty()
+        || note_contains(record, text)
+        || record.account.as_deref().is_some_and(|a| contains(a, text))
+        || record.tags.iter().any(|t| contains(t, text))
+        || record.expense.is_some_and(|e| contains(e.code(), text))
+}
+
+/// Vráti `true`, ak poznámka záznamu obsahuje text, pozri [`contains_text`].
+fn note_contains(record: &Record, text: &str) -> bool {
+    contains(record.note.as_deref().unwrap_or_default(), text)
+}
+
+/// Vráti `true`, ak `s` obsahuje `text` bez rozlíšenia veľkosti písmen v ASCII.
+fn contains(s: &str, text: &str) -> bool {
+    s.to_ascii_lowercase().contains(&text.to_ascii_lowercase())
+}
+
+/// SQL podmienka na štítok zhodná s [`Record::has_tag`].
+///
+/// Štítok sa porovná presne, s rozlíšením veľkosti písmen.
+fn tag_condition(tag: &str) -> SqlCondition {
+    Box::new(
+        sql::<Bool>("instr(',' || tags || ',', ',' || ")
+            .bind::<Text, _>(tag.to_string())
+            .sql(" || ',') > 0"),
+    )
+}
+
+/// SQL podmienka na hľadaný text zhodná s [`contains_text`].
+///
+/// Znaky `%` a `_` v texte sa hľadajú doslova. Štítky sú v databáze
+/// spojené čiarkou, text s čiarkou preto v štítkoch nehľadá, aby sa
+/// nezhodoval s dvoma susednými štítkami.
+fn text_condition(text: &str) -> SqlCondition {
+    let mut condition: SqlCondition = Box::new(
+        like("coalesce(note, '')", text).or(like("coalesce(account, '')", text)),
+    );
+    if !text.contains(TAG_SEPARATOR) {
+        condition = Box::new(condition.or(like("tags", text)));
+    }
+    Box::new(condition.or(like("coalesce(expense, 'NONE') <> 'NONE' AND expense", text)))
+}
+
+/// SQL podmienka, že stĺpec `column` obsahuje text, zhodná s [`contains`].
+fn like(column: &str, text: &str) -> SqlCondition {
+    Box::new(
+        sql::<Bool>(&format!("{} LIKE ", column))
+            .bind::<Text, _>(format!("%{}%", escape_like(text)))
+            .sql(" ESCAPE '\\'"),
+    )
+}
+
+/// Označí znaky `\`, `%` a `_` pre SQL `LIKE ... ESCAPE '\'`.
+fn escape_like(text: &str) -> String {
+    let mut ret = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            ret.push('\\');
+        }
+        ret.push(c);
+    }
+    ret
+}
+
+/// Poradie kategórie v SQL zhodné s poradím [`ExpenseType`] v pamäti.
+///
+/// Záznamy bez kategórie (`NONE`) sú pred všetkými kategóriami,
+/// rovnako ako `None` pri porovnaní `Option<ExpenseType>`.
+fn category_rank() -> diesel::expression::SqlLiteral<diesel::sql_types::Integer> {
+    let cases: String = ExpenseType::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!(" WHEN '{}' THEN {}", c.code(), i + 1))
+        .collect();
+    sql::<diesel::sql_types::Integer>(&format!("CASE expense{} ELSE 0 END", cases))
+}
@@ -0,0 +1,195 @@
+//! Stav záznamov a odsúhlasenie s výpisom z účtu.
+//!
+//! Každý záznam je čakajúci ([`RecordStatus::Pending`]), zúčtovaný bankou
+//! ([`RecordStatus::Cleared`]) alebo odsúhlasený s výpisom
+//! ([`RecordStatus::Reconciled`]). Odsúhlasené záznamy sú zamknuté:
+//! databázová vrstva ich neupraví ani nezmaže, kým sa ich stav
+//! výslovne nezmení cez [`crate::db::set_status`].
+//!
+//! [`Reconciliation`] drží rozpracované odsúhlasenie – dátum a zostatok
+//! z výpisu a označené záznamy. Rozdiel medzi výpisom a označenými
+//! záznamami sa počíta priebežne, a keď je nulový, označené záznamy
+//! sa dajú odsúhlasiť. Výpis je v jednej mene, odsúhlasujú sa preto iba
+//! záznamy v mene výpisu.
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::record::Record;
+
+/// Najväčší rozdiel, ktorý sa ešte považuje za odsúhlasený (zaokrúhlenie).
+const TOLERANCE: f32 = 0.005;
+
+/// Stav záznamu voči banke.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RecordStatus {
+    /// zadaný, ale ešte nezúčtovaný bankou
+    #[default]
+    Pending,
+    /// zúčtovaný bankou
+    Cleared,
+    /// odsúhlasený s výpisom, zamknutý proti úpravám
+    Reconciled,
+}
+
+impl RecordStatus {
+    /// Všetky stavy v poradí, v akom nimi záznam prechádza.
+    pub const ALL: [RecordStatus; 3] = [
+        RecordStatus::Pending,
+        RecordStatus::Cleared,
+        RecordStatus::Reconciled,
+    ];
+
+    /// Vráti kód stavu, ktorý sa ukladá do databázy.
+    pub fn code(&self) -> &'static str {
+        match self {
+            RecordStatus::Pending => "PENDING",
+            RecordStatus::Cleared => "CLEARED",
+            RecordStatus::Reconciled => "RECONCILED",
+        }
+    }
+
+    /// Vráti názov stavu pre používateľa v jazyku z nastavení.
+    pub fn name(&self) -> &'static str {
+        crate::i18n::tr(&format!("status.{}", self.code()))
+    }
+
+    /// Vráti značku stavu pre výpis záznamu.
+    pub fn symbol(&self) -> char {
+        match self {
+            RecordStatus::Pending => ' ',
+            RecordStatus::Cleared => '*',
+            RecordStatus::Reconciled => 'R',
+        }
+    }
+
+    /// Určí, či je záznam zamknutý proti úpravám a mazaniu.
+    pub fn is_locked(&self) -> bool {
+        *self == RecordStatus::Reconciled
+    }
+}
+
+impl FromStr for RecordStatus {
+    type Err = String;
+
+    /// Prevedie kód stavu bez ohľadu na veľkosť písmen.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RecordStatus::ALL
+            .into_iter()
+            .find(|st| st.code().eq_ignore_ascii_case(s))
+            .ok_or_else(|| crate::i18n::trf("record.unknown_status", &[&s]))
+    }
+}
+
+/// Rozpracované odsúhlasenie záznamov s výpisom z účtu.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconciliation {
+    /// posledný deň výpisu
+    pub end: NaiveDate,
+    /// konečný zostatok podľa výpisu
+    pub statement_balance: f32,
+    /// mena výpisu, záznamy v iných menách sa neodsúhlasujú
+    pub currency: String,
+    /// odsúhlasovaný účet, `None` znamená všetky záznamy
+    pub account: Option<String>,
+    /// ID záznamov označených ako zhodné s výpisom
+    pub selected: BTreeSet<i32>,
+}
+
+impl Reconciliation {
+    /// Začne odsúhlasenie výpisu bez označených záznamov.
+    ///
+    /// # Arguments
+    /// * `end` – posledný deň výpisu
+    /// * `statement_balance` – konečný zostatok podľa výpisu
+    /// * `currency` – mena výpisu, napr. `EUR`
+    pub fn new(
+        end: NaiveDate,
+        statement_balance: f32,
+        currency: impl Into<String>,
+    ) -> Reconciliation {
+        Reconciliation {
+            end,
+            statement_balance,
+            currency: currency.into(),
+            account: None,
+            selected: BTreeSet::new(),
+        }
+    }
+
+    /// Obmedzí odsúhlasenie na jeden účet.
+    pub fn with_account(mut self, account: impl Into<String>) -> Reconciliation {
+        self.account = Some(account.into());
+        self
+    }
+
+    /// Určí, či záznam patrí k odsúhlasovanému účtu a je v mene výpisu.
+    fn in_account(&self, record: &Record) -> bool {
+        (self.account.is_none() || record.account == self.account)
+            && record.currency == self.currency
+    }
+
+    /// Vráti záznamy, ktoré sa dajú označiť: neodsúhlasené záznamy účtu
+    /// do konca výpisu zoradené podľa dátumu.
+    pub fn candidates<'a>(&self, records: &'a [Record]) -> Vec<&'a Record> {
+        let mut ret: Vec<&Record> = records
+            .iter()
+            .filter(|r| self.in_account(r) && !r.status.is_locked() && r.time <= self.end)
+            .collect();
+        ret.sort_by_key(|r| (r.time, r.id));
+        ret
+    }
+
+    /// Označí všetky zúčtované záznamy, ktoré sa dajú odsúhlasiť.
+    pub fn select_cleared(&mut self, records: &[Record]) {
+        let cleared: Vec<i32> = self
+            .candidates(records)
+            .into_iter()
+            .filter(|r| r.status == RecordStatus::Cleared)
+            .map(|r| r.id)
+            .collect();
+        self.selected.extend(cleared);
+    }
+
+    /// Prepne označenie záznamu.
+    pub fn toggle(&mut self, id: i32) {
+        if !self.selected.remove(&id) {
+            self.selected.insert(id);
+        }
+    }
+
+    /// Zostatok už odsúhlasených záznamov účtu, teda začiatočný zostatok výpisu.
+    pub fn opening_balance(&self, records: &[Record]) -> f32 {
+        records
+            .iter()
+            .filter(|r| self.in_account(r) && r.status.is_locked())
+            .fold(0.0, |sum, r| sum + r.signed_amount())
+    }
+
+    /// Zostatok po započítaní označených záznamov.
+    pub fn cleared_balance(&self, records: &[Record]) -> f32 {
+        let selected: f32 = self
+            .candidates(records)
+            .into_iter()
+            .filter(|r| self.selected.contains(&r.id))
+            .map(|r| r.signed_amount())
+            .sum();
+        self.opening_balance(records) + selected
+    }
+
+    /// Rozdiel medzi zostatkom z výpisu a označenými záznamami.
+    pub fn difference(&self, records: &[Record]) -> f32 {
+        self.statement_balance - self.cleared_balance(records)
+    }
+
+    /// Určí, či označené záznamy presne vysvetľujú zostatok z výpisu.
+    pub fn is_balanced(&self, records: &[Record]) -> bool {
+        self.difference(records).abs() < TOLERANCE
+    }
+}
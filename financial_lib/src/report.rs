@@ -0,0 +1,148 @@
+//! Reporty nad súhrnmi záznamov.
+//!
+//! - mesačný prehľad ([`monthly_report`]): pre každý mesiac vybraného
+//!   rozsahu súčet príjmov, výdavkov, čisté úspory a mieru úspor.
+//!   Mesiace bez záznamov sa vrátia s nulami, aby sa dali priamo
+//!   vykresliť v grafe.
+//! - porovnanie dvoch období ([`compare_categories`]): zmena výdavkov
+//!   v každej kategórii v absolútnej hodnote aj v percentách.
+
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+use chrono::{Months, NaiveDate};
+
+use crate::aggregate::{Bucket, GroupKey, Period};
+use crate::record::ExpenseType;
+
+/// Súhrn jedného mesiaca.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthSummary {
+    /// prvý deň mesiaca
+    pub month: NaiveDate,
+    /// súčet príjmov
+    pub income: f32,
+    /// súčet výdavkov bez nákupu investícií (kladné číslo)
+    pub expense: f32,
+    /// čisté úspory, teda príjmy mínus výdavky; investície sa rátajú do úspor
+    pub net: f32,
+    /// podiel úspor na príjmoch, `None` ak mesiac nemá príjmy
+    pub savings_rate: Option<f32>,
+}
+
+impl MonthSummary {
+    fn from_bucket(month: NaiveDate, bucket: Option<&Bucket>) -> MonthSummary {
+        let (income, expense) = bucket.map_or((0.0, 0.0), |b| (b.income, b.expense));
+        let net = income - expense;
+        MonthSummary {
+            month,
+            income,
+            expense,
+            net,
+            savings_rate: (income > 0.0).then(|| net / income),
+        }
+    }
+}
+
+/// Zostaví mesačný prehľad pre mesiace zasahujúce do rozsahu `from..=to`.
+///
+/// Vstupom sú mesačné súhrny bez ďalšieho delenia, napr. výsledok
+/// [`crate::aggregate::aggregate`] alebo [`crate::db::aggregate`]
+/// s [`Period::Month`] a bez skupiny.
+///
+/// # Arguments
+/// * `buckets` – mesačné súhrny záznamov
+/// * `from` – prvý deň rozsahu
+/// * `to` – posledný deň rozsahu
+pub fn monthly_report(buckets: &[Bucket], from: NaiveDate, to: NaiveDate) -> Vec<MonthSummary> {
+    Period::Month
+        .starts_between(from, to)
+        .into_iter()
+        .map(|month| {
+            let bucket = buckets
+                .iter()
+                .find(|b| b.start == month && b.key == GroupKey::All);
+            MonthSummary::from_bucket(month, bucket)
+        })
+        .collect()
+}
+
+/// S ktorým obdobím sa aktuálne obdobie porovnáva.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Comparison {
+    /// bezprostredne predchádzajúce obdobie, napr. minulý mesiac
+    PreviousPeriod,
+    /// rovnaké obdobie o rok skôr
+    PreviousYear,
+}
+
+impl Comparison {
+    /// Vráti rozsah aktuálneho obdobia, do ktorého patrí `date`,
+    /// a rozsah obdobia, s ktorým sa porovnáva.
+    ///
+    /// # Arguments
+    /// * `period` – dĺžka obdobia
+    /// * `date` – ľubovoľný deň aktuálneho obdobia
+    pub fn ranges(
+        &self,
+        period: Period,
+        date: NaiveDate,
+    ) -> (RangeInclusive<NaiveDate>, RangeInclusive<NaiveDate>) {
+        let start = period.start_of(date);
+        let current = start..=period.end_of(start);
+        let previous_start = match self {
+            Comparison::PreviousPeriod => period.start_of(start.pred_opt().expect("day before period")),
+            Comparison::PreviousYear => period.start_of(start - Months::new(12)),
+        };
+        (current, previous_start..=period.end_of(previous_start))
+    }
+}
+
+/// Zmena výdavkov jednej kategórie medzi dvoma obdobiami.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryChange {
+    pub category: ExpenseType,
+    /// výdavky v porovnávanom (staršom) období
+    pub previous: f32,
+    /// výdavky v aktuálnom období
+    pub current: f32,
+    /// `current - previous`
+    pub delta: f32,
+    /// zmena v percentách, `None` ak v porovnávanom období nebol výdavok
+    pub percent: Option<f32>,
+}
+
+/// Porovná výdavky podľa kategórií v dvoch obdobiach.
+///
+/// Vstupom sú súčty kategórií, napr. z [`crate::record::RecordManager::categories_to_hash`]
+/// alebo [`crate::db::category_totals`]. Výsledok obsahuje kategórie,
+/// ktoré majú výdavok aspoň v jednom z období, zoradené od najväčšieho
+/// nárastu po najväčší pokles.
+///
+/// # Arguments
+/// * `previous` – súčty kategórií porovnávaného obdobia
+/// * `current` – súčty kategórií aktuálneho obdobia
+pub fn compare_categories(
+    previous: &BTreeMap<ExpenseType, f32>,
+    current: &BTreeMap<ExpenseType, f32>,
+) -> Vec<CategoryChange> {
+    let mut ret: Vec<CategoryChange> = ExpenseType::ALL
+        .into_iter()
+        .map(|category| {
+            let previous = previous.get(&category).copied().unwrap_or(0.0);
+            let current = current.get(&category).copied().unwrap_or(0.0);
+            let delta = current - previous;
+            CategoryChange {
+                category,
+                previous,
+                current,
+                delta,
+                percent: (previous != 0.0).then(|| delta / previous * 100.0),
+            }
+        })
+        .filter(|c| c.previous != 0.0 || c.current != 0.0)
+        .collect();
+
+    ret.sort_by(|a, b| b.delta.total_cmp(&a.delta));
+    ret
+}
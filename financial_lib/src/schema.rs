@@ -1,5 +1,58 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    audit_log (id) {
+        id -> Nullable<Integer>,
+        record_id -> Integer,
+        operation -> Text,
+        before -> Nullable<Text>,
+        after -> Nullable<Text>,
+        time -> Text,
+        actor -> Text,
+    }
+}
+
+diesel::table! {
+    exchange_rates (id) {
+        id -> Nullable<Integer>,
+        from_currency -> Text,
+        to_currency -> Text,
+        time -> Text,
+        rate -> Float,
+    }
+}
+
+diesel::table! {
+    history (id) {
+        id -> Nullable<Integer>,
+        description -> Text,
+        changes -> Text,
+        undone -> Bool,
+    }
+}
+
+diesel::table! {
+    loans (id) {
+        id -> Nullable<Integer>,
+        name -> Text,
+        principal -> Float,
+        annual_rate -> Float,
+        term -> Integer,
+        frequency -> Text,
+        start -> Text,
+        tag -> Text,
+    }
+}
+
+diesel::table! {
+    prices (id) {
+        id -> Nullable<Integer>,
+        symbol -> Text,
+        time -> Text,
+        price -> Float,
+    }
+}
+
 diesel::table! {
     records (id) {
         id -> Nullable<Integer>,
@@ -7,5 +60,75 @@ diesel::table! {
         amount -> Float,
         expense -> Nullable<Text>,
         time -> Text,
+        account -> Nullable<Text>,
+        tags -> Text,
+        note -> Nullable<Text>,
+        currency -> Text,
+        status -> Text,
+        deleted_on -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    suggestion_feedback (id) {
+        id -> Nullable<Integer>,
+        amount -> Float,
+        time -> Text,
+        category -> Text,
+        accepted -> Bool,
+        account -> Nullable<Text>,
+        note -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    accounts (name) {
+        name -> Text,
+        currency -> Text,
+    }
+}
+
+diesel::table! {
+    assets (id) {
+        id -> Nullable<Integer>,
+        name -> Text,
+        kind -> Text,
     }
 }
+
+diesel::table! {
+    trades (id) {
+        id -> Nullable<Integer>,
+        symbol -> Text,
+        time -> Text,
+        kind -> Text,
+        quantity -> Float,
+        price -> Float,
+        fee -> Float,
+    }
+}
+
+diesel::table! {
+    valuations (id) {
+        id -> Nullable<Integer>,
+        asset_id -> Integer,
+        time -> Text,
+        value -> Float,
+    }
+}
+
+diesel::joinable!(valuations -> assets (asset_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    accounts,
+    assets,
+    audit_log,
+    exchange_rates,
+    history,
+    loans,
+    prices,
+    records,
+    suggestion_feedback,
+    trades,
+    valuations,
+);
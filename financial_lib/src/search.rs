@@ -0,0 +1,454 @@
+//! Textový jazyk na vyhľadávanie záznamov.
+//!
+//! Dopyt sa skladá z výrazov oddelených medzerami, ktoré musia platiť
+//! všetky naraz. Výsledkom je [`RecordFilter`]:
+//!
+//! | výraz                     | význam                                          |
+//! |---------------------------|-------------------------------------------------|
+//! | `cat:RESTAURANT,FUN`      | kategória je jedna z uvedených                  |
+//! | `type:+` / `type:-`       | iba príjmy / iba výdavky                        |
+//! | `amount>20`               | suma (aj `>=`, `<`, `<=`, `=`)                  |
+//! | `amount:10..50`           | suma v rozsahu                                  |
+//! | `date:2026-09..2026-10`   | dátum v rozsahu (rok, mesiac alebo deň)         |
+//! | `acc:card`                | účet                                            |
+//! | `tag:work`                | štítok                                          |
+//! | `note:"x y"`              | text v poznámke                                 |
+//! | `status:pending,cleared`  | stav je jeden z uvedených                       |
+//! | `sort:amount`, `sort:-date` | triedenie (`-` znamená zostupne)            |
+//! | `limit:10`                | najviac toľko záznamov                          |
+//! | `pizza`, `"pizza place"`  | hľadaný text                                    |
+//!
+//! Hodnota poľa môže byť v úvodzovkách, napr. `acc:"my card"`. Výraz,
+//! ktorý úvodzovkami začína, je vždy hľadaný text, `"cat:FUN"` teda
+//! hľadá text `cat:FUN`.
+//!
+//! Výrazy `cat:`, `acc:`, `tag:`, `note:`, `status:` a hľadaný text sa dajú vylúčiť
+//! znakom `-` na začiatku, napr. `-tag:work`.
+//!
+//! Pri chybe sa vráti [`ParseError`] s pozíciou znaku, kde chyba nastala.
+
+use std::fmt;
+
+use chrono::{Months, NaiveDate};
+
+use crate::i18n::{tr, trf};
+use crate::query::{RecordFilter, SortKey, SortOrder, YEARS};
+use crate::reconcile::RecordStatus;
+use crate::record::{ExpenseType, MoneyType};
+
+/// Chyba pri spracovaní dopytu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// pozícia znaku (od 0), na ktorej chyba nastala
+    pub position: usize,
+    /// popis chyby
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(position: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&trf("search.position", &[&(self.position + 1), &self.message]))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Jeden výraz dopytu spolu s pozíciami jeho znakov.
+#[derive(Debug)]
+struct Token {
+    /// pozícia znaku `-` alebo prvého znaku výrazu
+    start: usize,
+    /// text výrazu bez úvodzoviek
+    text: String,
+    /// pozícia každého znaku `text` v dopyte
+    positions: Vec<usize>,
+    /// pozícia za posledným znakom výrazu v dopyte
+    end: usize,
+    /// koľko znakov na začiatku `text` bolo mimo úvodzoviek
+    unquoted: usize,
+    /// pozícia prvej otváracej úvodzovky v dopyte
+    quote: Option<usize>,
+    /// výraz začínal znakom `-`
+    negated: bool,
+}
+
+impl Token {
+    /// Vráti pozíciu znaku `index` z `text` v dopyte.
+    ///
+    /// Prázdna hodnota v úvodzovkách (`acc:""`) má pozíciu úvodzovky.
+    fn at(&self, index: usize) -> usize {
+        match self.positions.get(index) {
+            Some(&position) => position,
+            None if index == self.unquoted => self.quote.unwrap_or(self.end),
+            None => self.end,
+        }
+    }
+
+    /// Rozdelí výraz na pole a hodnotu, ak pred prvou dvojbodkou
+    /// nie sú úvodzovky, napr. `acc:"my card"`.
+    ///
+    /// Vráti názov poľa, hodnotu a index prvého znaku hodnoty v `text`.
+    fn field(&self) -> Option<(&str, &str, usize)> {
+        let colon = self.text.chars().take(self.unquoted).position(|c| c == ':')?;
+        let (key, value) = self.text.split_at(self.text.char_indices().nth(colon)?.0);
+        Some((key, &value[1..], colon + 1))
+    }
+}
+
+/// Rozdelí dopyt na výrazy podľa medzier, s ohľadom na úvodzovky.
+///
+/// Úvodzovky môžu byť kdekoľvek vo výraze, napr. `"pizza place"` alebo
+/// `note:"x y"`, a medzery v nich výraz nerozdeľujú.
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut ret = Vec::new();
+    let mut chars = input.chars().enumerate().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut negated = false;
+        if c == '-' {
+            chars.next();
+            match chars.peek() {
+                Some(&(_, c)) if !c.is_whitespace() => negated = true,
+                _ => return Err(ParseError::new(start, tr("search.expected_expression"))),
+            }
+        }
+
+        let mut text = String::new();
+        let mut positions = Vec::new();
+        let mut unquoted = None;
+        let mut quote = None;
+        let mut end = input.chars().count();
+        while let Some(&(i, c)) = chars.peek() {
+            if c.is_whitespace() {
+                end = i;
+                break;
+            }
+            chars.next();
+            if c != '"' {
+                text.push(c);
+                positions.push(i);
+                continue;
+            }
+            unquoted.get_or_insert(positions.len());
+            quote.get_or_insert(i);
+            loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((j, c)) => {
+                        text.push(c);
+                        positions.push(j);
+                    }
+                    None => return Err(ParseError::new(i, tr("search.unterminated_quote"))),
+                }
+            }
+        }
+        ret.push(Token {
+            start,
+            unquoted: unquoted.unwrap_or(positions.len()),
+            quote,
+            text,
+            positions,
+            end,
+            negated,
+        });
+    }
+
+    Ok(ret)
+}
+
+/// Spracuje textový dopyt na [`RecordFilter`].
+///
+/// # Errors
+/// Vráti [`ParseError`] s pozíciou chybného výrazu, ak dopyt
+/// nie je platný.
+pub fn parse_query(input: &str) -> Result<RecordFilter, ParseError> {
+    let mut filter = RecordFilter::new();
+
+    for token in tokenize(input)? {
+        filter = apply_token(filter, &token)?;
+    }
+
+    Ok(filter)
+}
+
+/// Pridá jeden výraz do filtra.
+fn apply_token(filter: RecordFilter, token: &Token) -> Result<RecordFilter, ParseError> {
+    let negated = token.negated;
+    let body = token.text.as_str();
+    let start = token.start;
+    let at = |index: usize| token.at(index);
+
+    let amount = "amount".len();
+    if token.unquoted > amount
+        && let Some(rest) = body.strip_prefix("amount")
+        && rest.starts_with(['>', '<', '=', ':'])
+    {
+        if negated {
+            return Err(ParseError::new(start, trf("search.negated", &[&"amount"])));
+        }
+        return parse_amount(filter, rest, &|i| at(amount + i));
+    }
+
+    let Some((key, value, value_index)) = token.field() else {
+        return Ok(if negated {
+            filter.exclude_text(body)
+        } else {
+            filter.text(body)
+        });
+    };
+    let value_position = at(value_index);
+    if value.is_empty() {
+        return Err(ParseError::new(value_position, tr("search.missing_value")));
+    }
+
+    match key {
+        "cat" | "category" => {
+            let mut filter = filter;
+            let mut item_index = value_index;
+            for code in value.split(',') {
+                let category: ExpenseType = code.to_uppercase().parse().map_err(|_| {
+                    ParseError::new(at(item_index), trf("search.unknown_category", &[&code]))
+                })?;
+                filter = if negated {
+                    filter.exclude_category(category)
+                } else {
+                    filter.category(category)
+                };
+                item_index += code.chars().count() + 1;
+            }
+            Ok(filter)
+        }
+        "status" => {
+            let mut filter = filter;
+            let mut item_index = value_index;
+            for code in value.split(',') {
+                let status: RecordStatus = code.parse().map_err(|_| {
+                    ParseError::new(at(item_index), trf("search.unknown_status", &[&code]))
+                })?;
+                filter = if negated {
+                    filter.exclude_status(status)
+                } else {
+                    filter.status(status)
+                };
+                item_index += code.chars().count() + 1;
+            }
+            Ok(filter)
+        }
+        "acc" | "account" => Ok(if negated {
+            filter.exclude_account(value)
+        } else {
+            filter.account(value)
+        }),
+        "tag" => Ok(if negated {
+            filter.exclude_tag(value)
+        } else {
+            filter.tag(value)
+        }),
+        "note" => Ok(if negated {
+            filter.exclude_note(value)
+        } else {
+            filter.note(value)
+        }),
+        "type" => {
+            let money_type = match value.to_lowercase().as_str() {
+                "+" | "income" => MoneyType::INCOME,
+                "-" | "expense" => MoneyType::EXPENSE,
+                _ => {
+                    return Err(ParseError::new(
+                        value_position,
+                        trf("search.unknown_type", &[&value]),
+                    ));
+                }
+            };
+            let money_type = match (negated, money_type) {
+                (false, t) => t,
+                (true, MoneyType::INCOME) => MoneyType::EXPENSE,
+                (true, MoneyType::EXPENSE) => MoneyType::INCOME,
+            };
+            Ok(filter.money_type(money_type))
+        }
+        "date" => {
+            if negated {
+                return Err(ParseError::new(start, trf("search.negated", &[&"date"])));
+            }
+            parse_date_range(filter, value, &|i| at(value_index + i))
+        }
+        "sort" => {
+            if negated {
+                return Err(ParseError::new(start, trf("search.negated", &[&"sort"])));
+            }
+            let (order, name) = match value.strip_prefix('-') {
+                Some(name) => (SortOrder::Descending, name),
+                None => (SortOrder::Ascending, value),
+            };
+            let key = match name {
+                "id" => SortKey::Id,
+                "date" => SortKey::Date,
+                "amount" => SortKey::Amount,
+                "cat" | "category" => SortKey::Category,
+                _ => {
+                    return Err(ParseError::new(
+                        value_position,
+                        trf("search.unknown_sort", &[&name]),
+                    ));
+                }
+            };
+            Ok(filter.sort_by(key, order))
+        }
+        "limit" => {
+            if negated {
+                return Err(ParseError::new(start, trf("search.negated", &[&"limit"])));
+            }
+            let limit = value.parse().map_err(|_| {
+                ParseError::new(value_position, trf("search.invalid_limit", &[&value]))
+            })?;
+            Ok(filter.limit(limit))
+        }
+        _ => Err(ParseError::new(at(0), trf("search.unknown_field", &[&key]))),
+    }
+}
+
+/// Pozícia v dopyte pre index znaku v spracovávanej hodnote.
+type Position<'a> = &'a dyn Fn(usize) -> usize;
+
+/// Spracuje podmienku na sumu, `rest` je text za slovom `amount`.
+fn parse_amount(
+    filter: RecordFilter,
+    rest: &str,
+    at: Position,
+) -> Result<RecordFilter, ParseError> {
+    let operators = [">=", "<=", ">", "<", "=", ":"];
+    let operator = operators
+        .into_iter()
+        .find(|op| rest.starts_with(op))
+        .expect("amount is always followed by an operator");
+    let value = &rest[operator.len()..];
+    let value_index = operator.len();
+    let number = |text: &str, index: usize| -> Result<f32, ParseError> {
+        text.parse()
+            .ok()
+            .filter(|amount: &f32| amount.is_finite())
+            .ok_or_else(|| ParseError::new(at(index), trf("search.invalid_amount", &[&text])))
+    };
+
+    match operator {
+        ">=" | ">" => Ok(filter.min_amount(next_up(number(value, value_index)?, operator))),
+        "<=" | "<" => Ok(filter.max_amount(next_down(number(value, value_index)?, operator))),
+        "=" => {
+            let amount = number(value, value_index)?;
+            Ok(filter.amount_between(amount, amount))
+        }
+        _ => match value.split_once("..") {
+            Some((min, max)) => {
+                let mut filter = filter;
+                if !min.is_empty() {
+                    filter = filter.min_amount(number(min, value_index)?);
+                }
+                if !max.is_empty() {
+                    let max_index = value_index + min.chars().count() + 2;
+                    filter = filter.max_amount(number(max, max_index)?);
+                }
+                Ok(filter)
+            }
+            None => {
+                let amount = number(value, value_index)?;
+                Ok(filter.amount_between(amount, amount))
+            }
+        },
+    }
+}
+
+/// Pri ostrej nerovnosti posunie hranicu na najbližšiu vyššiu hodnotu.
+fn next_up(value: f32, operator: &str) -> f32 {
+    if operator == ">" {
+        value.next_up()
+    } else {
+        value
+    }
+}
+
+/// Pri ostrej nerovnosti posunie hranicu na najbližšiu nižšiu hodnotu.
+fn next_down(value: f32, operator: &str) -> f32 {
+    if operator == "<" {
+        value.next_down()
+    } else {
+        value
+    }
+}
+
+/// Spracuje rozsah dátumov `od..do`, kde obe strany sú voliteľné.
+fn parse_date_range(
+    filter: RecordFilter,
+    value: &str,
+    at: Position,
+) -> Result<RecordFilter, ParseError> {
+    let (from, to, to_index) = match value.split_once("..") {
+        Some((from, to)) => (from, to, from.chars().count() + 2),
+        None => (value, value, 0),
+    };
+
+    let mut filter = filter;
+    if !from.is_empty() {
+        filter = filter.from(parse_period(from, at(0))?.0);
+    }
+    if !to.is_empty() {
+        filter = filter.to(parse_period(to, at(to_index))?.1);
+    }
+    Ok(filter)
+}
+
+/// Spracuje rok (`2026`), mesiac (`2026-09`) alebo deň (`2026-09-15`)
+/// na prvý a posledný deň daného obdobia.
+///
+/// Rok musí byť v rozsahu [`YEARS`], inak by sa dátum v databáze
+/// porovnal v nesprávnom poradí.
+fn parse_period(text: &str, position: usize) -> Result<(NaiveDate, NaiveDate), ParseError> {
+    let error = || {
+        ParseError::new(position, trf("search.invalid_date", &[&text]))
+    };
+    let parts: Vec<&str> = text.split('-').collect();
+    let numbers: Vec<u32> = parts
+        .iter()
+        .map(|p| p.parse().map_err(|_| error()))
+        .collect::<Result<_, _>>()?;
+
+    let year = i32::try_from(numbers[0])
+        .ok()
+        .filter(|year| YEARS.contains(year))
+        .ok_or_else(error)?;
+
+    match numbers.as_slice() {
+        [_] => {
+            let from = NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(error)?;
+            let to = NaiveDate::from_ymd_opt(year, 12, 31).ok_or_else(error)?;
+            Ok((from, to))
+        }
+        [_, month] => {
+            let from = NaiveDate::from_ymd_opt(year, *month, 1).ok_or_else(error)?;
+            let to = from
+                .checked_add_months(Months::new(1))
+                .and_then(|d| d.pred_opt())
+                .ok_or_else(error)?;
+            Ok((from, to))
+        }
+        [_, month, day] => {
+            let date = NaiveDate::from_ymd_opt(year, *month, *day).ok_or_else(error)?;
+            Ok((date, date))
+        }
+        _ => Err(error()),
+    }
+}
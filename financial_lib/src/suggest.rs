@@ -0,0 +1,243 @@
+//! Návrhy kategórií výdavkov z histórie záznamov.
+//!
+//! Modul obsahuje jednoduchý naivný Bayesov klasifikátor, ktorý
+//! z minulých výdavkov odhadne najpravdepodobnejšiu kategóriu
+//! nového záznamu podľa veľkosti sumy, dňa v týždni, účtu a slov
+//! z poznámky (napr. názov obchodníka alebo popis nákupu).
+//!
+//! Spätná väzba používateľa ([`Feedback`]) návrhy ďalej upravuje:
+//! odmietnutý návrh zníži skóre kategórie pre podobné záznamy – so sumou
+//! rovnakej veľkosti, z rovnakého účtu a s niektorým slovom z poznámky.
+//! Prijatý návrh zruší jedno skoršie odmietnutie podobného záznamu.
+//! Samotný prijatý záznam je už v histórii, preto sa znovu nezapočíta.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::record::{ExpenseType, MoneyType, Record, RecordManager};
+
+/// Násobok skóre kategórie za každé odmietnutie podobného záznamu.
+const REJECT_PENALTY: f64 = 0.5;
+
+/// Spätná väzba používateľa k jednému návrhu kategórie.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feedback {
+    /// suma záznamu, pre ktorý bol návrh vytvorený
+    pub amount: f32,
+    /// dátum záznamu
+    pub time: NaiveDate,
+    /// účet záznamu
+    pub account: Option<String>,
+    /// poznámka záznamu
+    pub note: Option<String>,
+    /// navrhnutá kategória
+    pub category: ExpenseType,
+    /// či používateľ návrh ponechal
+    pub accepted: bool,
+}
+
+/// Štatistiky jednej kategórie potrebné pre výpočet pravdepodobností.
+#[derive(Debug, Default, Clone)]
+struct CategoryStats {
+    /// počet (vážených) výskytov kategórie
+    count: f64,
+    /// výskyty podľa rozsahu sumy
+    buckets: HashMap<i32, f64>,
+    /// výskyty podľa dňa v týždni (pondelok = 0)
+    weekdays: [f64; 7],
+    /// výskyty podľa účtu
+    accounts: HashMap<String, f64>,
+    /// výskyty slov z poznámok
+    words: HashMap<String, f64>,
+    /// počet všetkých slov z poznámok
+    word_count: f64,
+    /// počet odmietnutí podľa záznamu, ku ktorému návrh patril
+    rejected: Vec<(Context, u32)>,
+}
+
+/// Znaky záznamu, podľa ktorých sa spätná väzba vzťahuje na ďalšie záznamy.
+#[derive(Debug, Clone, PartialEq)]
+struct Context {
+    bucket: i32,
+    account: Option<String>,
+    words: HashSet<String>,
+}
+
+impl Context {
+    fn new(amount: f32, account: Option<&String>, note: Option<&String>) -> Context {
+        Context {
+            bucket: amount_bucket(amount),
+            account: account.cloned(),
+            words: note_words(note),
+        }
+    }
+
+    /// Určí, či je záznam podobný: má sumu v rovnakom rozsahu, rovnaký účet
+    /// a spoločné slovo z poznámky, ak poznámku má niektorý z nich.
+    fn matches(&self, other: &Context) -> bool {
+        self.bucket == other.bucket
+            && self.account == other.account
+            && ((self.words.is_empty() && other.words.is_empty())
+                || !self.words.is_disjoint(&other.words))
+    }
+}
+
+impl CategoryStats {
+    /// Započíta jeden výskyt kategórie.
+    fn observe(&mut self, record: &Record) {
+        self.count += 1.0;
+        *self.buckets.entry(amount_bucket(record.amount)).or_default() += 1.0;
+        self.weekdays[record.time.weekday().num_days_from_monday() as usize] += 1.0;
+        if let Some(account) = &record.account {
+            *self.accounts.entry(account.clone()).or_default() += 1.0;
+        }
+        for word in note_words(record.note.as_ref()) {
+            *self.words.entry(word).or_default() += 1.0;
+            self.word_count += 1.0;
+        }
+    }
+}
+
+/// Rozdelí poznámku záznamu na slová bez rozlíšenia veľkosti písmen.
+///
+/// Slová kratšie ako dva znaky a čísla sa vynechajú.
+fn note_words(note: Option<&String>) -> HashSet<String> {
+    note.into_iter()
+        .flat_map(|note| note.split(|c: char| !c.is_alphanumeric()))
+        .filter(|w| w.chars().count() > 1 && !w.chars().all(|c| c.is_numeric()))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Zaradí sumu do logaritmického rozsahu (1–2, 2–4, 4–8, ...).
+fn amount_bucket(amount: f32) -> i32 {
+    amount.abs().max(1.0).log2().floor() as i32
+}
+
+/// Klasifikátor, ktorý navrhuje kategóriu výdavku podľa histórie.
+#[derive(Debug, Default, Clone)]
+pub struct CategorySuggester {
+    stats: BTreeMap<ExpenseType, CategoryStats>,
+    /// celkový počet výskytov všetkých kategórií
+    total: f64,
+    /// počet rôznych rozsahov súm v histórii
+    bucket_count: usize,
+    /// počet rôznych účtov v histórii
+    account_count: usize,
+    /// počet rôznych slov z poznámok v histórii
+    vocabulary: usize,
+}
+
+impl CategorySuggester {
+    /// Natrénuje klasifikátor zo záznamov a uloženej spätnej väzby.
+    ///
+    /// Do úvahy sa berú iba výdavky s vyplnenou kategóriou.
+    ///
+    /// # Arguments
+    /// * `manager` – história záznamov
+    /// * `feedback` – prijaté a odmietnuté návrhy
+    pub fn train(manager: &RecordManager, feedback: &[Feedback]) -> CategorySuggester {
+        let mut ret = CategorySuggester::default();
+        let mut buckets = HashSet::new();
+        let mut accounts = HashSet::new();
+        let mut words = HashSet::new();
+
+        for r in manager.get_all() {
+            if r.money_type != MoneyType::EXPENSE {
+                continue;
+            }
+            if let Some(category) = r.expense {
+                ret.stats.entry(category).or_default().observe(&r);
+                buckets.insert(amount_bucket(r.amount));
+                accounts.extend(r.account.clone());
+                words.extend(note_words(r.note.as_ref()));
+            }
+        }
+
+        for f in feedback {
+            let context = Context::new(f.amount, f.account.as_ref(), f.note.as_ref());
+            let rejected = &mut ret.stats.entry(f.category).or_default().rejected;
+            if f.accepted {
+                if let Some((_, count)) = rejected
+                    .iter_mut()
+                    .find(|(c, count)| *count > 0 && c.matches(&context))
+                {
+                    *count -= 1;
+                }
+            } else if let Some((_, count)) = rejected.iter_mut().find(|(c, _)| *c == context) {
+                *count += 1;
+            } else {
+                rejected.push((context, 1));
+            }
+        }
+
+        ret.total = ret.stats.values().map(|s| s.count).sum();
+        ret.bucket_count = buckets.len();
+        ret.account_count = accounts.len();
+        ret.vocabulary = words.len();
+        ret
+    }
+
+    /// Vráti kategórie zoradené od najpravdepodobnejšej spolu s ich skóre.
+    ///
+    /// Skóre je normalizované tak, aby súčet bol 1. Kategórie bez histórie
+    /// sa nevracajú. Z rozpísaného záznamu sa použije suma, dátum, účet
+    /// a poznámka, kategória sa ignoruje.
+    pub fn ranked(&self, record: &Record) -> Vec<(ExpenseType, f64)> {
+        let context = Context::new(record.amount, record.account.as_ref(), record.note.as_ref());
+        let bucket = context.bucket;
+        let weekday = record.time.weekday().num_days_from_monday() as usize;
+        let words = &context.words;
+        // kategórie iba so spätnou väzbou nemajú históriu
+        let categories = self.stats.values().filter(|s| s.count > 0.0).count() as f64;
+        let buckets = self.bucket_count as f64 + 1.0;
+        let accounts = self.account_count as f64 + 1.0;
+        let vocabulary = self.vocabulary as f64 + 1.0;
+
+        let mut ret: Vec<(ExpenseType, f64)> = self
+            .stats
+            .iter()
+            .filter(|(_, s)| s.count > 0.0)
+            .map(|(category, s)| {
+                let prior = (s.count + 1.0) / (self.total + categories);
+                let p_bucket =
+                    (s.buckets.get(&bucket).copied().unwrap_or(0.0) + 1.0) / (s.count + buckets);
+                let p_weekday = (s.weekdays[weekday] + 1.0) / (s.count + 7.0);
+                let p_account = record.account.as_ref().map_or(1.0, |a| {
+                    (s.accounts.get(a).copied().unwrap_or(0.0) + 1.0) / (s.count + accounts)
+                });
+                let p_words: f64 = words
+                    .iter()
+                    .map(|w| {
+                        (s.words.get(w).copied().unwrap_or(0.0) + 1.0)
+                            / (s.word_count + vocabulary)
+                    })
+                    .product();
+                let rejected: u32 = s
+                    .rejected
+                    .iter()
+                    .filter(|(c, _)| c.matches(&context))
+                    .map(|(_, count)| count)
+                    .sum();
+                let penalty = REJECT_PENALTY.powi(rejected as i32);
+                let score = prior * p_bucket * p_weekday * p_account * p_words * penalty;
+                (*category, score)
+            })
+            .collect();
+
+        let sum: f64 = ret.iter().map(|(_, score)| score).sum();
+        if sum > 0.0 {
+            ret.iter_mut().for_each(|(_, score)| *score /= sum);
+        }
+        ret.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ret
+    }
+
+    /// Navrhne najpravdepodobnejšiu kategóriu pre rozpísaný výdavok.
+    ///
+    /// Vráti `None`, ak história neobsahuje žiadny výdavok s kategóriou.
+    pub fn suggest(&self, record: &Record) -> Option<ExpenseType> {
+        self.ranked(record).first().map(|(category, _)| *category)
+    }
+}
@@ -0,0 +1,308 @@
+//! Import a export záznamov v CSV a JSON.
+//!
+//! CSV má hlavičku `id,date,type,amount,currency,category,account,tags,note,status`,
+//! dátum v tvare `YYYY-MM-DD` a štítky oddelené bodkočiarkou. Polia s čiarkou
+//! alebo úvodzovkami sú v úvodzovkách, úvodzovka sa v nich zdvojí.
+//!
+//! JSON je pole záznamov v tvare [`Record`], povinné sú iba `money_type`,
+//! `amount` a `time`.
+//!
+//! Pri importe sa ID a dátum odstránenia zo súboru ignorujú, záznamy sa
+//! vkladajú ako nové. Príkazový riadok ich pridá jednou akciou
+//! ([`crate::history::Action`] uložená cez [`crate::db::commit`]), ktorá
+//! sa dá vrátiť ako celok.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+
+use crate::i18n::{tr, trf};
+use crate::models::{DB_DATE_FORMAT, TAG_SEPARATOR};
+use crate::reconcile::RecordStatus;
+use crate::record::{ExpenseType, MoneyType, Record};
+
+/// Hlavička CSV súboru so záznamami.
+pub const CSV_HEADER: &str = "id,date,type,amount,currency,category,account,tags,note,status";
+
+/// Oddeľovač štítkov v stĺpci `tags`.
+const CSV_TAG_SEPARATOR: char = ';';
+
+/// Formát súboru so záznamami.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    /// Určí formát podľa prípony súboru, `None` pri neznámej prípone.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(trf("import.unknown_format", &[&s])),
+        }
+    }
+}
+
+/// Chyba pri načítaní záznamov zo súboru.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError {
+    /// číslo riadku (od 1), ak sa dá určiť
+    pub line: Option<usize>,
+    /// popis chyby
+    pub message: String,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => f.write_str(&trf("import.line", &[&line, &self.message])),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Zapíše záznamy v danom formáte.
+pub fn export(records: &[Record], format: Format) -> String {
+    match format {
+        Format::Csv => records_to_csv(records),
+        Format::Json => records_to_json(records),
+    }
+}
+
+/// Načíta záznamy v danom formáte.
+///
+/// # Errors
+/// Vráti [`ImportError`] s číslom prvého chybného riadku.
+pub fn import(input: &str, format: Format) -> Result<Vec<Record>, ImportError> {
+    match format {
+        Format::Csv => parse_records_csv(input),
+        Format::Json => parse_records_json(input),
+    }
+}
+
+/// Zapíše záznamy ako JSON pole.
+pub fn records_to_json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).expect("records are always serializable")
+}
+
+/// Načíta záznamy z JSON poľa.
+///
+/// Záznamy sa importujú ako nové, dátum odstránenia sa preto zahodí.
+///
+/// # Errors
+/// Vráti [`ImportError`], ak JSON nie je pole záznamov.
+pub fn parse_records_json(input: &str) -> Result<Vec<Record>, ImportError> {
+    let records: Vec<Record> = serde_json::from_str(input).map_err(|e| ImportError {
+        line: Some(e.line()),
+        message: e.to_string(),
+    })?;
+    records
+        .into_iter()
+        .enumerate()
+        .map(|(i, r)| {
+            check_record(&r).map_err(|message| ImportError {
+                line: None,
+                message: trf("import.record", &[&(i + 1), &message]),
+            })?;
+            Ok(Record {
+                deleted_on: None,
+                ..r
+            })
+        })
+        .collect()
+}
+
+/// Zapíše záznamy ako CSV s hlavičkou [`CSV_HEADER`].
+pub fn records_to_csv(records: &[Record]) -> String {
+    let mut ret = String::from(CSV_HEADER);
+    ret.push('\n');
+    for r in records {
+        let tags = r.tags.join(&CSV_TAG_SEPARATOR.to_string());
+        let fields = [
+            r.id.to_string(),
+            r.time.format(DB_DATE_FORMAT).to_string(),
+            r.money_type.code().to_string(),
+            format!("{:.2}", r.amount),
+            r.currency.clone(),
+            r.expense.map_or(String::new(), |e| e.code().to_string()),
+            r.account.clone().unwrap_or_default(),
+            tags,
+            r.note.clone().unwrap_or_default(),
+            r.status.code().to_string(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| quote(f)).collect();
+        ret.push_str(&line.join(","));
+        ret.push('\n');
+    }
+    ret
+}
+
+/// Dá pole do úvodzoviek, ak obsahuje čiarku, úvodzovky alebo koniec riadku.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Záznam CSV rozdelený na polia s číslom riadku (od 1), na ktorom začína.
+type CsvRow = (usize, Vec<String>);
+
+/// Rozdelí CSV na záznamy a polia s ohľadom na úvodzovky.
+///
+/// Pole v úvodzovkách môže obsahovať aj koniec riadku, jeden záznam
+/// preto môže zaberať viac riadkov.
+///
+/// # Errors
+/// Vráti [`ImportError`] s riadkom začiatku záznamu s neukončenými úvodzovkami.
+fn split_rows(input: &str) -> Result<Vec<CsvRow>, ImportError> {
+    let mut rows = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                fields.push(std::mem::take(&mut field));
+                rows.push((start, std::mem::take(&mut fields)));
+                line += 1;
+                start = line;
+            }
+            '\n' => {
+                field.push(c);
+                line += 1;
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(ImportError {
+            line: Some(start),
+            message: tr("import.unterminated_quote").to_string(),
+        });
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        rows.push((start, fields));
+    }
+    Ok(rows)
+}
+
+/// Načíta záznamy z CSV s hlavičkou [`CSV_HEADER`].
+///
+/// Prázdne riadky a hlavička začínajúca slovom `id` sa preskočia. Prázdna
+/// kategória, účet, poznámka a stav znamenajú predvolenú hodnotu. Poznámka
+/// v úvodzovkách môže obsahovať aj koniec riadku.
+///
+/// # Errors
+/// Vráti [`ImportError`] s číslom riadku, na ktorom začína prvý chybný záznam.
+pub fn parse_records_csv(input: &str) -> Result<Vec<Record>, ImportError> {
+    let mut ret = Vec::new();
+    for (line, fields) in split_rows(input)? {
+        let first = fields[0].trim();
+        if (fields.len() == 1 && first.is_empty())
+            || (line == 1 && first.to_lowercase().starts_with("id"))
+        {
+            continue;
+        }
+        let error = |message: String| ImportError {
+            line: Some(line),
+            message,
+        };
+        let fields: Vec<&str> = fields.iter().map(|f| f.trim()).collect();
+        let [_, time, money_type, amount, currency, category, account, tags, note, status] =
+            fields.as_slice()
+        else {
+            return Err(error(trf(
+                "import.field_count",
+                &[&10, &CSV_HEADER, &fields.len()],
+            )));
+        };
+        let time = NaiveDate::parse_from_str(time, DB_DATE_FORMAT)
+            .map_err(|_| error(trf("import.invalid_date", &[time])))?;
+        let money_type = match money_type.to_uppercase().as_str() {
+            "INCOME" => MoneyType::INCOME,
+            "EXPENSE" => MoneyType::EXPENSE,
+            _ => return Err(error(trf("import.invalid_type", &[money_type]))),
+        };
+        let amount: f32 = amount
+            .parse()
+            .map_err(|_| error(trf("record.invalid_amount", &[amount])))?;
+        let expense = match category.to_uppercase().as_str() {
+            "" | "NONE" => None,
+            code => Some(code.parse::<ExpenseType>().map_err(error)?),
+        };
+        let mut record = Record::new(0, money_type, amount, expense, time)
+            .with_tags(
+                tags.split(CSV_TAG_SEPARATOR)
+                    .map(|t| t.trim())
+                    .filter(|t| !t.is_empty()),
+            );
+        if !currency.is_empty() {
+            record = record.with_currency(currency.to_uppercase());
+        }
+        if !account.is_empty() {
+            record = record.with_account(*account);
+        }
+        if !note.is_empty() {
+            record = record.with_note(*note);
+        }
+        if !status.is_empty() {
+            record = record.with_status(status.parse::<RecordStatus>().map_err(error)?);
+        }
+        check_record(&record).map_err(error)?;
+        ret.push(record);
+    }
+    Ok(ret)
+}
+
+/// Skontroluje sumu, menu a štítky záznamu zadaného zvonka (import, API,
+/// príkazový riadok).
+///
+/// # Errors
+/// Vráti popis chyby, ak suma nie je nezáporné číslo, mena nie je
+/// trojpísmenový kód veľkými písmenami alebo je štítok prázdny či obsahuje
+/// oddeľovač štítkov v databáze ([`TAG_SEPARATOR`]).
+pub fn check_record(record: &Record) -> Result<(), String> {
+    if !record.amount.is_finite() || record.amount < 0.0 {
+        return Err(trf("record.invalid_amount", &[&record.amount]));
+    }
+    let code = &record.currency;
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(trf("record.invalid_currency", &[code]));
+    }
+    if let Some(tag) = record
+        .tags
+        .iter()
+        .find(|t| t.trim().is_empty() || t.contains(TAG_SEPARATOR))
+    {
+        return Err(trf("record.invalid_tag", &[tag]));
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! Kôš odstránených záznamov.
+//!
+//! Odstránený záznam sa nemaže hneď, iba sa mu nastaví dátum odstránenia
+//! ([`crate::record::Record::deleted_on`]). Záznamy v koši sa nenačítajú
+//! do [`crate::record::RecordManager`] ani sa nezapočítajú do žiadnych
+//! súčtov. Z koša sa dajú obnoviť alebo natrvalo vymazať a po uplynutí
+//! lehoty ([`retention_days`]) sa vymažú automaticky.
+
+use chrono::{Days, NaiveDate};
+
+use crate::config;
+use crate::record::Record;
+
+/// Koľko dní ostáva záznam v koši, ak lehota nie je nastavená.
+pub const DEFAULT_RETENTION_DAYS: u64 = 30;
+
+/// Vráti, koľko dní ostáva záznam v koši pred automatickým vymazaním.
+///
+/// Berie sa z nastavení (`trash.retention_days` alebo premenná prostredia
+/// `TRASH_RETENTION_DAYS`), inak je to [`DEFAULT_RETENTION_DAYS`].
+pub fn retention_days() -> u64 {
+    config::current().trash.retention_days
+}
+
+/// Vráti posledný deň, keď je záznam ešte v koši, alebo `None`, ak v koši
+/// nie je alebo je lehota taká dlhá, že nikdy nevyprší.
+///
+/// # Arguments
+/// * `record` – záznam
+/// * `retention` – lehota v dňoch
+pub fn expires_on(record: &Record, retention: u64) -> Option<NaiveDate> {
+    record
+        .deleted_on
+        .and_then(|d| d.checked_add_days(Days::new(retention)))
+}
+
+/// Vráti dátum, pred ktorým odstránené záznamy už prekročili lehotu.
+///
+/// Záznam odstránený pred týmto dňom sa má natrvalo vymazať. `None`
+/// znamená, že lehota siaha pred najmenší možný dátum a nevypršala
+/// žiadnemu záznamu.
+pub fn purge_before(today: NaiveDate, retention: u64) -> Option<NaiveDate> {
+    today.checked_sub_days(Days::new(retention))
+}
@@ -0,0 +1,138 @@
+//! Testy zhody súčtov v SQL ([`db::balance`], [`db::category_totals`],
+//! [`db::aggregate`]) so súčtami [`RecordManager`] nad prepočítanými záznamami.
+mod common;
+
+use std::sync::OnceLock;
+
+use financial_lib::aggregate::{Bucket, GroupBy, Period};
+use financial_lib::config::Config;
+use financial_lib::currency::{Converter, ExchangeRate};
+use financial_lib::db;
+use financial_lib::query::RecordFilter;
+use financial_lib::record::{ExpenseType, MoneyType, Record, RecordManager};
+use financial_lib::report::monthly_report;
+
+use common::date;
+
+/// Vloží testovacie záznamy raz pre celý program a vráti ich.
+fn records() -> &'static RecordManager {
+    static RECORDS: OnceLock<RecordManager> = OnceLock::new();
+    RECORDS.get_or_init(|| {
+        common::database(Config::default());
+        let expense = |id, amount, category, month, day| {
+            Record::new(id, MoneyType::EXPENSE, amount, Some(category), date(month, day))
+        };
+        let records = vec![
+            Record::new(1, MoneyType::INCOME, 2000.0, None, date(1, 1)).with_account("bank"),
+            expense(2, 12.5, ExpenseType::RESTAURANT, 1, 3).with_tags(["work"]),
+            expense(3, 40.0, ExpenseType::SHOPPING, 1, 3).with_tags(["work", "home"]),
+            expense(4, 8.0, ExpenseType::FUN, 1, 20).with_account("card"),
+            expense(5, 30.0, ExpenseType::RESTAURANT, 2, 2).with_currency("USD"),
+            expense(6, 20.0, ExpenseType::RESTAURANT, 2, 14).with_currency("USD"),
+            Record::new(7, MoneyType::INCOME, 100.0, None, date(2, 14)).with_currency("USD"),
+            expense(8, 4.5, ExpenseType::HOME, 3, 31).with_account("card"),
+            Record::new(9, MoneyType::EXPENSE, 3.0, None, date(4, 1)),
+            expense(10, 250.0, ExpenseType::INVESTMENT, 1, 20).with_account("card"),
+            expense(11, 100.0, ExpenseType::INVESTMENT, 2, 14).with_currency("USD"),
+        ];
+        records.iter().for_each(db::insert_record);
+        records.into_iter().collect()
+    })
+}
+
+/// Kurzy USD, ktoré sa menia v polovici februára.
+fn converter() -> Converter {
+    let rate = |day, rate| ExchangeRate {
+        from: "USD".to_string(),
+        to: "EUR".to_string(),
+        time: date(2, day),
+        rate,
+    };
+    Converter::new("EUR", &[rate(1, 0.5), rate(10, 0.75)])
+}
+
+/// Záznamy vyhovujúce filtru prepočítané do základnej meny.
+fn converted(filter: &RecordFilter) -> RecordManager {
+    let selected: RecordManager = records().query(filter).into_iter().collect();
+    converter().convert_all(&selected).unwrap()
+}
+
+fn assert_close(sql: f32, memory: f32) {
+    assert!((sql - memory).abs() < 1e-3, "sql {} != memory {}", sql, memory);
+}
+
+fn assert_buckets(sql: &[Bucket], memory: &[Bucket]) {
+    assert_eq!(sql.len(), memory.len(), "{:?} != {:?}", sql, memory);
+    for (s, m) in sql.iter().zip(memory) {
+        assert_eq!((s.start, &s.key, s.count), (m.start, &m.key, m.count));
+        assert_close(s.income, m.income);
+        assert_close(s.expense, m.expense);
+        assert_close(s.invested, m.invested);
+        assert_close(s.net, m.net);
+    }
+}
+
+#[test]
+fn balance_matches_records() {
+    for filter in [
+        RecordFilter::new(),
+        RecordFilter::new().account("card"),
+        RecordFilter::new().between(date(2, 1), date(2, 28)),
+    ] {
+        records();
+        let sql = db::balance(&filter, &converter()).unwrap();
+        assert_close(sql, converted(&filter).get_balance());
+    }
+}
+
+#[test]
+fn category_totals_match_records() {
+    for filter in [RecordFilter::new(), RecordFilter::new().tag("work")] {
+        records();
+        let sql = db::category_totals(&filter, &converter()).unwrap();
+        let memory = converted(&filter).categories_to_hash();
+        assert_eq!(sql.keys().collect::<Vec<_>>(), memory.keys().collect::<Vec<_>>());
+        for (category, total) in &sql {
+            assert_close(*total, memory[category]);
+        }
+    }
+}
+
+#[test]
+fn aggregate_matches_records() {
+    let filter = RecordFilter::new();
+    records();
+    for period in [Period::Day, Period::Week, Period::Month, Period::Quarter, Period::Year] {
+        for group in [None, Some(GroupBy::Category), Some(GroupBy::Account), Some(GroupBy::Tag)] {
+            let sql = db::aggregate(&filter, period, group, &converter()).unwrap();
+            assert_buckets(&sql, &converted(&filter).aggregate(period, group));
+        }
+    }
+}
+
+#[test]
+fn missing_rate_is_reported() {
+    records();
+    let converter = Converter::new("EUR", &[]);
+    let error = db::balance(&RecordFilter::new(), &converter).unwrap_err();
+    assert_eq!(error.from, "USD");
+    assert!(db::aggregate(&RecordFilter::new(), Period::Month, None, &converter).is_err());
+}
+
+#[test]
+fn investment_purchases_are_not_spending() {
+    records();
+    let january = RecordFilter::new().between(date(1, 1), date(1, 31));
+    let sql = db::aggregate(&january, Period::Month, None, &converter()).unwrap();
+
+    assert_eq!(sql.len(), 1);
+    assert_close(sql[0].expense, 60.5);
+    assert_close(sql[0].invested, 250.0);
+    assert_close(sql[0].net, 2000.0 - 60.5 - 250.0);
+    assert_eq!(sql[0].count, 5);
+    assert_eq!(sql, converted(&january).aggregate(Period::Month, None));
+
+    let report = monthly_report(&sql, date(1, 1), date(1, 31));
+    assert_close(report[0].expense, 60.5);
+    assert_close(report[0].net, 2000.0 - 60.5);
+}
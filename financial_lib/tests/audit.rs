@@ -0,0 +1,78 @@
+//! Testy audit logu – história záznamu a ochrana tabuľky pred zmenami.
+mod common;
+
+use diesel::RunQueryDsl;
+use financial_lib::audit::{FieldChange, Operation, actor};
+use financial_lib::config::{Config, Defaults};
+use financial_lib::db;
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+
+use common::date;
+
+/// Nastaví dočasnú databázu s iným formátom dátumu, než je predvolený.
+fn database() {
+    common::database(Config {
+        defaults: Defaults {
+            date_format: "%Y/%m/%d".to_string(),
+            ..Defaults::default()
+        },
+        ..Config::default()
+    });
+}
+
+fn record(id: i32, amount: f32, day: u32) -> Record {
+    let day = date(7, day);
+    Record::new(id, MoneyType::EXPENSE, amount, Some(ExpenseType::FUN), day)
+}
+
+fn change(field: &'static str, before: &str, after: &str) -> FieldChange {
+    FieldChange {
+        field,
+        before: before.to_string(),
+        after: after.to_string(),
+    }
+}
+
+#[test]
+fn history_has_old_and_new_values_with_actor() {
+    database();
+    db::insert_record(&record(1, 10.0, 1));
+    assert!(db::update_record(&record(1, 12.5, 2)));
+
+    let history = db::record_history(1);
+    assert_eq!(history.len(), 2);
+    assert!(history.iter().all(|e| e.record_id == 1 && e.actor == actor()));
+
+    assert_eq!(history[0].operation, Operation::Insert);
+    assert_eq!(history[0].before, None);
+    assert_eq!(history[0].after, Some(record(1, 10.0, 1)));
+
+    let update = &history[1];
+    assert_eq!(update.operation, Operation::Update);
+    assert_eq!(update.before, Some(record(1, 10.0, 1)));
+    assert_eq!(update.after, Some(record(1, 12.5, 2)));
+    assert_eq!(
+        update.changed_fields(),
+        vec![
+            change("amount", "10.00", "12.50"),
+            change("date", "2026/07/01", "2026/07/02"),
+        ]
+    );
+}
+
+#[test]
+fn audit_log_is_append_only() {
+    database();
+    db::insert_record(&record(2, 5.0, 3));
+    let before = db::record_history(2);
+    assert_eq!(before.len(), 1);
+
+    let conn = &mut db::establish_connection();
+    for statement in [
+        "UPDATE audit_log SET actor = 'someone else' WHERE record_id = 2",
+        "DELETE FROM audit_log WHERE record_id = 2",
+    ] {
+        assert!(diesel::sql_query(statement).execute(conn).is_err(), "{}", statement);
+    }
+    assert_eq!(db::record_history(2), before);
+}
@@ -0,0 +1,98 @@
+//! Testy, že zlyhaná akcia histórie nezmení databázu ani audit log.
+mod common;
+
+use std::sync::OnceLock;
+
+use diesel::RunQueryDsl;
+use financial_lib::config::Config;
+use financial_lib::db;
+use financial_lib::history::{Action, Change, HistoryError};
+use financial_lib::reconcile::RecordStatus;
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+
+use common::date;
+
+/// Suma, ktorej zápis databáza odmietne.
+const REJECTED_AMOUNT: f32 = 13.0;
+
+fn record(id: i32, amount: f32) -> Record {
+    let day = date(5, 1);
+    Record::new(id, MoneyType::EXPENSE, amount, Some(ExpenseType::FUN), day)
+}
+
+/// Vloží záznamy 1 a 2 (odsúhlasený) a trigger, ktorý odmietne zápis
+/// záznamu s [`REJECTED_AMOUNT`].
+fn setup() {
+    static SETUP: OnceLock<()> = OnceLock::new();
+    SETUP.get_or_init(|| {
+        common::database(Config::default());
+        db::insert_record(&record(1, 10.0));
+        db::insert_record(&record(2, 20.0).with_status(RecordStatus::Reconciled));
+        diesel::sql_query(format!(
+            "CREATE TRIGGER reject_amount BEFORE INSERT ON records \
+             WHEN NEW.amount = {} BEGIN SELECT RAISE(ABORT, 'rejected'); END",
+            REJECTED_AMOUNT
+        ))
+        .execute(&mut db::establish_connection())
+        .unwrap();
+    });
+}
+
+/// Skontroluje, že v databáze sú iba pôvodné záznamy a ich vloženie v audit logu.
+fn assert_untouched() {
+    let reconciled = record(2, 20.0).with_status(RecordStatus::Reconciled);
+    assert_eq!(db::load_records().get_all(), vec![record(1, 10.0), reconciled]);
+    assert!(db::load_trash().is_empty());
+    assert_eq!(db::load_audit_log().len(), 2);
+    assert!(db::load_history().is_empty());
+}
+
+#[test]
+fn database_failure_rolls_back_action() {
+    setup();
+    let action = Action::new(
+        "add",
+        vec![
+            Change::update(record(1, 10.0), record(1, 20.0)),
+            Change::insert(record(7, REJECTED_AMOUNT)),
+        ],
+    );
+
+    let error = db::commit(&action).unwrap_err();
+    assert!(matches!(error, HistoryError::Database { .. }));
+    assert_untouched();
+}
+
+#[test]
+fn conflict_rolls_back_action() {
+    setup();
+    let mut trashed = record(1, 10.0);
+    trashed.deleted_on = Some(date(5, 2));
+    let action = Action::new(
+        "bulk",
+        vec![
+            Change::insert(record(8, 5.0)),
+            Change::update(record(1, 10.0), trashed),
+            Change::delete(record(9, 1.0)),
+        ],
+    );
+
+    assert_eq!(db::commit(&action).unwrap_err(), HistoryError::Conflict { id: 9 });
+    assert_untouched();
+}
+
+#[test]
+fn locked_record_rolls_back_action() {
+    setup();
+    let reconciled = record(2, 20.0).with_status(RecordStatus::Reconciled);
+    let action = Action::new(
+        "bulk",
+        vec![
+            Change::insert(record(10, 5.0)),
+            Change::update(reconciled.clone(), record(2, 25.0).with_status(RecordStatus::Reconciled)),
+        ],
+    );
+
+    assert_eq!(db::commit(&action).unwrap_err(), HistoryError::Locked { id: 2 });
+    assert_untouched();
+}
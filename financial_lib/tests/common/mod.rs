@@ -0,0 +1,36 @@
+//! Spoločná príprava integračných testov knižnice.
+//!
+//! Každý testovací program používa iba časť pomocných funkcií.
+#![allow(dead_code)]
+
+use std::sync::OnceLock;
+
+use chrono::NaiveDate;
+use financial_lib::config::{self, Config, DatabaseConfig};
+use tempfile::TempDir;
+
+/// Nastaví prázdnu dočasnú databázu pre celý testovací program.
+///
+/// Testy v jednom programe bežia súbežne a zdieľajú databázu, preto
+/// každý pracuje iba so svojimi záznamami alebo ich iba číta. Nastavenia
+/// `config` sa použijú pri prvom volaní, cesta k databáze sa v nich
+/// nahradí dočasnou; program preto musí volať funkciu vždy s rovnakými
+/// nastaveniami.
+pub fn database(config: Config) {
+    static DIR: OnceLock<TempDir> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let dir = TempDir::new().expect("temporary directory");
+        config::install(Config {
+            database: DatabaseConfig {
+                path: Some(dir.path().join("test.db")),
+            },
+            ..config
+        });
+        dir
+    });
+}
+
+/// Vráti dátum v roku 2026, v ktorom sa odohrávajú všetky testy.
+pub fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+}
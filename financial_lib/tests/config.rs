@@ -0,0 +1,160 @@
+//! Testy nastavení: poradie súboru, prostredia a prepínačov, relatívne
+//! cesty a kontrola formátu dátumu.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use financial_lib::config::{Config, Flags, is_valid_date_format};
+use financial_lib::i18n::Language;
+use tempfile::TempDir;
+
+/// Premenné prostredia, ktoré prepisujú nastavenia.
+const ENV: [&str; 8] = [
+    "DATABASE_URL",
+    "FINANCE_ACCOUNT",
+    "BASE_CURRENCY",
+    "FINANCE_DATE_FORMAT",
+    "FINANCE_LANGUAGE",
+    "TRASH_RETENTION_DAYS",
+    "FINANCE_THEME",
+    "FINANCE_TICK_RATE",
+];
+
+/// Zapíše súbor nastavení `config.toml` do adresára `dir`.
+fn write_config(dir: &Path, text: &str) -> PathBuf {
+    let path = dir.join("config.toml");
+    fs::write(&path, text).unwrap();
+    path
+}
+
+#[test]
+fn environment_overrides_file_and_flags_override_both() {
+    let dir = TempDir::new().expect("temporary directory");
+    let path = write_config(
+        dir.path(),
+        "[defaults]\naccount = \"file\"\ncurrency = \"usd\"\nlanguage = \"sk\"\n",
+    );
+    // SAFETY: jediný test tohto programu, ktorý číta alebo mení prostredie
+    unsafe {
+        ENV.iter().for_each(|key| env::remove_var(key));
+    }
+
+    let config = Config::load(Some(&path)).unwrap();
+    assert_eq!(config.defaults.account.as_deref(), Some("file"));
+    assert_eq!(config.defaults.currency, "USD");
+    assert_eq!(config.defaults.language, Language::Sk);
+
+    unsafe {
+        env::set_var("FINANCE_ACCOUNT", "env");
+        env::set_var("BASE_CURRENCY", "czk");
+    }
+    let mut config = Config::load(Some(&path)).unwrap();
+    assert_eq!(config.defaults.account.as_deref(), Some("env"));
+    assert_eq!(config.defaults.currency, "CZK");
+    assert_eq!(config.defaults.language, Language::Sk);
+
+    config
+        .apply_flags(&Flags {
+            database: Some(PathBuf::from("flag.db")),
+            account: Some("flag".to_string()),
+            currency: Some("gbp".to_string()),
+            language: Some("en".to_string()),
+            ..Flags::default()
+        })
+        .unwrap();
+    assert_eq!(config.defaults.account.as_deref(), Some("flag"));
+    assert_eq!(config.defaults.currency, "GBP");
+    assert_eq!(config.defaults.language, Language::En);
+    // cesta z prepínača sa vzťahuje na pracovný adresár, nie na súbor nastavení
+    assert_eq!(config.database.path, Some(PathBuf::from("flag.db")));
+
+    unsafe { env::set_var("FINANCE_LANGUAGE", "xx") };
+    let error = Config::load(Some(&path)).unwrap_err();
+    assert_eq!(error.origin, "FINANCE_LANGUAGE");
+    assert_eq!(error.language, Language::Sk);
+
+    unsafe {
+        ENV.iter().for_each(|key| env::remove_var(key));
+    }
+}
+
+#[test]
+fn invalid_flags_are_rejected() {
+    let mut config = Config::default();
+    let error = config
+        .apply_flags(&Flags {
+            language: Some("de".to_string()),
+            ..Flags::default()
+        })
+        .unwrap_err();
+    assert_eq!(error.origin, "--language");
+
+    for flags in [
+        Flags {
+            currency: Some("EURO".to_string()),
+            ..Flags::default()
+        },
+        Flags {
+            date_format: Some("%d.%m".to_string()),
+            ..Flags::default()
+        },
+    ] {
+        assert!(Config::default().apply_flags(&flags).is_err(), "{:?}", flags);
+    }
+
+    // prázdny účet zruší predvolený účet zo súboru
+    config.defaults.account = Some("card".to_string());
+    config
+        .apply_flags(&Flags {
+            account: Some(" ".to_string()),
+            ..Flags::default()
+        })
+        .unwrap();
+    assert_eq!(config.defaults.account, None);
+}
+
+#[test]
+fn relative_paths_in_file_follow_its_directory() {
+    let dir = TempDir::new().expect("temporary directory");
+    let path = write_config(
+        dir.path(),
+        "[database]\npath = \"data/finance.db\"\n\n[ui]\ntheme = \"themes/mine.toml\"\n",
+    );
+    let config = Config::from_file(&path).unwrap();
+    assert_eq!(config.database.path, Some(dir.path().join("data/finance.db")));
+    assert_eq!(
+        PathBuf::from(&config.ui.theme),
+        dir.path().join("themes/mine.toml")
+    );
+
+    let absolute = dir.path().join("elsewhere.db");
+    let path = write_config(
+        dir.path(),
+        &format!("[database]\npath = {:?}\n\n[ui]\ntheme = \"light\"\n", absolute),
+    );
+    let config = Config::from_file(&path).unwrap();
+    assert_eq!(config.database.path, Some(absolute));
+    assert_eq!(config.ui.theme, "light");
+}
+
+#[test]
+fn file_errors_are_in_the_language_of_the_file() {
+    let dir = TempDir::new().expect("temporary directory");
+    let path = write_config(dir.path(), "[defaults]\nlanguage = \"sk\"\ncurrency = \"E\"\n");
+    let error = Config::from_file(&path).unwrap_err();
+    assert_eq!(error.language, Language::Sk);
+    assert!(error.message.starts_with("neplatná mena"), "{}", error.message);
+
+    let path = write_config(dir.path(), "[defaults]\ncolour = \"red\"\n");
+    assert!(Config::from_file(&path).is_err());
+}
+
+#[test]
+fn date_format_must_contain_day_month_and_year() {
+    for format in ["%d.%m.%Y", "%Y-%m-%d", "%m/%d/%y", "%d %b %Y"] {
+        assert!(is_valid_date_format(format), "{}", format);
+    }
+    for format in ["%d.%m", "%Y", "%Q.%m.%Y", "", "dd.mm.yyyy"] {
+        assert!(!is_valid_date_format(format), "{}", format);
+    }
+}
@@ -0,0 +1,79 @@
+//! Testy rozpoznania pravidelných platieb a predpovede zostatku.
+mod common;
+
+use financial_lib::forecast::{Interval, detect_recurring, forecast};
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+
+use common::date;
+
+fn salary(id: i32, month: u32, day: u32) -> Record {
+    Record::new(id, MoneyType::INCOME, 1000.0, None, date(month, day)).with_note("Salary")
+}
+
+fn rent(id: i32, month: u32) -> Record {
+    Record::new(id, MoneyType::EXPENSE, 500.0, Some(ExpenseType::HOME), date(month, 1))
+        .with_note("Rent")
+}
+
+#[test]
+fn month_end_payments_keep_their_day() {
+    let records = [salary(1, 1, 31), salary(2, 2, 28), salary(3, 3, 31)];
+    let series = detect_recurring(&records, date(3, 31));
+
+    assert_eq!(series.len(), 1);
+    assert_eq!(series[0].interval, Interval::Months(1));
+    assert_eq!(series[0].anchor, date(1, 31));
+    assert_eq!(
+        series[0].upcoming(date(6, 30)),
+        vec![date(4, 30), date(5, 31), date(6, 30)]
+    );
+}
+
+#[test]
+fn series_without_recent_payment_is_dropped() {
+    let records = [rent(1, 1), rent(2, 2), rent(3, 3)];
+
+    assert_eq!(detect_recurring(&records, date(4, 20)).len(), 1);
+    assert_eq!(detect_recurring(&records, date(5, 1)).len(), 1);
+    assert!(detect_recurring(&records, date(5, 2)).is_empty());
+}
+
+#[test]
+fn irregular_amounts_and_short_history_are_not_recurring() {
+    let short = [rent(1, 1), rent(2, 2)];
+    assert!(detect_recurring(&short, date(2, 10)).is_empty());
+
+    let mut uneven = [rent(1, 1), rent(2, 2), rent(3, 3)];
+    uneven[1].amount = 900.0;
+    assert!(detect_recurring(&uneven, date(3, 10)).is_empty());
+}
+
+#[test]
+fn forecast_projects_recurring_payments_from_anchor() {
+    let records = [salary(1, 1, 31), salary(2, 2, 28), salary(3, 3, 31)];
+    let result = forecast(&records, date(3, 31), 61);
+
+    assert_eq!(result.start_balance, 3000.0);
+    assert!(result.daily_spending.is_empty());
+    let balance_on = |day| {
+        result
+            .points
+            .iter()
+            .find(|p| p.date == day)
+            .map(|p| p.balance)
+            .unwrap()
+    };
+    assert_eq!(balance_on(date(4, 29)), 3000.0);
+    assert_eq!(balance_on(date(4, 30)), 4000.0);
+    assert_eq!(balance_on(date(5, 30)), 4000.0);
+    assert_eq!(result.end_balance(), 5000.0);
+}
+
+#[test]
+fn stale_series_is_not_projected() {
+    let records = [rent(1, 1), rent(2, 2), rent(3, 3)];
+    let result = forecast(&records, date(6, 1), 30);
+
+    assert!(result.recurring.is_empty());
+    assert_eq!(result.start_balance, -1500.0);
+}
@@ -0,0 +1,111 @@
+//! Testy vrátenia a zopakovania akcií uložených v databáze.
+mod common;
+
+use std::sync::{Mutex, MutexGuard};
+
+use financial_lib::config::Config;
+use financial_lib::db;
+use financial_lib::history::{Action, Change, HISTORY_LIMIT};
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+
+use common::date;
+
+fn record(id: i32, amount: f32) -> Record {
+    let day = date(6, 1);
+    Record::new(id, MoneyType::EXPENSE, amount, Some(ExpenseType::SHOPPING), day)
+}
+
+/// Nastaví databázu a zamkne históriu pre jeden test.
+///
+/// História je v databáze spoločná, preto testy s ňou nesmú bežať súbežne.
+fn history() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    common::database(Config::default());
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn stored(id: i32) -> Option<Record> {
+    db::load_records().get_record_id(id).cloned()
+}
+
+#[test]
+fn undone_action_can_be_redone() {
+    let _lock = history();
+    let action = Action::new(
+        "add 2",
+        vec![Change::insert(record(100, 5.0)), Change::insert(record(101, 7.0))],
+    );
+    db::commit(&action).unwrap();
+
+    let undone = db::undo().unwrap().expect("action to undo");
+    assert_eq!(undone.changes, action.changes);
+    assert_eq!(stored(100), None);
+    assert_eq!(stored(101), None);
+
+    let redone = db::redo().unwrap().expect("action to redo");
+    assert_eq!(redone.id, undone.id);
+    assert_eq!(stored(100), Some(record(100, 5.0)));
+    assert_eq!(stored(101), Some(record(101, 7.0)));
+    assert_eq!(db::redo().unwrap(), None);
+}
+
+#[test]
+fn history_survives_new_connections() {
+    let _lock = history();
+    db::commit(&Action::new("add 200", vec![Change::insert(record(200, 1.0))])).unwrap();
+    db::commit(&Action::new(
+        "edit 200",
+        vec![Change::update(record(200, 1.0), record(200, 2.0))],
+    ))
+    .unwrap();
+
+    // každé volanie otvára vlastné spojenie, akcie sa čítajú z databázy
+    let history = db::load_history();
+    let last = history.last().unwrap();
+    assert_eq!(last.description, "edit 200");
+    assert!(!last.undone);
+
+    assert_eq!(db::undo().unwrap().unwrap().description, "edit 200");
+    assert_eq!(stored(200), Some(record(200, 1.0)));
+    assert!(db::load_history().last().unwrap().undone);
+    assert_eq!(db::undo().unwrap().unwrap().description, "add 200");
+    assert_eq!(stored(200), None);
+}
+
+#[test]
+fn new_action_drops_undone_actions() {
+    let _lock = history();
+    db::commit(&Action::new("add 300", vec![Change::insert(record(300, 3.0))])).unwrap();
+    db::undo().unwrap().unwrap();
+    db::commit(&Action::new("add 301", vec![Change::insert(record(301, 4.0))])).unwrap();
+
+    assert_eq!(db::redo().unwrap(), None);
+    let history = db::load_history();
+    assert!(history.iter().all(|a| a.description != "add 300" && !a.undone));
+    assert_eq!(stored(300), None);
+    assert_eq!(db::undo().unwrap().unwrap().description, "add 301");
+}
+
+#[test]
+fn history_keeps_only_latest_actions() {
+    let _lock = history();
+    let first = 1000;
+    let count = HISTORY_LIMIT as i32 + 5;
+    for id in first..first + count {
+        let action = Action::new(format!("add {}", id), vec![Change::insert(record(id, 1.0))]);
+        db::commit(&action).unwrap();
+    }
+
+    let history = db::load_history();
+    assert_eq!(history.len(), HISTORY_LIMIT);
+    assert_eq!(history[0].description, format!("add {}", first + 5));
+    assert_eq!(history.last().unwrap().description, format!("add {}", first + count - 1));
+
+    for _ in 0..HISTORY_LIMIT {
+        db::undo().unwrap().unwrap();
+    }
+    assert_eq!(db::undo().unwrap(), None);
+    // záznamy zo zahodených akcií ostanú
+    assert!(stored(first + 4).is_some());
+    assert_eq!(stored(first + 5), None);
+}
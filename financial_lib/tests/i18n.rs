@@ -0,0 +1,35 @@
+//! Testy, že každý text knižnice, ktorý sa používa, má anglický preklad.
+use std::path::Path;
+
+use financial_lib::i18n::{Language, MESSAGES, source_keys};
+use financial_lib::reconcile::RecordStatus;
+use financial_lib::record::ExpenseType;
+
+/// Volania, ktorých prvý argument je kľúč textu.
+const CALLS: [&str; 3] = ["tr(", "trf(", "self.text("];
+
+#[test]
+fn every_used_key_is_in_english_table() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let keys = source_keys(&src, &CALLS);
+    for (path, key) in &keys {
+        assert!(MESSAGES.has(Language::En, key), "{}: missing text '{}'", path.display(), key);
+    }
+    assert!(keys.len() > 50, "only {} keys found", keys.len());
+}
+
+#[test]
+fn category_and_status_names_are_in_every_language() {
+    let categories = ExpenseType::ALL.map(|c| format!("category.{}", c.code()));
+    let statuses = RecordStatus::ALL.map(|s| format!("status.{}", s.code()));
+    for key in categories.iter().chain(&statuses) {
+        for language in Language::ALL {
+            assert!(MESSAGES.has(language, key), "missing {} text '{}'", language, key);
+        }
+    }
+}
+
+#[test]
+fn every_translation_has_english_text() {
+    assert_eq!(MESSAGES.inconsistencies(), Vec::<String>::new());
+}
@@ -0,0 +1,103 @@
+//! Testy lotov FIFO, realizovaného zisku a kontroly obchodov.
+mod common;
+
+use financial_lib::config::Config;
+use financial_lib::db;
+use financial_lib::investment::{Price, Trade, TradeKind, holdings, parse_prices_csv};
+
+use common::date;
+
+fn trade(kind: TradeKind, month: u32, quantity: f32, price: f32, fee: f32) -> Trade {
+    Trade::new("VWCE", date(month, 10), kind, quantity, price, fee).unwrap()
+}
+
+#[test]
+fn sale_closes_oldest_lots_first() {
+    let trades = [
+        trade(TradeKind::Buy, 1, 10.0, 100.0, 5.0),
+        trade(TradeKind::Buy, 2, 5.0, 120.0, 0.0),
+        trade(TradeKind::Sell, 3, 12.0, 130.0, 2.0),
+    ];
+    let holding = &holdings(&trades, &[], date(3, 31))[0];
+
+    assert_eq!(holding.lots.len(), 1);
+    assert_eq!(holding.lots[0].opened, date(2, 10));
+    assert_eq!(holding.lots[0].quantity, 3.0);
+    assert_eq!(holding.cost_basis(), 360.0);
+    // 12 × 130 − 2 za predaj, náklady 10 × 100,50 + 2 × 120
+    assert_eq!(holding.realised_gain, 1558.0 - 1245.0);
+}
+
+#[test]
+fn partial_sale_keeps_the_rest_of_the_lot() {
+    let trades = [
+        trade(TradeKind::Buy, 1, 10.0, 100.0, 0.0),
+        trade(TradeKind::Sell, 2, 4.0, 90.0, 0.0),
+    ];
+    let prices = [Price {
+        symbol: "VWCE".to_string(),
+        time: date(2, 20),
+        price: 110.0,
+    }];
+    let holding = &holdings(&trades, &prices, date(3, 1))[0];
+
+    assert_eq!(holding.quantity(), 6.0);
+    assert_eq!(holding.realised_gain, -40.0);
+    assert_eq!(holding.market_value(), Some(660.0));
+    assert_eq!(holding.unrealised_gain(), Some(60.0));
+
+    let before_sale = &holdings(&trades, &prices, date(1, 31))[0];
+    assert_eq!(before_sale.quantity(), 10.0);
+    assert_eq!(before_sale.price, None);
+}
+
+#[test]
+fn non_positive_quantity_is_rejected() {
+    for quantity in [0.0, -1.0, f32::NAN] {
+        assert!(Trade::new("VWCE", date(1, 1), TradeKind::Buy, quantity, 1.0, 0.0).is_err());
+    }
+    assert!(Trade::new("VWCE", date(1, 1), TradeKind::Buy, 1.0, -1.0, 0.0).is_err());
+    assert!(Trade::new(" ", date(1, 1), TradeKind::Buy, 1.0, 1.0, 0.0).is_err());
+
+    // obchod s nulovým počtom kusov z databázy nepokazí výpočet nákladov
+    let zero = Trade {
+        quantity: 0.0,
+        ..trade(TradeKind::Buy, 1, 1.0, 100.0, 1.0)
+    };
+    let ok = trade(TradeKind::Buy, 1, 2.0, 50.0, 0.0);
+    let holding = &holdings(&[zero, ok], &[], date(2, 1))[0];
+    assert_eq!(holding.lots.len(), 1);
+    assert_eq!(holding.cost_basis(), 100.0);
+}
+
+#[test]
+fn invalid_trade_is_not_stored() {
+    common::database(Config::default());
+    let invalid = Trade {
+        symbol: "BAD".to_string(),
+        quantity: 0.0,
+        ..trade(TradeKind::Buy, 1, 1.0, 100.0, 0.0)
+    };
+    assert!(db::insert_trade(&invalid).is_err());
+    assert!(db::load_trades().iter().all(|t| t.symbol != "BAD"));
+
+    let valid = Trade {
+        symbol: "GOOD".to_string(),
+        ..trade(TradeKind::Buy, 1, 1.0, 100.0, 0.0)
+    };
+    let id = db::insert_trade(&valid).unwrap();
+    let stored = db::load_trades().into_iter().find(|t| t.id == Some(id)).unwrap();
+    assert_eq!(stored.symbol, "GOOD");
+}
+
+#[test]
+fn prices_csv_reports_bad_lines() {
+    let prices = parse_prices_csv("symbol,date,price\nVWCE,2026-03-01,101.5\n\nIWDA, 2026-03-01 ,80\n")
+        .unwrap();
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[1].symbol, "IWDA");
+
+    let error = parse_prices_csv("VWCE,2026-03-01,1\nVWCE,2026-03-02,-5\n").unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(parse_prices_csv("VWCE,2026-03-01\n").unwrap_err().line, 1);
+}
@@ -0,0 +1,40 @@
+//! Testy chronologického prehľadu s priebežným zostatkom.
+mod common;
+
+use financial_lib::ledger::ledger;
+use financial_lib::record::{ExpenseType, MoneyType, Record, RecordManager};
+
+use common::date;
+
+fn manager() -> RecordManager {
+    [
+        Record::new(4, MoneyType::EXPENSE, 30.0, Some(ExpenseType::HOME), date(3, 5)),
+        Record::new(1, MoneyType::INCOME, 100.0, None, date(3, 1)),
+        Record::new(3, MoneyType::EXPENSE, 20.0, Some(ExpenseType::FUN), date(3, 5)),
+        Record::new(2, MoneyType::EXPENSE, 10.0, Some(ExpenseType::CAR), date(3, 9)),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn entries_are_ordered_by_date_then_id() {
+    let ids: Vec<i32> = manager().ledger().iter().map(|e| e.record.id).collect();
+    assert_eq!(ids, vec![1, 3, 4, 2]);
+}
+
+#[test]
+fn balance_runs_in_ledger_order() {
+    let balances: Vec<f32> = manager().ledger().iter().map(|e| e.balance).collect();
+    assert_eq!(balances, vec![100.0, 80.0, 50.0, 40.0]);
+    assert_eq!(balances.last().copied(), Some(manager().get_balance()));
+}
+
+#[test]
+fn input_order_does_not_matter() {
+    let manager = manager();
+    let mut reversed = manager.get_all();
+    reversed.reverse();
+    assert_eq!(ledger(&reversed), manager.ledger());
+    assert!(ledger(&[]).is_empty());
+}
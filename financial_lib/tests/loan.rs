@@ -0,0 +1,128 @@
+//! Testy splátkového kalendára a rozdelenia splátok úveru.
+mod common;
+
+use chrono::NaiveDate;
+use financial_lib::currency::{Converter, ExchangeRate};
+use financial_lib::loan::{Loan, PaymentFrequency};
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+
+use common::date;
+
+fn loan(principal: f32, annual_rate: f32, term: u32) -> Loan {
+    Loan {
+        id: None,
+        name: "Car".to_string(),
+        principal,
+        annual_rate,
+        term,
+        frequency: PaymentFrequency::Monthly,
+        start: date(1, 31),
+        tag: "car-loan".to_string(),
+    }
+}
+
+fn payment(id: i32, amount: f32, time: NaiveDate) -> Record {
+    Record::new(id, MoneyType::EXPENSE, amount, Some(ExpenseType::CAR), time).with_tags(["car-loan"])
+}
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!((actual - expected).abs() < 0.01, "{} != {}", actual, expected);
+}
+
+#[test]
+fn schedule_without_interest_splits_principal_evenly() {
+    let schedule = loan(1200.0, 0.0, 12).schedule();
+
+    assert_eq!(schedule.len(), 12);
+    assert!(schedule.iter().all(|i| i.payment == 100.0 && i.interest == 0.0));
+    assert_eq!(schedule[0].balance, 1100.0);
+    assert_eq!(schedule[11].balance, 0.0);
+    let dates: Vec<NaiveDate> = schedule.iter().take(3).map(|i| i.date).collect();
+    assert_eq!(dates, vec![date(1, 31), date(2, 28), date(3, 31)]);
+}
+
+#[test]
+fn annuity_schedule_pays_off_the_principal() {
+    let loan = loan(10000.0, 6.0, 12);
+    let schedule = loan.schedule();
+
+    assert_close(loan.payment(), 860.66);
+    assert_close(schedule[0].interest, 50.0);
+    assert_close(schedule[0].principal, 810.66);
+    assert!(schedule[11].interest < schedule[0].interest);
+    assert_close(schedule.iter().map(|i| i.principal).sum(), 10000.0);
+    assert_close(schedule[11].balance, 0.0);
+    for i in &schedule {
+        assert_close(i.principal + i.interest, i.payment);
+    }
+}
+
+#[test]
+fn payments_are_split_into_interest_and_principal() {
+    let loan = loan(10000.0, 6.0, 12);
+    let records = [
+        payment(1, 860.66, date(1, 31)),
+        payment(2, 1000.0, date(2, 28)),
+        Record::new(3, MoneyType::EXPENSE, 500.0, None, date(2, 10)),
+        Record::new(4, MoneyType::INCOME, 500.0, None, date(2, 11)).with_tags(["car-loan"]),
+    ];
+    let status = loan.status(&records);
+
+    let ids: Vec<Option<i32>> = status.payments.iter().map(|p| p.record_id).collect();
+    assert_eq!(ids, vec![Some(1), Some(2)]);
+    assert_close(status.payments[0].interest, 50.0);
+    assert_close(status.payments[0].principal, 810.66);
+    assert_close(status.payments[1].interest, 9189.34 * 0.005);
+    assert_close(status.remaining, 9189.34 - (1000.0 - 9189.34 * 0.005));
+    assert_close(status.interest_paid, 50.0 + 9189.34 * 0.005);
+}
+
+#[test]
+fn payoff_date_follows_regular_payments() {
+    let loan = loan(1200.0, 0.0, 12);
+    assert_eq!(loan.status(&[]).payoff_date, Some(date(12, 31)));
+    let first = [payment(1, 100.0, date(1, 31))];
+    assert_eq!(loan.status(&first).payoff_date, Some(date(12, 31)));
+
+    let paid = [payment(1, 600.0, date(1, 31)), payment(2, 600.0, date(2, 28))];
+    let status = loan.status(&paid);
+    assert_eq!(status.remaining, 0.0);
+    assert_eq!(status.payoff_date, Some(date(2, 28)));
+}
+
+#[test]
+fn underpayment_increases_the_balance() {
+    let loan = loan(10000.0, 12.0, 12);
+    let status = loan.status(&[payment(1, 50.0, date(1, 31))]);
+
+    assert_close(status.payments[0].principal, -50.0);
+    assert_close(status.remaining, 10050.0);
+    assert!(status.payoff_date.expect("regular payments cover interest") > date(12, 31));
+}
+
+#[test]
+fn rounded_payments_do_not_add_an_extra_payment() {
+    let loan = loan(10000.0, 6.0, 12);
+    let status = loan.status(&[payment(1, 860.66, date(1, 31))]);
+
+    assert_eq!(status.payoff_date, Some(date(12, 31)));
+}
+
+#[test]
+fn payments_in_other_currencies_are_converted_to_base() {
+    let loan = loan(1200.0, 0.0, 12);
+    let records = [
+        payment(1, 100.0, date(1, 31)),
+        payment(2, 200.0, date(2, 28)).with_currency("USD"),
+    ];
+    let rate = ExchangeRate {
+        from: "USD".to_string(),
+        to: "EUR".to_string(),
+        time: date(1, 1),
+        rate: 0.5,
+    };
+
+    let status = loan.converted_status(&records, &Converter::new("EUR", &[rate])).unwrap();
+    assert_close(status.remaining, 1000.0);
+    assert!(loan.converted_status(&records, &Converter::new("EUR", &[])).is_err());
+}
@@ -0,0 +1,106 @@
+//! Testy čistej hodnoty – posledné platné ocenenie a odpočítanie záväzkov.
+mod common;
+
+use financial_lib::config::Config;
+use financial_lib::db;
+use financial_lib::networth::{
+    Asset, AssetKind, Valuation, net_worth_on, net_worth_over_time, value_on,
+};
+
+use common::date;
+
+fn asset(id: i32, name: &str, kind: AssetKind) -> Asset {
+    Asset {
+        id: Some(id),
+        ..Asset::new(name, kind)
+    }
+}
+
+fn valuation(asset_id: i32, month: u32, day: u32, value: f32) -> Valuation {
+    Valuation {
+        asset_id,
+        time: date(month, day),
+        value,
+    }
+}
+
+fn assets() -> Vec<Asset> {
+    vec![
+        asset(1, "flat", AssetKind::Asset),
+        asset(2, "car", AssetKind::Asset),
+        asset(3, "mortgage", AssetKind::Liability),
+    ]
+}
+
+fn valuations() -> Vec<Valuation> {
+    vec![
+        valuation(1, 1, 1, 100000.0),
+        valuation(1, 6, 1, 110000.0),
+        valuation(2, 3, 1, 8000.0),
+        valuation(3, 1, 1, 60000.0),
+        valuation(3, 4, 15, 58000.0),
+    ]
+}
+
+#[test]
+fn latest_valuation_applies_until_the_next_one() {
+    let valuations = valuations();
+    assert_eq!(value_on(&valuations, 1, date(1, 1)).unwrap().value, 100000.0);
+    assert_eq!(value_on(&valuations, 1, date(5, 31)).unwrap().value, 100000.0);
+    assert_eq!(value_on(&valuations, 1, date(6, 1)).unwrap().value, 110000.0);
+    assert_eq!(value_on(&valuations, 1, date(12, 31)).unwrap().value, 110000.0);
+    // pred prvým ocenením hodnota nie je známa
+    assert_eq!(value_on(&valuations, 2, date(2, 28)), None);
+    assert_eq!(value_on(&valuations, 4, date(12, 31)), None);
+}
+
+#[test]
+fn liabilities_are_subtracted_from_assets() {
+    let point = net_worth_on(&assets(), &valuations(), date(5, 1));
+    assert_eq!(point.date, date(5, 1));
+    assert_eq!(point.assets, 108000.0);
+    assert_eq!(point.liabilities, 58000.0);
+    assert_eq!(point.net, 50000.0);
+
+    // majetok bez ocenenia sa nezapočíta
+    let point = net_worth_on(&assets(), &valuations(), date(2, 1));
+    assert_eq!(point.assets, 100000.0);
+    assert_eq!(point.liabilities, 60000.0);
+    assert_eq!(point.net, 40000.0);
+}
+
+#[test]
+fn unsaved_assets_and_foreign_valuations_are_ignored() {
+    let assets = [
+        Asset::new("flat", AssetKind::Asset),
+        asset(3, "mortgage", AssetKind::Liability),
+    ];
+    let point = net_worth_on(&assets, &valuations(), date(12, 31));
+    assert_eq!(point.assets, 0.0);
+    assert_eq!(point.liabilities, 58000.0);
+    assert_eq!(point.net, -58000.0);
+}
+
+#[test]
+fn net_worth_changes_only_on_valuation_days() {
+    let points = net_worth_over_time(&assets(), &valuations());
+    let dates: Vec<_> = points.iter().map(|p| p.date).collect();
+    assert_eq!(dates, vec![date(1, 1), date(3, 1), date(4, 15), date(6, 1)]);
+    let net: Vec<f32> = points.iter().map(|p| p.net).collect();
+    assert_eq!(net, vec![40000.0, 48000.0, 50000.0, 60000.0]);
+}
+
+#[test]
+fn deleted_asset_takes_its_valuations_along() {
+    common::database(Config::default());
+    let kept = db::insert_asset(&Asset::new("bike", AssetKind::Asset));
+    let deleted = db::insert_asset(&Asset::new("boat", AssetKind::Asset));
+    db::insert_valuation(&valuation(kept, 1, 1, 500.0));
+    db::insert_valuation(&valuation(deleted, 1, 1, 9000.0));
+
+    db::delete_asset(deleted).unwrap();
+    let ids: Vec<_> = db::load_assets().iter().map(|a| a.id).collect();
+    assert_eq!(ids, vec![Some(kept)]);
+    let valued: Vec<_> = db::load_valuations().iter().map(|v| v.asset_id).collect();
+    assert_eq!(valued, vec![kept]);
+}
@@ -0,0 +1,126 @@
+//! Testy zhody filtra v SQL ([`db::query_records`]) a v pamäti
+//! ([`RecordFilter::apply`]) nad rovnakými záznamami.
+mod common;
+
+use std::sync::OnceLock;
+
+use financial_lib::config::Config;
+use financial_lib::db;
+use chrono::NaiveDate;
+use financial_lib::query::{RecordFilter, SortKey, SortOrder};
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+use financial_lib::search::parse_query;
+
+use common::date;
+
+/// Vloží testovacie záznamy raz pre celý program a vráti ich.
+fn records() -> &'static [Record] {
+    static RECORDS: OnceLock<Vec<Record>> = OnceLock::new();
+    RECORDS.get_or_init(|| {
+        common::database(Config::default());
+        let expense = |id, amount, category, day| {
+            Record::new(id, MoneyType::EXPENSE, amount, Some(category), date(3, day))
+        };
+        let records = vec![
+            Record::new(1, MoneyType::INCOME, 2000.0, None, date(3, 1))
+                .with_account("bank")
+                .with_note("Salary March"),
+            expense(2, 12.5, ExpenseType::RESTAURANT, 3)
+                .with_account("card")
+                .with_note("Café Čierny"),
+            expense(3, 40.0, ExpenseType::SHOPPING, 4)
+                .with_note("50% off sale")
+                .with_tags(["Work", "home"]),
+            expense(4, 8.0, ExpenseType::FUN, 5)
+                .with_note("500 points")
+                .with_tags(["work"]),
+            expense(5, 19.0, ExpenseType::OTHER, 6).with_note("file_name.txt"),
+            expense(6, 21.0, ExpenseType::OTHER, 7)
+                .with_note("fileXname")
+                .with_account("my card"),
+            expense(7, 33.0, ExpenseType::HOME, 8)
+                .with_note("čierny kábel")
+                .with_tags(["a", "b"]),
+            expense(8, 5.0, ExpenseType::CAR, 9).with_note(r"back\slash"),
+            Record::new(9, MoneyType::EXPENSE, 7.0, None, date(3, 10)).with_tags(["ab"]),
+        ];
+        records.iter().for_each(db::insert_record);
+        records
+    })
+}
+
+/// Porovná ID záznamov vrátených z databázy a z pamäte.
+fn assert_same(filter: RecordFilter) -> Vec<i32> {
+    let ids = |records: Vec<Record>| records.iter().map(|r| r.id).collect::<Vec<_>>();
+    let memory = ids(filter.apply(records()));
+    let sql = ids(db::query_records(&filter));
+    assert_eq!(sql, memory, "filter {:?}", filter);
+    memory
+}
+
+#[test]
+fn percent_and_underscore_are_literal() {
+    assert_eq!(assert_same(RecordFilter::new().text("50%")), vec![3]);
+    assert_eq!(assert_same(RecordFilter::new().text("file_name")), vec![5]);
+    assert_eq!(assert_same(RecordFilter::new().text("_")), vec![5]);
+    assert_eq!(assert_same(RecordFilter::new().text(r"\s")), vec![8]);
+    assert_same(RecordFilter::new().exclude_text("%"));
+}
+
+#[test]
+fn case_is_folded_only_in_ascii() {
+    assert_eq!(assert_same(RecordFilter::new().text("SALARY")), vec![1]);
+    assert_eq!(assert_same(RecordFilter::new().text("Čierny")), vec![2]);
+    assert_eq!(assert_same(RecordFilter::new().text("čierny")), vec![7]);
+    assert_same(RecordFilter::new().exclude_text("čierny"));
+}
+
+#[test]
+fn tags_match_exactly_and_one_by_one() {
+    assert_eq!(assert_same(RecordFilter::new().tag("work")), vec![4]);
+    assert_eq!(assert_same(RecordFilter::new().tag("Work")), vec![3]);
+    assert_eq!(assert_same(RecordFilter::new().tag("a")), vec![7]);
+    assert_eq!(assert_same(RecordFilter::new().tag("%")), Vec::<i32>::new());
+    assert_eq!(assert_same(RecordFilter::new().text("a,b")), Vec::<i32>::new());
+    assert_eq!(assert_same(RecordFilter::new().text("ab")), vec![9]);
+    assert_same(RecordFilter::new().exclude_tag("work"));
+}
+
+#[test]
+fn combined_filters_and_sorting_match() {
+    assert_same(
+        RecordFilter::new()
+            .money_type(MoneyType::EXPENSE)
+            .text("card")
+            .sort_by(SortKey::Amount, SortOrder::Descending),
+    );
+    assert_same(
+        RecordFilter::new()
+            .exclude_category(ExpenseType::OTHER)
+            .exclude_account("card")
+            .sort_by(SortKey::Category, SortOrder::Ascending)
+            .limit(4),
+    );
+    assert_same(RecordFilter::new().between(date(3, 4), date(3, 8)).text("e"));
+    assert_eq!(assert_same(RecordFilter::new().note("card")), Vec::<i32>::new());
+    assert_eq!(assert_same(RecordFilter::new().note("_")), vec![5]);
+    assert_same(RecordFilter::new().exclude_note("e"));
+}
+
+#[test]
+fn first_and_last_comparable_years_agree() {
+    let first = NaiveDate::from_ymd_opt(0, 1, 1).unwrap();
+    let last = NaiveDate::from_ymd_opt(9999, 12, 31).unwrap();
+    assert_eq!(assert_same(RecordFilter::new().from(last)), Vec::<i32>::new());
+    assert_eq!(assert_same(RecordFilter::new().to(first)), Vec::<i32>::new());
+    assert_eq!(assert_same(RecordFilter::new().between(first, last)).len(), records().len());
+    assert_eq!(assert_same(parse_query("date:9999..").unwrap()), Vec::<i32>::new());
+    assert_eq!(assert_same(parse_query("date:..0").unwrap()), Vec::<i32>::new());
+    assert!(parse_query("date:10000..").is_err());
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn years_with_five_digits_are_rejected() {
+    RecordFilter::new().from(NaiveDate::from_ymd_opt(10000, 1, 1).unwrap());
+}
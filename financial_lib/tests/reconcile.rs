@@ -0,0 +1,90 @@
+//! Testy odsúhlasenia s výpisom a zamknutia odsúhlasených záznamov.
+mod common;
+
+use financial_lib::config::Config;
+use financial_lib::db;
+use financial_lib::reconcile::{Reconciliation, RecordStatus};
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+
+use common::date;
+
+fn record(id: i32, money_type: MoneyType, amount: f32, d: u32, status: RecordStatus) -> Record {
+    let expense = (money_type == MoneyType::EXPENSE).then_some(ExpenseType::FUN);
+    let mut record = Record::new(id, money_type, amount, expense, date(8, d)).with_account("bank");
+    record.status = status;
+    record
+}
+
+/// Výpis k 20. dňu: odsúhlasený príjem 100, zúčtovaný výdavok 30,
+/// čakajúci výdavok 5, výdavok po konci výpisu, výdavok iného účtu
+/// a výdavok v inej mene.
+fn statement() -> Vec<Record> {
+    vec![
+        record(1, MoneyType::INCOME, 100.0, 1, RecordStatus::Reconciled),
+        record(2, MoneyType::EXPENSE, 30.0, 10, RecordStatus::Cleared),
+        record(3, MoneyType::EXPENSE, 5.0, 12, RecordStatus::Pending),
+        record(4, MoneyType::EXPENSE, 7.0, 25, RecordStatus::Cleared),
+        record(5, MoneyType::EXPENSE, 9.0, 11, RecordStatus::Cleared).with_account("card"),
+        record(6, MoneyType::EXPENSE, 4.0, 11, RecordStatus::Cleared).with_currency("USD"),
+    ]
+}
+
+#[test]
+fn difference_follows_selected_records() {
+    let records = statement();
+    let mut reconciliation = Reconciliation::new(date(8, 20), 65.0, "EUR").with_account("bank");
+    let ids: Vec<i32> = reconciliation.candidates(&records).iter().map(|r| r.id).collect();
+    assert_eq!(ids, vec![2, 3]);
+
+    assert_eq!(reconciliation.opening_balance(&records), 100.0);
+    assert_eq!(reconciliation.difference(&records), -35.0);
+
+    reconciliation.select_cleared(&records);
+    assert_eq!(reconciliation.selected.iter().copied().collect::<Vec<_>>(), vec![2]);
+    assert_eq!(reconciliation.difference(&records), -5.0);
+    assert!(!reconciliation.is_balanced(&records));
+
+    reconciliation.toggle(3);
+    assert!(reconciliation.is_balanced(&records));
+    reconciliation.toggle(3);
+    assert_eq!(reconciliation.difference(&records), -5.0);
+
+    // záznam mimo výpisu sa nezapočíta, ani keď je označený
+    reconciliation.toggle(4);
+    assert_eq!(reconciliation.difference(&records), -5.0);
+}
+
+#[test]
+fn reconciled_record_is_locked_until_unlocked() {
+    common::database(Config::default());
+    let pending = record(11, MoneyType::EXPENSE, 30.0, 10, RecordStatus::Pending);
+    db::insert_record(&pending);
+    db::set_status(&[11], RecordStatus::Reconciled);
+    let locked = record(11, MoneyType::EXPENSE, 30.0, 10, RecordStatus::Reconciled);
+
+    let mut changed = locked.clone();
+    changed.amount = 31.0;
+    assert!(!db::update_record(&changed));
+    assert!(!db::delete_record(locked.clone()));
+    assert!(db::load_records().get_all().contains(&locked));
+
+    db::set_status(&[11], RecordStatus::Cleared);
+    changed.status = RecordStatus::Cleared;
+    assert!(db::update_record(&changed));
+    assert!(db::load_records().get_all().contains(&changed));
+    assert!(db::delete_record(changed));
+}
+
+#[test]
+fn reconciliation_without_account_keeps_to_one_currency() {
+    let records = statement();
+    let mut reconciliation = Reconciliation::new(date(8, 20), 61.0, "EUR");
+    reconciliation.select_cleared(&records);
+    assert_eq!(reconciliation.selected.iter().copied().collect::<Vec<_>>(), vec![2, 5]);
+    assert!(reconciliation.is_balanced(&records));
+
+    let mut usd = Reconciliation::new(date(8, 20), -4.0, "USD");
+    usd.select_cleared(&records);
+    assert_eq!(usd.opening_balance(&records), 0.0);
+    assert!(usd.is_balanced(&records));
+}
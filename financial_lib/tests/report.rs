@@ -0,0 +1,50 @@
+//! Testy porovnania výdavkov podľa kategórií medzi obdobiami.
+use std::collections::BTreeMap;
+
+use financial_lib::record::ExpenseType;
+use financial_lib::report::compare_categories;
+
+fn totals(values: &[(ExpenseType, f32)]) -> BTreeMap<ExpenseType, f32> {
+    values.iter().copied().collect()
+}
+
+#[test]
+fn changes_are_ordered_from_biggest_increase() {
+    let previous = totals(&[
+        (ExpenseType::RESTAURANT, 100.0),
+        (ExpenseType::SHOPPING, 50.0),
+        (ExpenseType::FUN, 30.0),
+    ]);
+    let current = totals(&[
+        (ExpenseType::RESTAURANT, 80.0),
+        (ExpenseType::SHOPPING, 75.0),
+        (ExpenseType::HOME, 40.0),
+    ]);
+
+    let changes = compare_categories(&previous, &current);
+    let order: Vec<ExpenseType> = changes.iter().map(|c| c.category).collect();
+    assert_eq!(
+        order,
+        vec![
+            ExpenseType::HOME,
+            ExpenseType::SHOPPING,
+            ExpenseType::RESTAURANT,
+            ExpenseType::FUN,
+        ]
+    );
+
+    let deltas: Vec<(f32, f32, f32)> =
+        changes.iter().map(|c| (c.previous, c.current, c.delta)).collect();
+    assert_eq!(
+        deltas,
+        vec![(0.0, 40.0, 40.0), (50.0, 75.0, 25.0), (100.0, 80.0, -20.0), (30.0, 0.0, -30.0)]
+    );
+    let percents: Vec<Option<f32>> = changes.iter().map(|c| c.percent).collect();
+    assert_eq!(percents, vec![None, Some(50.0), Some(-20.0), Some(-100.0)]);
+}
+
+#[test]
+fn categories_without_expenses_are_left_out() {
+    let previous = totals(&[(ExpenseType::CAR, 0.0)]);
+    assert!(compare_categories(&previous, &BTreeMap::new()).is_empty());
+}
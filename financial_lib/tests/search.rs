@@ -0,0 +1,103 @@
+//! Testy textového jazyka na vyhľadávanie.
+mod common;
+
+use financial_lib::config::Config;
+use financial_lib::query::{RecordFilter, SortKey, SortOrder};
+use financial_lib::record::{ExpenseType, MoneyType};
+use financial_lib::search::parse_query;
+
+use common::date;
+
+fn parse(input: &str) -> RecordFilter {
+    common::database(Config::default());
+    parse_query(input).unwrap_or_else(|e| panic!("{}: {}", input, e))
+}
+
+/// Vráti pozíciu chyby v dopyte, ktorý musí byť chybný.
+fn error_at(input: &str) -> usize {
+    common::database(Config::default());
+    match parse_query(input) {
+        Ok(filter) => panic!("{} parsed as {:?}", input, filter),
+        Err(e) => e.position,
+    }
+}
+
+#[test]
+fn parses_fields_together() {
+    let filter = parse("cat:RESTAURANT,fun amount>20 date:2026-09..2026-10 -tag:work sort:-amount");
+
+    let expected = RecordFilter::new()
+        .categories([ExpenseType::RESTAURANT, ExpenseType::FUN])
+        .min_amount(20f32.next_up())
+        .between(
+            date(9, 1),
+            date(10, 31),
+        )
+        .exclude_tag("work")
+        .sort_by(SortKey::Amount, SortOrder::Descending);
+    assert_eq!(filter, expected);
+    assert_eq!(parse("type:+ limit:5"), RecordFilter::new().money_type(MoneyType::INCOME).limit(5));
+}
+
+#[test]
+fn quoted_values_stay_field_filters() {
+    assert_eq!(parse(r#"acc:"my card""#), RecordFilter::new().account("my card"));
+    assert_eq!(parse(r#"-acc:"my card""#), RecordFilter::new().exclude_account("my card"));
+    assert_eq!(parse(r#"note:"x y""#), RecordFilter::new().note("x y"));
+    assert_eq!(parse(r#"tag:"side project""#), RecordFilter::new().tag("side project"));
+    assert_eq!(parse(r#"acc:my" "card"#), RecordFilter::new().account("my card"));
+}
+
+#[test]
+fn quoted_expression_is_free_text() {
+    assert_eq!(parse(r#""pizza place""#), RecordFilter::new().text("pizza place"));
+    assert_eq!(parse(r#""cat:FUN""#), RecordFilter::new().text("cat:FUN"));
+    assert_eq!(parse(r#"-"a b" pizza"#), RecordFilter::new().exclude_text("a b").text("pizza"));
+    assert_eq!(parse(r#""amount>5""#), RecordFilter::new().text("amount>5"));
+}
+
+#[test]
+fn error_positions_point_at_the_bad_part() {
+    assert_eq!(error_at("cat:FOO"), 4);
+    assert_eq!(error_at("cat:FUN,BAD"), 8);
+    assert_eq!(error_at(r#"cat:"FUN,BAD""#), 9);
+    assert_eq!(error_at("status:pending,nope"), 15);
+    assert_eq!(error_at("x amount>abc"), 9);
+    assert_eq!(error_at("amount:10..x"), 11);
+    assert_eq!(error_at("date:2026-13"), 5);
+    assert_eq!(error_at("date:2026..2026-13"), 11);
+    assert_eq!(error_at("type:x"), 5);
+    assert_eq!(error_at("sort:foo"), 5);
+    assert_eq!(error_at("limit:x"), 6);
+    assert_eq!(error_at("pizza foo:bar"), 6);
+    assert_eq!(error_at("acc:"), 4);
+    assert_eq!(error_at(r#"acc:"""#), 4);
+}
+
+#[test]
+fn error_positions_for_negation_and_quotes() {
+    assert_eq!(error_at("x -date:2026"), 2);
+    assert_eq!(error_at("-amount>5"), 0);
+    assert_eq!(error_at("pizza - x"), 6);
+    assert_eq!(error_at(r#"note:"abc"#), 5);
+    assert_eq!(error_at(r#"a "b"#), 2);
+}
+
+#[test]
+fn non_finite_amounts_and_out_of_range_years_are_rejected() {
+    assert_eq!(error_at("amount>nan"), 7);
+    assert_eq!(error_at("amount=inf"), 7);
+    assert_eq!(error_at("amount:1..-infinity"), 10);
+    assert_eq!(error_at("amount:NaN.."), 7);
+    assert_eq!(error_at("date:4294967295"), 5);
+    assert_eq!(error_at("date:10000.."), 5);
+    assert_eq!(error_at("date:..10000-01-01"), 7);
+    assert_eq!(error_at("date:2026..4294967295-01"), 11);
+}
+
+#[test]
+fn error_message_counts_columns_from_one() {
+    common::database(Config::default());
+    let error = parse_query("cat:FOO").unwrap_err();
+    assert_eq!(error.to_string(), "column 5: unknown category 'FOO'");
+}
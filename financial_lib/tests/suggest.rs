@@ -0,0 +1,150 @@
+//! Testy návrhov kategórií z histórie záznamov.
+mod common;
+
+use financial_lib::config::Config;
+use financial_lib::db;
+use financial_lib::record::{ExpenseType, MoneyType, Record, RecordManager};
+use financial_lib::suggest::{CategorySuggester, Feedback};
+
+use common::date;
+
+fn expense(id: i32, amount: f32, category: ExpenseType, day: u32, note: &str) -> Record {
+    Record::new(id, MoneyType::EXPENSE, amount, Some(category), date(3, day)).with_note(note)
+}
+
+fn draft(amount: f32, day: u32) -> Record {
+    Record::new(0, MoneyType::EXPENSE, amount, None, date(3, day))
+}
+
+fn history() -> RecordManager {
+    [
+        expense(1, 30.0, ExpenseType::SHOPPING, 2, "Tesco weekly"),
+        expense(2, 28.0, ExpenseType::SHOPPING, 9, "Tesco"),
+        expense(3, 25.0, ExpenseType::RESTAURANT, 3, "Pizza place"),
+        expense(4, 31.0, ExpenseType::RESTAURANT, 10, "pizza with friends"),
+        expense(5, 27.0, ExpenseType::RESTAURANT, 17, "Sushi bar"),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn note_words_decide_between_categories_with_similar_amounts() {
+    let suggester = CategorySuggester::train(&history(), &[]);
+
+    let shop = draft(29.0, 19).with_note("TESCO express");
+    let pizza = draft(29.0, 19).with_note("pizza");
+
+    assert_eq!(suggester.suggest(&shop), Some(ExpenseType::SHOPPING));
+    assert_eq!(suggester.suggest(&pizza), Some(ExpenseType::RESTAURANT));
+}
+
+#[test]
+fn account_is_used_as_feature() {
+    let manager: RecordManager = [
+        expense(1, 40.0, ExpenseType::CAR, 2, "").with_account("fuel card"),
+        expense(2, 42.0, ExpenseType::CAR, 9, "").with_account("fuel card"),
+        expense(3, 41.0, ExpenseType::HOME, 3, ""),
+        expense(4, 43.0, ExpenseType::HOME, 10, ""),
+        expense(5, 39.0, ExpenseType::HOME, 17, ""),
+    ]
+    .into_iter()
+    .collect();
+    let suggester = CategorySuggester::train(&manager, &[]);
+
+    let record = draft(40.0, 19).with_account("fuel card");
+    assert_eq!(suggester.suggest(&record), Some(ExpenseType::CAR));
+    assert_eq!(suggester.suggest(&draft(40.0, 19)), Some(ExpenseType::HOME));
+}
+
+#[test]
+fn accepted_suggestion_is_not_counted_twice() {
+    let accepted = Feedback {
+        amount: 29.0,
+        time: date(3, 19),
+        account: None,
+        note: None,
+        category: ExpenseType::SHOPPING,
+        accepted: true,
+    };
+    let without = CategorySuggester::train(&history(), &[]);
+    let with = CategorySuggester::train(&history(), &[accepted]);
+
+    assert_eq!(without.ranked(&draft(29.0, 19)), with.ranked(&draft(29.0, 19)));
+}
+
+#[test]
+fn acceptance_cancels_earlier_rejection() {
+    let feedback = |accepted| Feedback {
+        amount: 29.0,
+        time: date(3, 19),
+        account: None,
+        note: None,
+        category: ExpenseType::RESTAURANT,
+        accepted,
+    };
+    let record = draft(29.0, 19);
+    let plain = CategorySuggester::train(&history(), &[]).ranked(&record);
+    let rejected = CategorySuggester::train(&history(), &[feedback(false)]).ranked(&record);
+    let restored =
+        CategorySuggester::train(&history(), &[feedback(false), feedback(true)]).ranked(&record);
+
+    let score = |ranked: &[(ExpenseType, f64)]| {
+        ranked
+            .iter()
+            .find(|(c, _)| *c == ExpenseType::RESTAURANT)
+            .map(|(_, s)| *s)
+            .unwrap()
+    };
+    assert!(score(&rejected) < score(&plain));
+    assert_eq!(score(&restored), score(&plain));
+}
+
+#[test]
+fn rejection_applies_only_to_similar_records() {
+    let rejected = Feedback {
+        amount: 29.0,
+        time: date(3, 19),
+        account: Some("card".to_string()),
+        note: Some("Sushi bar".to_string()),
+        category: ExpenseType::RESTAURANT,
+        accepted: false,
+    };
+    let plain = CategorySuggester::train(&history(), &[]);
+    let trained = CategorySuggester::train(&history(), &[rejected]);
+    let score = |suggester: &CategorySuggester, record: &Record| {
+        suggester
+            .ranked(record)
+            .into_iter()
+            .find(|(c, _)| *c == ExpenseType::RESTAURANT)
+            .map(|(_, s)| s)
+            .unwrap()
+    };
+
+    let similar = draft(27.0, 19).with_account("card").with_note("sushi");
+    assert!(score(&trained, &similar) < score(&plain, &similar));
+
+    for other in [
+        draft(27.0, 19).with_account("card").with_note("pizza"),
+        draft(27.0, 19).with_account("cash").with_note("sushi"),
+        draft(27.0, 19).with_account("card"),
+        draft(270.0, 19).with_account("card").with_note("sushi"),
+    ] {
+        assert_eq!(score(&trained, &other), score(&plain, &other), "{:?}", other);
+    }
+}
+
+#[test]
+fn feedback_is_saved_with_account_and_note() {
+    common::database(Config::default());
+    let feedback = Feedback {
+        amount: 12.0,
+        time: date(3, 20),
+        account: Some("card".to_string()),
+        note: Some("Sushi bar".to_string()),
+        category: ExpenseType::RESTAURANT,
+        accepted: false,
+    };
+    db::insert_feedback(&feedback);
+    assert_eq!(db::load_feedback(), vec![feedback]);
+}
@@ -0,0 +1,75 @@
+//! Testy exportu a importu záznamov v CSV a JSON.
+mod common;
+
+use financial_lib::config::Config;
+use financial_lib::reconcile::RecordStatus;
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+use financial_lib::transfer::{self, Format};
+
+use common::date;
+
+/// Záznamy s poznámkami, ktoré treba v CSV dať do úvodzoviek.
+fn records() -> Vec<Record> {
+    let mut multiline = Record::new(0, MoneyType::EXPENSE, 12.5, Some(ExpenseType::FUN), date(4, 1));
+    multiline.note = Some("line1\nline2".to_string());
+    multiline.tags = vec!["trip".to_string(), "summer".to_string()];
+    let mut quoted = Record::new(0, MoneyType::INCOME, 100.0, None, date(4, 2));
+    quoted.note = Some("say \"hi\", then\r\nleave".to_string());
+    quoted.account = Some("bank, main".to_string());
+    quoted.status = RecordStatus::Reconciled;
+    vec![multiline, quoted]
+}
+
+#[test]
+fn csv_round_trip_keeps_newlines_commas_and_quotes() {
+    common::database(Config::default());
+    let records = records();
+    let csv = transfer::export(&records, Format::Csv);
+    assert_eq!(transfer::import(&csv, Format::Csv), Ok(records));
+}
+
+#[test]
+fn csv_error_reports_line_where_record_starts() {
+    common::database(Config::default());
+    let csv = format!(
+        "{}\n2026-04-01,EXPENSE,1.00,EUR,,,,\"a\nb\",\n1,2026-04-02,EXPENSE,-1,EUR,,,,,\n",
+        transfer::CSV_HEADER
+    );
+    assert_eq!(transfer::import(&csv, Format::Csv).unwrap_err().line, Some(2));
+
+    let csv = "1,2026-04-01,EXPENSE,1.00,EUR,,,,ok,\n2,2026-04-02,EXPENSE,1.00,EUR,,,,\"open\n\n";
+    assert_eq!(transfer::import(csv, Format::Csv).unwrap_err().line, Some(2));
+
+    let csv = "1,2026-04-01,EXPENSE,1.00,EUR,,,,\"a\nb\",\n2,2026-04-02,EXPENSE,-1,EUR,,,,,\n";
+    assert_eq!(transfer::import(csv, Format::Csv).unwrap_err().line, Some(3));
+}
+
+#[test]
+fn json_import_restores_deleted_records_as_new() {
+    common::database(Config::default());
+    let mut deleted = records();
+    deleted[0].id = 7;
+    deleted[0].deleted_on = Some(date(4, 3));
+    let json = transfer::export(&deleted, Format::Json);
+
+    let imported = transfer::import(&json, Format::Json).unwrap();
+    assert_eq!(imported[0].deleted_on, None);
+    assert_eq!(imported[0].note, deleted[0].note);
+}
+
+#[test]
+fn empty_tags_and_tags_with_separator_are_rejected() {
+    common::database(Config::default());
+    let mut record = records().remove(0);
+    assert_eq!(transfer::check_record(&record), Ok(()));
+    for tag in ["a,b", "", " "] {
+        record.tags = vec!["trip".to_string(), tag.to_string()];
+        assert!(transfer::check_record(&record).is_err(), "{:?}", tag);
+    }
+
+    let csv = format!(
+        "{}\n1,2026-04-01,EXPENSE,1.00,EUR,,,\"a,b\",,\n",
+        transfer::CSV_HEADER
+    );
+    assert_eq!(transfer::import(&csv, Format::Csv).unwrap_err().line, Some(2));
+}
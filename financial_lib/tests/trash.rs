@@ -0,0 +1,82 @@
+//! Testy koša – obnovenie, vymazanie, lehota a vynechanie zo súčtov.
+mod common;
+
+use chrono::{Days, Local};
+use financial_lib::config::Config;
+use financial_lib::currency::Converter;
+use financial_lib::db;
+use financial_lib::query::RecordFilter;
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+use financial_lib::trash::{expires_on, purge_before};
+
+use common::date;
+
+fn record(id: i32, account: &str) -> Record {
+    let day = date(6, 1);
+    Record::new(id, MoneyType::EXPENSE, 10.0, Some(ExpenseType::FUN), day).with_account(account)
+}
+
+fn in_trash(id: i32) -> bool {
+    db::load_trash().iter().any(|r| r.id == id)
+}
+
+fn in_records(id: i32) -> bool {
+    db::load_records().get_all().iter().any(|r| r.id == id)
+}
+
+#[test]
+fn expiry_date_follows_retention() {
+    let deleted = date(6, 10);
+    let mut trashed = record(0, "none");
+    assert_eq!(expires_on(&trashed, 30), None);
+
+    trashed.deleted_on = Some(deleted);
+    assert_eq!(expires_on(&trashed, 30), Some(date(7, 10)));
+    assert_eq!(purge_before(deleted, 30), Some(date(5, 11)));
+
+    // príliš dlhá lehota nikdy nevyprší
+    assert_eq!(expires_on(&trashed, u64::MAX), None);
+    assert_eq!(purge_before(deleted, u64::MAX), None);
+}
+
+#[test]
+fn deleted_record_is_restored_purged_and_expires() {
+    common::database(Config::default());
+    let today = Local::now().date_naive();
+    for id in [101, 102, 103] {
+        db::insert_record(&record(id, "lifecycle"));
+        assert!(db::delete_record(record(id, "lifecycle")));
+    }
+    assert!(in_trash(101) && !in_records(101));
+    assert!(!db::delete_record(record(101, "lifecycle")));
+
+    assert!(db::restore_record(101));
+    assert!(in_records(101) && !in_trash(101));
+    assert!(!db::restore_record(101));
+
+    assert!(!db::purge_record(101), "only records in the trash are purged");
+    assert!(db::purge_record(102));
+    assert!(!in_trash(102) && !in_records(102));
+
+    assert_eq!(db::purge_expired_trash(today, u64::MAX), 0);
+    assert_eq!(db::purge_expired_trash(today + Days::new(30), 30), 0);
+    assert!(in_trash(103));
+    assert!(db::purge_expired_trash(today + Days::new(31), 30) >= 1);
+    assert!(!in_trash(103));
+    assert!(in_records(101));
+}
+
+#[test]
+fn deleted_record_is_excluded_from_totals() {
+    common::database(Config::default());
+    db::insert_record(&record(201, "totals"));
+    db::insert_record(&record(202, "totals"));
+    assert!(db::delete_record(record(202, "totals")));
+
+    let filter = RecordFilter::new().account("totals");
+    let converter = Converter::new("EUR", &[]);
+    assert_eq!(db::balance(&filter, &converter), Ok(-10.0));
+    let totals = db::category_totals(&filter, &converter).unwrap();
+    assert_eq!(totals.get(&ExpenseType::FUN), Some(&10.0));
+    assert_eq!(db::load_records().query(&filter).len(), 1);
+}
@@ -0,0 +1,95 @@
+//! Texty servera v angličtine a slovenčine.
+//!
+//! Jazyk sa vyberá v nastaveniach, pozri [`financial_lib::i18n`]. Chyby
+//! v odpovediach API sú v jazyku servera, názvy polí JSON, kódy kategórií
+//! a syntax dopytov sa neprekladajú.
+use std::fmt;
+
+use financial_lib::i18n::{Catalogue, Table};
+
+/// Texty servera.
+pub static TEXTS: Catalogue = Catalogue { en: EN, sk: SK };
+
+/// Vráti text v jazyku z nastavení.
+pub fn tr(key: &str) -> &'static str {
+    TEXTS.text(key)
+}
+
+/// Vráti text s doplnenými hodnotami namiesto `{}`.
+pub fn trf(key: &str, args: &[&dyn fmt::Display]) -> String {
+    TEXTS.format(key, args)
+}
+
+const EN: Table = &[
+    // spustenie
+    ("server.listen_failed", "cannot listen on {}: {}"),
+    ("server.no_token", "no token set, the API is open to anyone who can reach it"),
+    ("server.listening", "listening on http://{}"),
+    // chyby požiadaviek
+    ("error.unauthorized", "missing or wrong bearer token"),
+    ("error.body_read", "request body cannot be read"),
+    ("error.body_not_utf8", "request body is not valid UTF-8"),
+    ("error.body_too_large", "request body is larger than {} bytes"),
+    ("error.internal", "internal error, see server log"),
+    ("error.no_endpoint", "no endpoint {}"),
+    ("error.method_not_allowed", "method {} not allowed"),
+    ("error.no_schema", "no schema named '{}'"),
+    ("error.invalid_id", "invalid id '{}'"),
+    ("error.query", "query {}"),
+    ("error.invalid_date", "invalid {} '{}', expected YYYY-MM-DD"),
+    ("error.from_after_to", "from must not be after to"),
+    ("error.unknown_period", "unknown period '{}'"),
+    ("error.unknown_grouping", "unknown grouping '{}'"),
+    ("error.expected_object", "expected an object"),
+    ("error.invalid_record", "invalid record: {}"),
+    ("error.invalid_asset", "invalid asset: {}"),
+    ("error.invalid_valuation", "invalid valuation: {}"),
+    ("error.asset_name", "name must be a non-empty string"),
+    ("error.asset_kind", "kind must be a string"),
+    ("error.valuation_value", "value must be a number >= 0"),
+    ("error.valuation_time", "time '{}', expected YYYY-MM-DD"),
+    ("error.valuation_time_type", "time must be a string"),
+    ("error.record_not_found", "record {} not found"),
+    ("error.not_in_trash", "record {} is not in the trash"),
+    ("error.asset_not_found", "asset {} not found"),
+    // výsledky
+    ("done.purged", "permanently deleted"),
+    ("done.trashed", "moved to the trash"),
+];
+
+const SK: Table = &[
+    // spustenie
+    ("server.listen_failed", "nedá sa počúvať na {}: {}"),
+    ("server.no_token", "token nie je nastavený, API môže použiť ktokoľvek, kto sa k nemu dostane"),
+    ("server.listening", "počúvam na http://{}"),
+    // chyby požiadaviek
+    ("error.unauthorized", "chýba alebo nesprávny token"),
+    ("error.body_read", "telo požiadavky sa nedá prečítať"),
+    ("error.body_not_utf8", "telo požiadavky nie je platné UTF-8"),
+    ("error.body_too_large", "telo požiadavky je väčšie ako {} bajtov"),
+    ("error.internal", "vnútorná chyba, pozri log servera"),
+    ("error.no_endpoint", "endpoint {} neexistuje"),
+    ("error.method_not_allowed", "metóda {} nie je povolená"),
+    ("error.no_schema", "schéma '{}' neexistuje"),
+    ("error.invalid_id", "neplatné ID '{}'"),
+    ("error.query", "dopyt {}"),
+    ("error.invalid_date", "neplatný parameter {} '{}', očakáva sa RRRR-MM-DD"),
+    ("error.from_after_to", "from nesmie byť po to"),
+    ("error.unknown_period", "neznáme obdobie '{}'"),
+    ("error.unknown_grouping", "neznáme zoskupenie '{}'"),
+    ("error.expected_object", "očakáva sa objekt"),
+    ("error.invalid_record", "neplatný záznam: {}"),
+    ("error.invalid_asset", "neplatný majetok: {}"),
+    ("error.invalid_valuation", "neplatné ocenenie: {}"),
+    ("error.asset_name", "name musí byť neprázdny reťazec"),
+    ("error.asset_kind", "kind musí byť reťazec"),
+    ("error.valuation_value", "value musí byť číslo >= 0"),
+    ("error.valuation_time", "time '{}', očakáva sa RRRR-MM-DD"),
+    ("error.valuation_time_type", "time musí byť reťazec"),
+    ("error.record_not_found", "záznam {} neexistuje"),
+    ("error.not_in_trash", "záznam {} nie je v koši"),
+    ("error.asset_not_found", "majetok {} neexistuje"),
+    // výsledky
+    ("done.purged", "natrvalo vymazaný"),
+    ("done.trashed", "presunutý do koša"),
+];
@@ -0,0 +1,103 @@
+//! Lokálne HTTP/JSON API nad knižnicou `financial_lib`.
+//!
+//! Server sprístupní záznamy, kategórie, agregácie a reporty pre domáce
+//! dashboardy a skratky v telefóne. Požiadavky sa vybavujú postupne jedným
+//! vláknom, zápisy do databázy sa preto nikdy neprekrývajú. Zmeny záznamov
+//! idú cez históriu ako v TUI, dajú sa teda vrátiť.
+//!
+//! Ak je nastavený token ([`ApiConfig::token`]), každá požiadavka musí
+//! mať hlavičku `Authorization: Bearer <token>`. Telo požiadavky môže mať
+//! najviac [`MAX_BODY_SIZE`] bajtov.
+//!
+//! Obsahuje:
+//! - texty v angličtine a slovenčine (`i18n`)
+//! - smerovanie a obsluhu endpointov (`routes`)
+//! - JSON schémy vstupov a výstupov (`schemas`)
+pub mod i18n;
+pub mod routes;
+pub mod schemas;
+
+use std::io::Read;
+use std::panic::{self, AssertUnwindSafe};
+
+use serde_json::{Value, json};
+use tiny_http::{Header, Request, Response, Server};
+
+use crate::i18n::{tr, trf};
+use crate::routes::{ApiError, route};
+
+/// Najväčšia povolená veľkosť tela požiadavky v bajtoch.
+pub const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// Nastavenie servera.
+#[derive(Debug, Clone, Default)]
+pub struct ApiConfig {
+    /// token, ktorý musí poslať klient, `None` vypne overovanie
+    pub token: Option<String>,
+}
+
+/// Vybavuje požiadavky, kým server beží.
+pub fn serve(server: &Server, config: &ApiConfig) {
+    for request in server.incoming_requests() {
+        handle(request, config);
+    }
+}
+
+/// Vybaví jednu požiadavku a odošle odpoveď.
+///
+/// Panika pri obsluhe (napr. chyba databázy) sa zmení na odpoveď 500,
+/// server beží ďalej.
+pub fn handle(mut request: Request, config: &ApiConfig) {
+    let (status, body) = match respond(&mut request, config) {
+        Ok((status, body)) => (status, body),
+        Err(e) => (e.status, json!({ "error": e.message })),
+    };
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("valid header");
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+    // klient mohol spojenie medzitým zavrieť
+    let _ = request.respond(response);
+}
+
+/// Overí token, prečíta telo a nasmeruje požiadavku.
+fn respond(request: &mut Request, config: &ApiConfig) -> Result<(u16, Value), ApiError> {
+    if let Some(token) = &config.token {
+        let expected = format!("Bearer {}", token);
+        let authorized = request.headers().iter().any(|h| {
+            h.field.equiv("Authorization")
+                && constant_time_eq(h.value.as_bytes(), expected.as_bytes())
+        });
+        if !authorized {
+            return Err(ApiError::new(401, tr("error.unauthorized")));
+        }
+    }
+    let too_large = || ApiError::new(413, trf("error.body_too_large", &[&MAX_BODY_SIZE]));
+    if request.body_length().is_some_and(|length| length > MAX_BODY_SIZE) {
+        return Err(too_large());
+    }
+    // telo bez dĺžky v hlavičke (chunked) sa číta najviac o bajt dlhšie než limit
+    let mut bytes = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_SIZE as u64 + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|_| ApiError::new(400, tr("error.body_read")))?;
+    if bytes.len() > MAX_BODY_SIZE {
+        return Err(too_large());
+    }
+    let body =
+        String::from_utf8(bytes).map_err(|_| ApiError::new(400, tr("error.body_not_utf8")))?;
+    let method = request.method().as_str().to_string();
+    let url = request.url().to_string();
+    panic::catch_unwind(AssertUnwindSafe(|| route(&method, &url, &body)))
+        .unwrap_or_else(|_| Err(ApiError::new(500, tr("error.internal"))))
+}
+
+/// Porovná dva reťazce bajtov v čase nezávislom od miesta prvého rozdielu.
+///
+/// Pri overovaní tokenu tak čas odpovede neprezradí, koľko znakov sa zhoduje.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
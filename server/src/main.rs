@@ -0,0 +1,91 @@
+//! Binárka `finance-server` – spustí HTTP/JSON API nad databázou
+//! zo súboru nastavení ([`financial_lib::config`]), nastavenia prepíšu
+//! prepínače `--db`, `--default-account`, `--base-currency`, `--date-format`
+//! a `--language`.
+//!
+//! Adresa sa zadáva cez `--addr`, token cez `--token` alebo premennú
+//! prostredia `FINANCE_API_TOKEN`. Bez tokenu môže API používať ktokoľvek,
+//! kto sa dostane na adresu, preto sa predvolene počúva len na `127.0.0.1`.
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use financial_lib::config::{self, Config, Flags};
+use financial_lib::i18n::{MESSAGES, fill};
+use server::i18n::{tr, trf};
+use server::{ApiConfig, serve};
+use tiny_http::Server;
+
+/// Local HTTP/JSON API over the ledger.
+#[derive(Debug, Parser)]
+#[command(name = "finance-server", version, about)]
+struct Args {
+    /// Address to listen on, use 0.0.0.0:PORT for the whole LAN
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+    /// Require `Authorization: Bearer <TOKEN>` on every request
+    #[arg(long, env = "FINANCE_API_TOKEN")]
+    token: Option<String>,
+    /// Configuration file instead of the default one
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Database file, overrides the configuration and DATABASE_URL
+    #[arg(long, value_name = "FILE")]
+    db: Option<PathBuf>,
+    /// Account of new records, overrides the configuration and FINANCE_ACCOUNT
+    #[arg(long, value_name = "NAME")]
+    default_account: Option<String>,
+    /// Currency of totals, overrides the configuration and BASE_CURRENCY
+    #[arg(long, value_name = "CODE")]
+    base_currency: Option<String>,
+    /// Date format such as %d.%m.%Y, overrides the configuration and FINANCE_DATE_FORMAT
+    #[arg(long, value_name = "FORMAT")]
+    date_format: Option<String>,
+    /// Language of messages (en or sk), overrides the configuration and FINANCE_LANGUAGE
+    #[arg(long, value_name = "CODE")]
+    language: Option<String>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let flags = Flags {
+        database: args.db,
+        account: args.default_account,
+        currency: args.base_currency,
+        date_format: args.date_format,
+        language: args.language,
+    };
+    let loaded = Config::load(args.config.as_deref())
+        .and_then(|mut settings| settings.apply_flags(&flags).map(|()| settings));
+    let settings = match loaded {
+        Ok(settings) => settings,
+        Err(e) => {
+            // nastavenia sa nenainštalovali, chyba pozná jazyk, v ktorom je napísaná
+            eprintln!("{}", fill(MESSAGES.text_in(e.language, "message.error"), &[&e]));
+            return ExitCode::FAILURE;
+        }
+    };
+    let legacy = settings.legacy_database();
+    config::install(settings);
+    if let Some(legacy) = legacy {
+        let warning = MESSAGES.format("config.legacy_database", &[&legacy.display()]);
+        eprintln!("{}", MESSAGES.format("message.warning", &[&warning]));
+    }
+    let server = match Server::http(&args.addr) {
+        Ok(server) => server,
+        Err(e) => {
+            let error = trf("server.listen_failed", &[&args.addr, &e]);
+            eprintln!("{}", MESSAGES.format("message.error", &[&error]));
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = ApiConfig {
+        token: args.token.filter(|t| !t.is_empty()),
+    };
+    if config.token.is_none() {
+        eprintln!("{}", MESSAGES.format("message.warning", &[&tr("server.no_token")]));
+    }
+    eprintln!("{}", trf("server.listening", &[&args.addr]));
+    serve(&server, &config);
+    ExitCode::SUCCESS
+}
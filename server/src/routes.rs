@@ -0,0 +1,444 @@
+//! Smerovanie a obsluha endpointov.
+//!
+//! Endpointy:
+//! - `GET /records?q=<dopyt>&trash=true` – záznamy vyhovujúce dopytu
+//!   (syntax ako vyhľadávanie v TUI) alebo záznamy v koši
+//! - `POST /records` – pridá záznam, vráti ho aj s novým ID
+//! - `GET /records/{id}` – jeden záznam
+//! - `PUT /records/{id}` – upraví záznam, stav sa nemení
+//! - `DELETE /records/{id}?purge=true` – presunie záznam do koša,
+//!   s `purge` ho natrvalo vymaže z koša
+//! - `GET /balance?q=` – zostatok v základnej mene
+//! - `GET /categories` – kategórie výdavkov
+//! - `GET /categories/totals?q=` – výdavky podľa kategórií v základnej mene
+//! - `GET /aggregates?period=month&by=category&q=` – súčty za obdobia
+//! - `GET /reports/monthly?from=YYYY-MM-DD&to=YYYY-MM-DD` – mesačný prehľad
+//! - `GET /assets` – majetok a záväzky s posledným ocenením
+//! - `POST /assets` – pridá majetok alebo záväzok, vráti ho aj s novým ID
+//! - `POST /assets/{id}/valuations` – pridá ocenenie k danému dňu
+//! - `GET /schemas`, `GET /schemas/{name}` – JSON schémy
+use std::fmt;
+
+use chrono::{Datelike, Local, NaiveDate};
+use financial_lib::{
+    aggregate::{Bucket, GroupBy, GroupKey, Period},
+    db::{
+        aggregate, balance, category_totals, commit, get_next_id, insert_asset, insert_valuation,
+        load_assets, load_converter, load_records, load_trash, load_valuations, purge_record,
+        query_records,
+    },
+    history::{Action, Change, HistoryError},
+    i18n::MESSAGES,
+    models::DB_DATE_FORMAT,
+    networth::{Asset, AssetKind, Valuation, value_on},
+    query::RecordFilter,
+    record::{ExpenseType, Record},
+    report::monthly_report,
+    search::parse_query,
+    transfer::check_record,
+};
+use serde_json::{Value, json};
+
+use crate::i18n::{tr, trf};
+use crate::schemas;
+
+/// Chyba požiadavky s HTTP stavom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl ApiError {
+    /// Vytvorí chybu s HTTP stavom a popisom.
+    pub fn new(status: u16, message: impl Into<String>) -> ApiError {
+        ApiError {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status, self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Výsledok obsluhy: HTTP stav a JSON telo odpovede.
+type ApiResult = Result<(u16, Value), ApiError>;
+
+/// Parametre z časti URL za `?`.
+#[derive(Debug, Default)]
+struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// Rozdelí a dekóduje parametre v tvare `a=1&b=2`.
+    fn parse(query: &str) -> Params {
+        Params(
+            query
+                .split('&')
+                .filter(|p| !p.is_empty())
+                .map(|p| {
+                    let (key, value) = p.split_once('=').unwrap_or((p, ""));
+                    (decode(key), decode(value))
+                })
+                .collect(),
+        )
+    }
+
+    /// Vráti hodnotu parametra, ak bol zadaný.
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Určí, či je prepínač zapnutý (`true` alebo `1`).
+    fn flag(&self, key: &str) -> bool {
+        matches!(self.get(key), Some("true" | "1"))
+    }
+
+    /// Spracuje dopyt vyhľadávania z parametra `q`.
+    fn filter(&self) -> Result<RecordFilter, ApiError> {
+        parse_query(self.get("q").unwrap_or_default())
+            .map_err(|e| ApiError::new(400, trf("error.query", &[&e])))
+    }
+
+    /// Načíta dátum `YYYY-MM-DD` z parametra.
+    fn date(&self, key: &str) -> Result<Option<NaiveDate>, ApiError> {
+        self.get(key)
+            .map(|d| {
+                NaiveDate::parse_from_str(d, DB_DATE_FORMAT)
+                    .map_err(|_| ApiError::new(400, trf("error.invalid_date", &[&key, &d])))
+            })
+            .transpose()
+    }
+}
+
+/// Dekóduje `%XX` a `+` v parametri URL.
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let mut ret = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => ret.push(b' '),
+            b'%' if i + 2 < bytes.len() => match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                (Some(high), Some(low)) => {
+                    ret.push(high * 16 + low);
+                    i += 2;
+                }
+                _ => ret.push(b'%'),
+            },
+            b => ret.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&ret).into_owned()
+}
+
+/// Nasmeruje požiadavku na obsluhu podľa metódy a cesty.
+///
+/// # Argumenty
+/// * `method` – HTTP metóda, napr. `GET`
+/// * `url` – cesta aj s parametrami, napr. `/records?q=cat:FUN`
+/// * `body` – telo požiadavky
+pub fn route(method: &str, url: &str, body: &str) -> ApiResult {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let params = Params::parse(query);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match (method, segments.as_slice()) {
+        ("GET", ["records"]) => list_records(&params),
+        ("POST", ["records"]) => create_record(body),
+        ("GET", ["records", id]) => Ok((200, json!(find_record(parse_id(id)?)?))),
+        ("PUT", ["records", id]) => update_record(parse_id(id)?, body),
+        ("DELETE", ["records", id]) => delete_record(parse_id(id)?, &params),
+        ("GET", ["balance"]) => get_balance(&params),
+        ("GET", ["categories"]) => Ok((200, categories())),
+        ("GET", ["categories", "totals"]) => get_category_totals(&params),
+        ("GET", ["aggregates"]) => get_aggregates(&params),
+        ("GET", ["reports", "monthly"]) => get_monthly_report(&params),
+        ("GET", ["assets"]) => Ok((200, list_assets())),
+        ("POST", ["assets"]) => create_asset(body),
+        ("POST", ["assets", id, "valuations"]) => create_valuation(parse_id(id)?, body),
+        ("GET", ["schemas"]) => Ok((200, schemas::all())),
+        ("GET", ["schemas", name]) => schemas::get(name)
+            .map(|s| (200, s))
+            .ok_or_else(|| ApiError::new(404, trf("error.no_schema", &[name]))),
+        (_, ["records"] | ["records", _] | ["balance"] | ["categories"] | ["categories", "totals"]
+        | ["aggregates"] | ["reports", "monthly"] | ["assets"] | ["assets", _, "valuations"]
+        | ["schemas"] | ["schemas", _]) => {
+            Err(ApiError::new(405, trf("error.method_not_allowed", &[&method])))
+        }
+        _ => Err(ApiError::new(404, trf("error.no_endpoint", &[&path]))),
+    }
+}
+
+/// Načíta ID záznamu alebo majetku z cesty.
+fn parse_id(text: &str) -> Result<i32, ApiError> {
+    text.parse()
+        .map_err(|_| ApiError::new(400, trf("error.invalid_id", &[&text])))
+}
+
+/// Načíta JSON objekt z tela požiadavky.
+///
+/// # Argumenty
+/// * `body` – telo požiadavky
+/// * `invalid` – kľúč textu chyby pre tento objekt, napr. `error.invalid_asset`
+fn parse_object(body: &str, invalid: &str) -> Result<serde_json::Map<String, Value>, ApiError> {
+    match serde_json::from_str(body) {
+        Ok(Value::Object(object)) => Ok(object),
+        Ok(_) => Err(ApiError::new(422, trf(invalid, &[&tr("error.expected_object")]))),
+        Err(e) => Err(ApiError::new(422, trf(invalid, &[&e]))),
+    }
+}
+
+/// Načíta záznam z tela požiadavky a skontroluje ho.
+fn parse_record(body: &str) -> Result<Record, ApiError> {
+    let record: Record = serde_json::from_str(body)
+        .map_err(|e| ApiError::new(422, trf("error.invalid_record", &[&e])))?;
+    check_record(&record).map_err(|e| ApiError::new(422, trf("error.invalid_record", &[&e])))?;
+    Ok(record)
+}
+
+/// Nájde záznam mimo koša.
+fn find_record(id: i32) -> Result<Record, ApiError> {
+    load_records()
+        .get_record_id(id)
+        .cloned()
+        .ok_or_else(|| ApiError::new(404, trf("error.record_not_found", &[&id])))
+}
+
+/// Uplatní akciu cez históriu, konflikt sa vráti ako 409, chyba databázy ako 500.
+fn commit_action(description: String, change: Change) -> Result<(), ApiError> {
+    commit(&Action::new(description, vec![change])).map_err(|e| {
+        let status = match e {
+            HistoryError::Database { .. } => 500,
+            HistoryError::Conflict { .. } | HistoryError::Locked { .. } => 409,
+        };
+        ApiError::new(status, e.to_string())
+    })
+}
+
+fn list_records(params: &Params) -> ApiResult {
+    let records = if params.flag("trash") {
+        load_trash()
+    } else {
+        query_records(&params.filter()?)
+    };
+    Ok((200, json!(records)))
+}
+
+fn create_record(body: &str) -> ApiResult {
+    let mut record = parse_record(body)?;
+    record.id = get_next_id();
+    record.deleted_on = None;
+    commit_action(
+        MESSAGES.format("action.add", &[&record.id]),
+        Change::insert(record.clone()),
+    )?;
+    Ok((201, json!(record)))
+}
+
+fn update_record(id: i32, body: &str) -> ApiResult {
+    let before = find_record(id)?;
+    let after = Record {
+        id,
+        status: before.status,
+        deleted_on: None,
+        ..parse_record(body)?
+    };
+    commit_action(
+        MESSAGES.format("action.edit", &[&id]),
+        Change::update(before, after.clone()),
+    )?;
+    Ok((200, json!(after)))
+}
+
+fn delete_record(id: i32, params: &Params) -> ApiResult {
+    if params.flag("purge") {
+        if !purge_record(id) {
+            return Err(ApiError::new(404, trf("error.not_in_trash", &[&id])));
+        }
+        return Ok((200, json!({ "id": id, "message": tr("done.purged") })));
+    }
+    let before = find_record(id)?;
+    let mut trashed = before.clone();
+    trashed.deleted_on = Some(Local::now().date_naive());
+    commit_action(MESSAGES.format("action.delete", &[&id]), Change::update(before, trashed))?;
+    Ok((200, json!({ "id": id, "message": tr("done.trashed") })))
+}
+
+fn get_balance(params: &Params) -> ApiResult {
+    let converter = load_converter();
+    let value = balance(&params.filter()?, &converter)
+        .map_err(|e| ApiError::new(422, e.to_string()))?;
+    Ok((200, json!({ "balance": value, "currency": converter.base })))
+}
+
+fn categories() -> Value {
+    let categories: Vec<Value> = ExpenseType::ALL
+        .iter()
+        .map(|c| {
+            let name: &str = (*c).into();
+            json!({ "code": c.code(), "name": name, "spending": c.is_spending() })
+        })
+        .collect();
+    Value::Array(categories)
+}
+
+fn get_category_totals(params: &Params) -> ApiResult {
+    let converter = load_converter();
+    let totals = category_totals(&params.filter()?, &converter)
+        .map_err(|e| ApiError::new(422, e.to_string()))?;
+    let totals: serde_json::Map<String, Value> = totals
+        .into_iter()
+        .map(|(c, total)| (c.code().to_string(), json!(total)))
+        .collect();
+    Ok((200, json!({ "currency": converter.base, "totals": totals })))
+}
+
+fn get_aggregates(params: &Params) -> ApiResult {
+    let period = match params.get("period").unwrap_or("month") {
+        "day" => Period::Day,
+        "week" => Period::Week,
+        "month" => Period::Month,
+        "quarter" => Period::Quarter,
+        "year" => Period::Year,
+        other => return Err(ApiError::new(400, trf("error.unknown_period", &[&other]))),
+    };
+    let group = match params.get("by") {
+        None => None,
+        Some("category") => Some(GroupBy::Category),
+        Some("account") => Some(GroupBy::Account),
+        Some("tag") => Some(GroupBy::Tag),
+        Some(other) => return Err(ApiError::new(400, trf("error.unknown_grouping", &[&other]))),
+    };
+    let converter = load_converter();
+    let buckets = aggregate(&params.filter()?, period, group, &converter)
+        .map_err(|e| ApiError::new(422, e.to_string()))?;
+    let buckets: Vec<Value> = buckets.iter().map(|b| bucket_json(b, &converter.base)).collect();
+    Ok((200, Value::Array(buckets)))
+}
+
+/// Prevedie súhrn obdobia na JSON podľa schémy `bucket`.
+fn bucket_json(bucket: &Bucket, currency: &str) -> Value {
+    let group = match &bucket.key {
+        GroupKey::All => None,
+        GroupKey::Category(c) => Some(c.map_or("NONE".to_string(), |c| c.code().to_string())),
+        GroupKey::Account(a) | GroupKey::Tag(a) => a.clone(),
+    };
+    json!({
+        "start": bucket.start,
+        "group": group,
+        "income": bucket.income,
+        "expense": bucket.expense,
+        "invested": bucket.invested,
+        "net": bucket.net,
+        "count": bucket.count,
+        "currency": currency,
+    })
+}
+
+fn get_monthly_report(params: &Params) -> ApiResult {
+    let today = Local::now().date_naive();
+    let from = params
+        .date("from")?
+        .unwrap_or_else(|| today.with_ordinal(1).expect("first day of year"));
+    let to = params.date("to")?.unwrap_or(today);
+    if from > to {
+        return Err(ApiError::new(400, tr("error.from_after_to")));
+    }
+    let converter = load_converter();
+    let buckets = aggregate(&params.filter()?, Period::Month, None, &converter)
+        .map_err(|e| ApiError::new(422, e.to_string()))?;
+    let months: Vec<Value> = monthly_report(&buckets, from, to)
+        .iter()
+        .map(|m| {
+            json!({
+                "month": m.month,
+                "income": m.income,
+                "expense": m.expense,
+                "net": m.net,
+                "savings_rate": m.savings_rate,
+                "currency": converter.base,
+            })
+        })
+        .collect();
+    Ok((200, Value::Array(months)))
+}
+
+/// Prevedie majetok a jeho posledné ocenenie na JSON podľa schémy `asset`.
+fn asset_json(asset: &Asset, latest: Option<Valuation>) -> Value {
+    json!({
+        "id": asset.id,
+        "name": asset.name,
+        "kind": asset.kind.code(),
+        "value": latest.map(|v| v.value),
+        "valued_on": latest.map(|v| v.time),
+    })
+}
+
+fn list_assets() -> Value {
+    let today = Local::now().date_naive();
+    let valuations = load_valuations();
+    let assets: Vec<Value> = load_assets()
+        .iter()
+        .map(|a| asset_json(a, a.id.and_then(|id| value_on(&valuations, id, today))))
+        .collect();
+    Value::Array(assets)
+}
+
+fn create_asset(body: &str) -> ApiResult {
+    let invalid = |key: &str| ApiError::new(422, trf("error.invalid_asset", &[&tr(key)]));
+    let object = parse_object(body, "error.invalid_asset")?;
+    let name = match object.get("name") {
+        Some(Value::String(name)) if !name.trim().is_empty() => name.trim(),
+        _ => return Err(invalid("error.asset_name")),
+    };
+    let kind = match object.get("kind") {
+        None | Some(Value::Null) => AssetKind::Asset,
+        Some(Value::String(kind)) => kind
+            .parse()
+            .map_err(|e| ApiError::new(422, trf("error.invalid_asset", &[&e])))?,
+        Some(_) => return Err(invalid("error.asset_kind")),
+    };
+    let mut asset = Asset::new(name, kind);
+    asset.id = Some(insert_asset(&asset));
+    Ok((201, asset_json(&asset, None)))
+}
+
+fn create_valuation(asset_id: i32, body: &str) -> ApiResult {
+    if !load_assets().iter().any(|a| a.id == Some(asset_id)) {
+        return Err(ApiError::new(404, trf("error.asset_not_found", &[&asset_id])));
+    }
+    let invalid = |message: String| ApiError::new(422, trf("error.invalid_valuation", &[&message]));
+    let object = parse_object(body, "error.invalid_valuation")?;
+    let value = object
+        .get("value")
+        .and_then(Value::as_f64)
+        // hodnota sa ukladá ako f32, väčšie číslo by sa uložilo ako nekonečno
+        .filter(|v| (*v as f32).is_finite() && *v >= 0.0)
+        .ok_or_else(|| invalid(tr("error.valuation_value").to_string()))?;
+    let time = match object.get("time") {
+        None | Some(Value::Null) => Local::now().date_naive(),
+        Some(Value::String(time)) => NaiveDate::parse_from_str(time, DB_DATE_FORMAT)
+            .map_err(|_| invalid(trf("error.valuation_time", &[time])))?,
+        Some(_) => return Err(invalid(tr("error.valuation_time_type").to_string())),
+    };
+    let valuation = Valuation {
+        asset_id,
+        time,
+        value: value as f32,
+    };
+    insert_valuation(&valuation);
+    Ok((
+        201,
+        json!({ "asset_id": asset_id, "time": valuation.time, "value": valuation.value }),
+    ))
+}
@@ -0,0 +1,198 @@
+//! JSON schémy vstupov a výstupov API.
+//!
+//! Schémy sú vo formáte JSON Schema (draft 2020-12) a opisujú presne to,
+//! čo vracia [`crate::routes`], takže si podľa nich klient môže
+//! vygenerovať typy alebo overiť odpovede.
+use financial_lib::{reconcile::RecordStatus, record::ExpenseType};
+use serde_json::{Value, json};
+
+/// Názvy všetkých schém v poradí, v akom ich vráti [`all`].
+pub const NAMES: [&str; 10] = [
+    "record",
+    "message",
+    "error",
+    "balance",
+    "category",
+    "category_totals",
+    "bucket",
+    "month",
+    "asset",
+    "valuation",
+];
+
+/// Vráti schému podľa názvu, `None` pre neznámy názov.
+pub fn get(name: &str) -> Option<Value> {
+    let mut schema = match name {
+        "record" => record(),
+        "message" => object(
+            "Result of a change of one record",
+            json!({ "id": { "type": "integer" }, "message": { "type": "string" } }),
+            &["id", "message"],
+        ),
+        "error" => object(
+            "Error response of any endpoint",
+            json!({ "error": { "type": "string" } }),
+            &["error"],
+        ),
+        "balance" => object(
+            "Balance in the base currency",
+            json!({ "balance": { "type": "number" }, "currency": currency() }),
+            &["balance", "currency"],
+        ),
+        "category" => object(
+            "Expense category",
+            json!({
+                "code": categories(),
+                "name": { "type": "string" },
+                "spending": {
+                    "type": "boolean",
+                    "description": "false for categories that are not counted as spending"
+                }
+            }),
+            &["code", "name", "spending"],
+        ),
+        "category_totals" => object(
+            "Spending per category in the base currency",
+            json!({
+                "currency": currency(),
+                "totals": {
+                    "type": "object",
+                    "propertyNames": categories(),
+                    "additionalProperties": { "type": "number" }
+                }
+            }),
+            &["currency", "totals"],
+        ),
+        "bucket" => object(
+            "Sums of one period, optionally split by category, account or tag",
+            json!({
+                "start": date("first day of the period"),
+                "group": {
+                    "type": ["string", "null"],
+                    "description": "category code, account or tag; null when not split"
+                },
+                "income": { "type": "number" },
+                "expense": {
+                    "type": "number",
+                    "description": "spending without investment purchases, positive number"
+                },
+                "invested": {
+                    "type": "number",
+                    "description": "expenses that are not spending, e.g. investment purchases"
+                },
+                "net": { "type": "number", "description": "income - expense - invested" },
+                "count": { "type": "integer", "minimum": 0 },
+                "currency": currency()
+            }),
+            &["start", "group", "income", "expense", "invested", "net", "count", "currency"],
+        ),
+        "month" => object(
+            "Income, expense and savings of one month",
+            json!({
+                "month": date("first day of the month"),
+                "income": { "type": "number" },
+                "expense": {
+                    "type": "number",
+                    "description": "spending without investment purchases"
+                },
+                "net": { "type": "number", "description": "income - expense" },
+                "savings_rate": {
+                    "type": ["number", "null"],
+                    "description": "net divided by income, null without income"
+                },
+                "currency": currency()
+            }),
+            &["month", "income", "expense", "net", "savings_rate", "currency"],
+        ),
+        "asset" => object(
+            "Asset or liability with its latest value; only name is required on input",
+            json!({
+                "id": { "type": "integer" },
+                "name": { "type": "string", "minLength": 1 },
+                "kind": { "enum": ["ASSET", "LIABILITY"], "default": "ASSET" },
+                "value": {
+                    "type": ["number", "null"],
+                    "description": "latest value, the owed amount for a liability"
+                },
+                "valued_on": {
+                    "anyOf": [date("day of the latest value"), { "type": "null" }]
+                }
+            }),
+            &["name"],
+        ),
+        "valuation" => object(
+            "Value of an asset or liability on a day; time defaults to today on input",
+            json!({
+                "asset_id": { "type": "integer" },
+                "time": date("day of the value"),
+                "value": { "type": "number", "minimum": 0 }
+            }),
+            &["value"],
+        ),
+        _ => return None,
+    };
+    schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+    schema["title"] = json!(name);
+    Some(schema)
+}
+
+/// Vráti všetky schémy ako objekt názov → schéma.
+pub fn all() -> Value {
+    Value::Object(
+        NAMES
+            .iter()
+            .map(|name| (name.to_string(), get(name).expect("every name has a schema")))
+            .collect(),
+    )
+}
+
+/// Schéma záznamu, vstup aj výstup endpointov `/records`.
+fn record() -> Value {
+    let statuses: Vec<&str> = RecordStatus::ALL.iter().map(|s| s.code()).collect();
+    object(
+        "Record; only money_type, amount and time are required on input, \
+         id, status and deleted_on are ignored on input",
+        json!({
+            "id": { "type": "integer" },
+            "money_type": { "enum": ["INCOME", "EXPENSE"] },
+            "amount": { "type": "number", "minimum": 0 },
+            "expense": {
+                "anyOf": [categories(), { "type": "null" }],
+                "description": "expense category"
+            },
+            "time": date("date of the record"),
+            "account": { "type": ["string", "null"] },
+            "tags": { "type": "array", "items": { "type": "string" } },
+            "note": { "type": ["string", "null"] },
+            "currency": currency(),
+            "status": { "enum": statuses },
+            "deleted_on": {
+                "anyOf": [date("day the record was moved to the trash"), { "type": "null" }]
+            }
+        }),
+        &["money_type", "amount", "time"],
+    )
+}
+
+/// Schéma objektu s popisom, položkami a povinnými položkami.
+fn object(description: &str, properties: Value, required: &[&str]) -> Value {
+    json!({
+        "type": "object",
+        "description": description,
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn categories() -> Value {
+    let codes: Vec<&str> = ExpenseType::ALL.iter().map(|c| c.code()).collect();
+    json!({ "enum": codes })
+}
+
+fn currency() -> Value {
+    json!({ "type": "string", "pattern": "^[A-Z]{3}$", "description": "ISO 4217 code" })
+}
+
+fn date(description: &str) -> Value {
+    json!({ "type": "string", "format": "date", "description": description })
+}
@@ -0,0 +1,311 @@
+//! Integračné testy HTTP API nad dočasnou SQLite databázou.
+//!
+//! Všetky testy zdieľajú jeden server a jednu databázu, preto každý test
+//! pracuje iba so záznamami so svojím štítkom.
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpStream};
+use std::sync::OnceLock;
+use std::thread;
+
+use serde_json::{Value, json};
+use server::{ApiConfig, MAX_BODY_SIZE, schemas, serve};
+use tempfile::TempDir;
+use tiny_http::Server;
+
+const TOKEN: &str = "test-token";
+
+/// Spustený server a dočasný adresár s jeho databázou.
+struct TestServer {
+    addr: SocketAddr,
+    _dir: TempDir,
+}
+
+/// Spustí server nad prázdnou dočasnou databázou pri prvom použití.
+fn server() -> SocketAddr {
+    static SERVER: OnceLock<TestServer> = OnceLock::new();
+    SERVER
+        .get_or_init(|| {
+            let dir = TempDir::new().expect("temporary directory");
+            let db = dir.path().join("test.db");
+            // SAFETY: nastaví sa raz, skôr než server a testy začnú čítať prostredie
+            unsafe {
+                std::env::set_var("FINANCE_CONFIG", dir.path().join("config.toml"));
+                std::env::set_var("DATABASE_URL", &db);
+                std::env::set_var("BASE_CURRENCY", "EUR");
+            }
+            let http = Server::http("127.0.0.1:0").expect("bind test server");
+            let addr = http.server_addr().to_ip().expect("TCP address");
+            let config = ApiConfig {
+                token: Some(TOKEN.to_string()),
+            };
+            thread::spawn(move || serve(&http, &config));
+            TestServer { addr, _dir: dir }
+        })
+        .addr
+}
+
+/// Pošle požiadavku a vráti HTTP stav a JSON telo odpovede.
+fn request_with(token: Option<&str>, method: &str, path: &str, body: Option<Value>) -> (u16, Value) {
+    let mut stream = TcpStream::connect(server()).expect("connect to test server");
+    let body = body.map(|b| b.to_string()).unwrap_or_default();
+    let auth = token.map_or(String::new(), |t| format!("Authorization: Bearer {}\r\n", t));
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{}\
+         Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        auth,
+        body.len(),
+        body
+    )
+    .expect("send request");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("read response");
+    let (head, body) = response.split_once("\r\n\r\n").expect("HTTP response");
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .expect("status code");
+    let body = serde_json::from_str(body).unwrap_or(Value::Null);
+    (status, body)
+}
+
+fn request(method: &str, path: &str, body: Option<Value>) -> (u16, Value) {
+    request_with(Some(TOKEN), method, path, body)
+}
+
+fn expense(amount: f32, category: &str, time: &str, tag: &str) -> Value {
+    json!({
+        "money_type": "EXPENSE",
+        "amount": amount,
+        "expense": category,
+        "time": time,
+        "tags": [tag],
+    })
+}
+
+#[test]
+fn requests_without_valid_token_are_rejected() {
+    let (status, body) = request_with(None, "GET", "/categories", None);
+    assert_eq!(status, 401);
+    assert!(body["error"].is_string());
+
+    for token in ["wrong", "test-toke", "test-token2", ""] {
+        let (status, _) = request_with(Some(token), "GET", "/categories", None);
+        assert_eq!(status, 401, "{:?}", token);
+    }
+}
+
+#[test]
+fn oversized_bodies_are_rejected() {
+    // hlavička s dĺžkou nad limit stačí, telo klient vôbec nepošle
+    let mut stream = TcpStream::connect(server()).expect("connect to test server");
+    write!(
+        stream,
+        "POST /records HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+         Authorization: Bearer {}\r\nContent-Length: {}\r\n\r\n",
+        TOKEN,
+        MAX_BODY_SIZE + 1
+    )
+    .expect("send request");
+    stream.shutdown(Shutdown::Write).expect("close request");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("read response");
+    assert!(response.starts_with("HTTP/1.1 413"), "{}", response);
+
+    let mut record = expense(1.0, "FUN", "2026-01-01", "oversized");
+    record["note"] = json!("x".repeat(MAX_BODY_SIZE));
+    let (status, body) = request("POST", "/records", Some(record));
+    assert_eq!(status, 413);
+    assert!(body["error"].is_string());
+}
+
+#[test]
+fn record_lifecycle() {
+    let (status, created) = request(
+        "POST",
+        "/records",
+        Some(expense(12.5, "RESTAURANT", "2026-03-04", "lifecycle")),
+    );
+    assert_eq!(status, 201);
+    let id = created["id"].as_i64().expect("new id");
+    assert_eq!(created["currency"], "EUR");
+    assert_eq!(created["status"], "PENDING");
+
+    let (status, fetched) = request("GET", &format!("/records/{}", id), None);
+    assert_eq!(status, 200);
+    assert_eq!(fetched, created);
+
+    let mut changed = created.clone();
+    changed["note"] = json!("dinner, two people");
+    changed["status"] = json!("RECONCILED");
+    let (status, updated) = request("PUT", &format!("/records/{}", id), Some(changed));
+    assert_eq!(status, 200);
+    assert_eq!(updated["note"], "dinner, two people");
+    assert_eq!(updated["status"], "PENDING", "status is not changed by PUT");
+
+    let (status, listed) = request("GET", "/records?q=tag:lifecycle", None);
+    assert_eq!(status, 200);
+    assert_eq!(listed.as_array().map(|a| a.len()), Some(1));
+    assert_eq!(listed[0]["note"], "dinner, two people");
+
+    let (status, _) = request("DELETE", &format!("/records/{}", id), None);
+    assert_eq!(status, 200);
+    let (status, _) = request("GET", &format!("/records/{}", id), None);
+    assert_eq!(status, 404);
+    let (_, listed) = request("GET", "/records?q=tag:lifecycle", None);
+    assert_eq!(listed, json!([]));
+    let (_, trash) = request("GET", "/records?trash=true", None);
+    assert!(trash.as_array().expect("array").iter().any(|r| r["id"] == id));
+
+    let (status, _) = request("DELETE", &format!("/records/{}?purge=true", id), None);
+    assert_eq!(status, 200);
+    let (status, _) = request("DELETE", &format!("/records/{}?purge=true", id), None);
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn invalid_requests_are_reported() {
+    let (status, body) = request("POST", "/records", Some(json!({ "amount": 3 })));
+    assert_eq!(status, 422);
+    assert!(body["error"].as_str().expect("message").contains("money_type"));
+
+    let (status, _) = request(
+        "POST",
+        "/records",
+        Some(expense(-3.0, "FUN", "2026-01-01", "invalid")),
+    );
+    assert_eq!(status, 422);
+
+    let (status, _) = request("GET", "/records?q=amount%3Eabc", None);
+    assert_eq!(status, 400);
+    let (status, _) = request("GET", "/records/abc", None);
+    assert_eq!(status, 400);
+    let (status, _) = request("GET", "/records/999999", None);
+    assert_eq!(status, 404);
+    let (status, _) = request("GET", "/nothing", None);
+    assert_eq!(status, 404);
+    let (status, _) = request("PATCH", "/records", None);
+    assert_eq!(status, 405);
+    let (status, _) = request("GET", "/aggregates?period=decade", None);
+    assert_eq!(status, 400);
+}
+
+#[test]
+fn aggregates_and_reports() {
+    for body in [
+        expense(10.0, "FUN", "2026-01-10", "agg"),
+        expense(30.0, "HOME", "2026-01-20", "agg"),
+        expense(5.0, "FUN", "2026-02-03", "agg"),
+        json!({ "money_type": "INCOME", "amount": 100.0, "time": "2026-02-01", "tags": ["agg"] }),
+    ] {
+        assert_eq!(request("POST", "/records", Some(body)).0, 201);
+    }
+
+    let (status, balance) = request("GET", "/balance?q=tag:agg", None);
+    assert_eq!(status, 200);
+    assert_eq!(balance, json!({ "balance": 55.0, "currency": "EUR" }));
+
+    let (status, totals) = request("GET", "/categories/totals?q=tag:agg", None);
+    assert_eq!(status, 200);
+    assert_eq!(totals["totals"]["FUN"], 15.0);
+    assert_eq!(totals["totals"]["HOME"], 30.0);
+
+    let (status, buckets) = request("GET", "/aggregates?period=month&by=category&q=tag:agg", None);
+    assert_eq!(status, 200);
+    let january_fun = buckets
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|b| b["start"] == "2026-01-01" && b["group"] == "FUN")
+        .expect("January FUN bucket");
+    assert_eq!(january_fun["expense"], 10.0);
+    assert_eq!(january_fun["count"], 1);
+
+    let (status, months) = request(
+        "GET",
+        "/reports/monthly?from=2026-01-01&to=2026-03-31&q=tag%3Aagg",
+        None,
+    );
+    assert_eq!(status, 200);
+    let months = months.as_array().expect("array");
+    assert_eq!(months.len(), 3);
+    assert_eq!(months[0]["expense"], 40.0);
+    assert_eq!(months[0]["savings_rate"], Value::Null);
+    assert_eq!(months[1]["net"], 95.0);
+    assert_eq!(months[2]["net"], 0.0);
+}
+
+#[test]
+fn categories_and_schemas() {
+    let (status, categories) = request("GET", "/categories", None);
+    assert_eq!(status, 200);
+    let categories = categories.as_array().expect("array");
+    assert_eq!(categories.len(), 10);
+    assert!(categories.contains(&json!({ "code": "INVESTMENT", "name": "Investment", "spending": false })));
+
+    let (status, all) = request("GET", "/schemas", None);
+    assert_eq!(status, 200);
+    for name in schemas::NAMES {
+        assert_eq!(all[name]["title"], name);
+    }
+    let (status, record) = request("GET", "/schemas/record", None);
+    assert_eq!(status, 200);
+    assert_eq!(record["required"], json!(["money_type", "amount", "time"]));
+    let (status, _) = request("GET", "/schemas/nope", None);
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn assets_and_valuations() {
+    let (status, flat) = request("POST", "/assets", Some(json!({ "name": "Flat" })));
+    assert_eq!(status, 201);
+    assert_eq!(flat["kind"], "ASSET");
+    assert_eq!(flat["value"], Value::Null);
+    let id = flat["id"].as_i64().expect("new id");
+
+    let (status, valuation) = request(
+        "POST",
+        &format!("/assets/{}/valuations", id),
+        Some(json!({ "time": "2026-01-01", "value": 120000.0 })),
+    );
+    assert_eq!(status, 201);
+    assert_eq!(valuation, json!({ "asset_id": id, "time": "2026-01-01", "value": 120000.0 }));
+
+    let (status, assets) = request("GET", "/assets", None);
+    assert_eq!(status, 200);
+    let listed = assets
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|a| a["id"] == id)
+        .expect("new asset is listed");
+    assert_eq!(listed["value"], 120000.0);
+    assert_eq!(listed["valued_on"], "2026-01-01");
+
+    let (status, loan) = request(
+        "POST",
+        "/assets",
+        Some(json!({ "name": "Mortgage", "kind": "LIABILITY" })),
+    );
+    assert_eq!(status, 201);
+    assert_eq!(loan["kind"], "LIABILITY");
+
+    let (status, _) = request("POST", "/assets", Some(json!({ "name": " " })));
+    assert_eq!(status, 422);
+    let (status, _) = request("POST", "/assets", Some(json!({ "name": "X", "kind": "CAR" })));
+    assert_eq!(status, 422);
+    let path = format!("/assets/{}/valuations", id);
+    let (status, _) = request("POST", &path, Some(json!({ "value": -1 })));
+    assert_eq!(status, 422);
+    let (status, _) = request("POST", &path, Some(json!({ "value": 1e39 })));
+    assert_eq!(status, 422);
+    let (status, _) = request("POST", &path, Some(json!({ "value": 1, "time": "1.1.2026" })));
+    assert_eq!(status, 422);
+    let (status, _) = request("POST", "/assets/999999/valuations", Some(json!({ "value": 1 })));
+    assert_eq!(status, 404);
+    let (status, _) = request("DELETE", "/assets", None);
+    assert_eq!(status, 405);
+}
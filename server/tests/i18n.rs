@@ -0,0 +1,29 @@
+//! Testy, že každý text servera, ktorý sa používa, má anglický preklad.
+use std::path::Path;
+
+use financial_lib::i18n::{Language, MESSAGES, source_keys};
+use server::i18n::TEXTS;
+
+#[test]
+fn every_used_key_is_in_english_table() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    // kľúče chýb objektov sa odovzdávajú do `parse_object` a `invalid`
+    let keys = source_keys(&src, &["tr(", "trf(", "parse_object(body,", "invalid("]);
+    for (path, key) in &keys {
+        assert!(TEXTS.has(Language::En, key), "{}: missing text '{}'", path.display(), key);
+    }
+    for (path, key) in source_keys(&src, &["MESSAGES.format("]) {
+        assert!(
+            MESSAGES.has(Language::En, &key),
+            "{}: missing library text '{}'",
+            path.display(),
+            key
+        );
+    }
+    assert!(keys.len() > 25, "only {} keys found", keys.len());
+}
+
+#[test]
+fn every_translation_has_english_text() {
+    assert_eq!(TEXTS.inconsistencies(), Vec::<String>::new());
+}
@@ -1,16 +1,31 @@
 
 //! Hlavný aplikačný modul TUI aplikácie.
 use crate::{
+    audit::AuditView,
     event::{AppEvent, Event, EventHandler},
+    i18n::{tr, trf},
+    investments::InvestmentView,
+    keymap::{Keymap, Mode},
+    loans::LoanView,
+    net_worth::NetWorthView,
+    reconcile::ReconcileView,
     record_list::RecordLister,
+    rpc::{self, APP_ERROR, Reply, RpcError},
+    theme::Theme,
+    trash::TrashView,
 };
+use chrono::{Local, NaiveDate};
 use color_eyre::eyre::Ok;
-use financial_lib::record::{ExpenseType, Record};
+use financial_lib::config::{self, date_format};
+use financial_lib::i18n::MESSAGES;
+use financial_lib::reconcile::RecordStatus;
+use financial_lib::record::{ExpenseType, MoneyType, Record};
+use financial_lib::search::{ParseError, parse_query};
+use financial_lib::suggest::Feedback;
+use serde_json::json;
+
+use ratatui::{DefaultTerminal, crossterm::event::KeyEvent};
 
-use ratatui::{
-    DefaultTerminal,
-    crossterm::event::{KeyCode, KeyEvent},
-};
 
 /// Určuje, ktorý widget je momentálne aktívny.
 #[derive(Debug, PartialEq)]
@@ -39,9 +54,39 @@ pub struct App {
     pub input_select: usize,
     /// čo sa má zobrazit v input režime
     pub input_buffer: Vec<String>,
-    
+    /// kategória predvyplnená z histórie, ak nejaká bola navrhnutá
+    pub suggested_category: Option<ExpenseType>,
+
     ///update mode zapnutý
     pub update_mode: bool,
+
+    /// vyhľadávací riadok je aktívny
+    pub search_mode: bool,
+    /// text vyhľadávacieho dopytu
+    pub search_input: String,
+    /// chyba v dopyte, ak sa nedal spracovať
+    pub search_error: Option<ParseError>,
+
+    /// namiesto grafu zostatku sa zobrazuje mesačný prehľad
+    pub monthly_view: bool,
+    /// otvorená obrazovka čistej hodnoty
+    pub net_worth: Option<NetWorthView>,
+    /// otvorená obrazovka úverov
+    pub loans: Option<LoanView>,
+    /// otvorená obrazovka investícií
+    pub investments: Option<InvestmentView>,
+    /// otvorená obrazovka odsúhlasenia s výpisom
+    pub reconcile: Option<ReconcileView>,
+    /// otvorená história vybraného záznamu
+    pub audit: Option<AuditView>,
+    /// otvorený kôš odstránených záznamov
+    pub trash: Option<TrashView>,
+    /// krátke upozornenie k poslednej akcii, zmizne pri ďalšej klávese
+    pub notice: Option<String>,
+    /// aktívne klávesové skratky
+    pub keymap: Keymap,
+    /// farebná téma
+    pub theme: Theme,
 }
 
 impl Default for App {
@@ -53,21 +98,52 @@ impl Default for App {
             record_lister: RecordLister::new(),
             input_mode: false,
             input_select: 0,
-            input_buffer: vec!["".to_string(); 4],
+            input_buffer: vec!["".to_string(); 8],
+            suggested_category: None,
             update_mode: false,
             help_show: false,
+            search_mode: false,
+            search_input: String::new(),
+            search_error: None,
+            monthly_view: config::current().ui.monthly_view,
+            net_worth: None,
+            loans: None,
+            investments: None,
+            reconcile: None,
+            audit: None,
+            trash: None,
+            notice: None,
+            keymap: Keymap::default(),
+            theme: Theme::default(),
         }
     }
 }
 
 impl App {
-    /// vytvortý novú inštanciu [`App`].
-    pub fn new() -> Self {
-        Self::default()
+    /// vytvortý novú inštanciu [`App`] so zadanými klávesovými skratkami a témou.
+    pub fn new(keymap: Keymap, theme: Theme) -> Self {
+        Self {
+            keymap,
+            theme,
+            ..Self::default()
+        }
+    }
+    /// Upozornenie pri pokuse zmeniť odsúhlasený záznam.
+    fn locked_notice(&self) -> String {
+        trf("notice.locked", &[&self.keymap.keys(Mode::Normal, "unlock")])
     }
 
     /// Hlavný cyklus applikácie
+    ///
+    /// Kým beží, počúva aj na ovládacom sockete (pozri [`rpc`]).
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
+        let _socket = match rpc::start(self.events.sender()) {
+            Result::Ok(socket) => Some(socket),
+            Err(e) => {
+                self.notice = Some(trf("notice.socket_disabled", &[&e]));
+                None
+            }
+        };
         while self.running {
             terminal.draw(|frame| frame.render_widget(&mut self, frame.area()))?;
             match self.events.next().await? {
@@ -92,13 +168,65 @@ impl App {
                     AppEvent::Addchar(c) => self.char_add(c),
                     AppEvent::Remchar => self.rem_char(),
                     AppEvent::TabInput => self.tab_input(),
-                    AppEvent::BackTabInput => self.BackTabInput(),
-                    AppEvent::EscReset => self.EscReset(),
-                    AppEvent::EnterCOnfirm => self.EnterConfirm().await,
+                    AppEvent::BackTabInput => self.back_tab_input(),
+                    AppEvent::EscReset => self.esc_reset(),
+                    AppEvent::EnterConfirm => self.enter_confirm().await,
                     AppEvent::EnterInputMode => self.enter_input_mode(),
                     AppEvent::EditRecord => self.enter_edit_mode(),
                     AppEvent::HelpEnter => self.help_enter(),
                     AppEvent::HelpExit => self.help_exit(),
+                    AppEvent::SearchEnter => self.search_enter(),
+                    AppEvent::SearchChar(c) => self.search_char(c).await,
+                    AppEvent::SearchRemChar => self.search_rem_char().await,
+                    AppEvent::SearchConfirm => self.search_confirm(),
+                    AppEvent::SearchCancel => self.search_cancel().await,
+                    AppEvent::ToggleMonthly => self.toggle_monthly(),
+                    AppEvent::CycleComparison => self.record_lister.cycle_comparison().await,
+                    AppEvent::CycleForecast => self.record_lister.cycle_forecast(),
+                    AppEvent::ToggleNetWorth => self.toggle_net_worth().await,
+                    AppEvent::ToggleLoans => self.toggle_loans().await,
+                    AppEvent::ToggleInvestments => self.toggle_investments().await,
+                    AppEvent::NextLoan => self.loans.iter_mut().for_each(|l| l.select_next()),
+                    AppEvent::PreviousLoan => {
+                        self.loans.iter_mut().for_each(|l| l.select_previous())
+                    }
+                    AppEvent::CycleStatus => self.cycle_status().await,
+                    AppEvent::UnlockRecord => self.unlock_record().await,
+                    AppEvent::ToggleAudit => self.toggle_audit().await,
+                    AppEvent::Undo => self.undo().await,
+                    AppEvent::Redo => self.redo().await,
+                    AppEvent::ToggleReconcile => self.toggle_reconcile().await,
+                    AppEvent::ReconcileChar(c) => {
+                        self.reconcile.iter_mut().for_each(|r| r.char_add(c))
+                    }
+                    AppEvent::ReconcileRemChar => {
+                        self.reconcile.iter_mut().for_each(|r| r.rem_char())
+                    }
+                    AppEvent::ReconcileTab => {
+                        self.reconcile.iter_mut().for_each(|r| r.tab_input())
+                    }
+                    AppEvent::ReconcileConfirm => self.reconcile_confirm().await,
+                    AppEvent::ReconcileToggle => {
+                        self.reconcile.iter_mut().for_each(|r| r.toggle_selected())
+                    }
+                    AppEvent::NextReconcile => {
+                        self.reconcile.iter_mut().for_each(|r| r.select_next())
+                    }
+                    AppEvent::PreviousReconcile => {
+                        self.reconcile.iter_mut().for_each(|r| r.select_previous())
+                    }
+                    AppEvent::ToggleTrash => self.toggle_trash().await,
+                    AppEvent::NextTrash => self.trash.iter_mut().for_each(|t| t.select_next()),
+                    AppEvent::PreviousTrash => {
+                        self.trash.iter_mut().for_each(|t| t.select_previous())
+                    }
+                    AppEvent::RestoreTrash => self.restore_trashed().await,
+                    AppEvent::PurgeTrash => self.purge_trashed().await,
+                    AppEvent::RpcPushRecords(records, reply) => {
+                        self.push_records(records, reply).await
+                    }
+                    AppEvent::RpcSelection(reply) => self.send_selection(reply),
+                    AppEvent::RpcFilter(reply) => self.send_filter(reply),
                 },
             }
         }
@@ -106,41 +234,42 @@ impl App {
     }
 
     /// handler pre eventy  [`App`].
+    ///
+    /// Klávesu preloží na udalosť podľa skratiek aktuálneho režimu.
     pub fn handle_key_events(&mut self, key_event: KeyEvent) -> color_eyre::Result<()> {
+        self.notice = None;
+        if let Some(event) = self.keymap.event(self.key_mode(), key_event) {
+            self.events.send(event);
+        }
+        Ok(())
+    }
+
+    /// Vráti režim, ktorého skratky práve platia.
+    pub fn key_mode(&self) -> Mode {
         if self.input_mode {
-            match key_event.code {
-                KeyCode::Char(c) => self.events.send(AppEvent::Addchar(c)),
-                KeyCode::Backspace => self.events.send(AppEvent::Remchar),
-                KeyCode::Tab => self.events.send(AppEvent::TabInput),
-                KeyCode::BackTab => self.events.send(AppEvent::BackTabInput),
-                KeyCode::Esc => self.events.send(AppEvent::EscReset),
-                KeyCode::Enter => self.events.send(AppEvent::EnterCOnfirm),
-                _ => {}
+            Mode::Input
+        } else if self.search_mode {
+            Mode::Search
+        } else if self.net_worth.is_some() {
+            Mode::NetWorth
+        } else if self.loans.is_some() {
+            Mode::Loans
+        } else if self.investments.is_some() {
+            Mode::Investments
+        } else if self.audit.is_some() {
+            Mode::History
+        } else if self.trash.is_some() {
+            Mode::Trash
+        } else if let Some(view) = &self.reconcile {
+            if view.reconciliation.is_none() {
+                Mode::ReconcileForm
+            } else {
+                Mode::Reconcile
             }
-            Ok(())
         } else if self.help_show {
-            match key_event.code {
-                KeyCode::Esc | KeyCode::Char('q') => self.events.send(AppEvent::HelpExit),
-
-                _ => {}
-            }
-            Ok(())
+            Mode::Help
         } else {
-            match key_event.code {
-                KeyCode::Char('q') => self.events.send(AppEvent::Quit),
-                KeyCode::Tab => self.events.send(AppEvent::IncrementWidget),
-                KeyCode::BackTab => self.events.send(AppEvent::DecrementWidget),
-                KeyCode::Up => self.events.send(AppEvent::IncrementRecords),
-                KeyCode::Down => self.events.send(AppEvent::DecrementRecords),
-                KeyCode::Delete => self.events.send(AppEvent::RemoveRecord),
-                KeyCode::Char('a') => self.events.send(AppEvent::EnterInputMode),
-                KeyCode::Enter => self.events.send(AppEvent::EditRecord),
-                KeyCode::Char('h') => self.events.send(AppEvent::HelpEnter),
-
-                
-                _ => {}
-            }
-            Ok(())
+            Mode::Normal
         }
     }
 
@@ -159,10 +288,242 @@ impl App {
     pub fn help_exit(&mut self) {
         self.help_show = false;
     }
+    /// Prepne spodný graf medzi zostatkom a mesačným prehľadom.
+    pub fn toggle_monthly(&mut self) {
+        self.monthly_view = !self.monthly_view;
+    }
+    /// Otvorí obrazovku čistej hodnoty s čerstvými dátami alebo ju zatvorí.
+    pub async fn toggle_net_worth(&mut self) {
+        self.net_worth = match self.net_worth {
+            Some(_) => None,
+            None => Some(NetWorthView::load().await),
+        };
+    }
+    /// Otvorí obrazovku úverov s čerstvými dátami alebo ju zatvorí.
+    pub async fn toggle_loans(&mut self) {
+        self.loans = match self.loans {
+            Some(_) => None,
+            None => Some(LoanView::load().await),
+        };
+    }
+    /// Otvorí obrazovku investícií s čerstvými dátami alebo ju zatvorí.
+    pub async fn toggle_investments(&mut self) {
+        self.investments = match self.investments {
+            Some(_) => None,
+            None => Some(InvestmentView::load().await),
+        };
+    }
+    /// Otvorí obrazovku odsúhlasenia alebo ju zatvorí bez zmien.
+    pub async fn toggle_reconcile(&mut self) {
+        self.reconcile = match self.reconcile {
+            Some(_) => None,
+            None => Some(ReconcileView::load().await),
+        };
+    }
+    /// Potvrdí formulár výpisu, alebo odsúhlasí označené záznamy a zatvorí obrazovku.
+    pub async fn reconcile_confirm(&mut self) {
+        let Some(view) = &mut self.reconcile else {
+            return;
+        };
+        if view.reconciliation.is_none() {
+            view.start();
+            return;
+        }
+        let Some(ticked) = view.ticked() else {
+            return;
+        };
+        let description = MESSAGES.format("action.reconcile", &[&ticked.len()]);
+        match self
+            .record_lister
+            .set_status(&ticked, RecordStatus::Reconciled, description.clone())
+            .await
+        {
+            Result::Ok(()) => {
+                self.reconcile = None;
+                self.notice = Some(description);
+            }
+            Err(e) => {
+                if let Some(view) = &mut self.reconcile {
+                    view.error = Some(e.to_string());
+                }
+            }
+        }
+    }
+    /// Otvorí históriu vybraného záznamu z audit logu alebo ju zatvorí.
+    pub async fn toggle_audit(&mut self) {
+        if self.audit.is_some() {
+            self.audit = None;
+        } else if let Some(record) = self.selected_record() {
+            self.audit = Some(AuditView::load(record.id).await);
+        }
+    }
+    /// Otvorí kôš s čerstvými dátami alebo ho zatvorí.
+    pub async fn toggle_trash(&mut self) {
+        self.trash = match self.trash {
+            Some(_) => None,
+            None => Some(TrashView::load().await),
+        };
+    }
+    /// Obnoví vybraný záznam z koša.
+    pub async fn restore_trashed(&mut self) {
+        let Some(record) = self.trash.as_ref().and_then(|t| t.selected()).cloned() else {
+            return;
+        };
+        let description = MESSAGES.format("action.restore", &[&record.id]);
+        self.notice = match self.record_lister.restore_record(record).await {
+            Result::Ok(()) => Some(description),
+            Err(e) => Some(e.to_string()),
+        };
+        self.reload_trash().await;
+    }
+    /// Natrvalo vymaže vybraný záznam z koša.
+    pub async fn purge_trashed(&mut self) {
+        let Some(record_id) = self.trash.as_ref().and_then(|t| t.selected()).map(|r| r.id) else {
+            return;
+        };
+        if self.record_lister.purge_record(record_id).await {
+            self.notice = Some(trf("notice.purged", &[&record_id]));
+        }
+        self.reload_trash().await;
+    }
+    /// Znovu načíta kôš a ponechá výber na rovnakom mieste.
+    async fn reload_trash(&mut self) {
+        let selected = self.trash.as_ref().and_then(|t| t.state.selected());
+        let mut trash = TrashView::load().await;
+        if let Some(i) = selected
+            && !trash.records.is_empty()
+        {
+            trash.state.select(Some(i.min(trash.records.len() - 1)));
+        }
+        self.trash = Some(trash);
+    }
+    /// Pridá záznamy poslané cez ovládací socket a pošle späť ich ID.
+    pub async fn push_records(&mut self, records: Vec<Record>, reply: Reply) {
+        let result = match self.record_lister.add_records(records).await {
+            Result::Ok(ids) => {
+                self.notice = Some(trf("notice.socket_added", &[&ids.len()]));
+                Result::Ok(json!({ "ids": ids }))
+            }
+            Err(e) => Err(RpcError::new(APP_ERROR, e.to_string())),
+        };
+        let _ = reply.send(result);
+    }
+    /// Pošle cez ovládací socket vybraný záznam zoznamu, bez ohľadu na fokus.
+    pub fn send_selection(&self, reply: Reply) {
+        let record = self
+            .record_lister
+            .state
+            .selected()
+            .and_then(|i| self.record_lister.record_manager.get_all().get(i).cloned());
+        let _ = reply.send(Result::Ok(json!(record)));
+    }
+    /// Pošle cez ovládací socket aktuálny dopyt a počet zobrazených záznamov.
+    pub fn send_filter(&self, reply: Reply) {
+        let _ = reply.send(Result::Ok(json!({
+            "query": self.search_input,
+            "error": self.search_error.as_ref().map(|e| e.to_string()),
+            "shown": self.record_lister.record_manager.get_all().len(),
+            "total": self.record_lister.all_records.get_all().len(),
+        })));
+    }
+    /// Vráti vybraný záznam zo zoznamu, ak je zoznam aktívny.
+    fn selected_record(&self) -> Option<Record> {
+        if self.focusing_widget != FocusedWidget::Records {
+            return None;
+        }
+        let selected = self.record_lister.state.selected()?;
+        self.record_lister.record_manager.get_all().get(selected).cloned()
+    }
+    /// Prepne stav vybraného záznamu medzi čakajúcim a zúčtovaným.
+    pub async fn cycle_status(&mut self) {
+        let Some(record) = self.selected_record() else {
+            return;
+        };
+        let status = match record.status {
+            RecordStatus::Pending => RecordStatus::Cleared,
+            RecordStatus::Cleared => RecordStatus::Pending,
+            RecordStatus::Reconciled => {
+                self.notice = Some(self.locked_notice());
+                return;
+            }
+        };
+        let description = MESSAGES.format("action.status", &[&record.id, &status.name()]);
+        if let Err(e) = self.record_lister.set_status(&[record], status, description).await {
+            self.notice = Some(e.to_string());
+        }
+    }
+    /// Odomkne vybraný odsúhlasený záznam, vráti ho medzi zúčtované.
+    pub async fn unlock_record(&mut self) {
+        let Some(record) = self.selected_record() else {
+            return;
+        };
+        if record.status.is_locked() {
+            let description = MESSAGES.format("action.unlock", &[&record.id]);
+            self.notice = match self
+                .record_lister
+                .set_status(&[record], RecordStatus::Cleared, description.clone())
+                .await
+            {
+                Result::Ok(()) => Some(description),
+                Err(e) => Some(e.to_string()),
+            };
+        }
+    }
+    /// Vráti späť poslednú zmenu záznamov.
+    pub async fn undo(&mut self) {
+        self.notice = match self.record_lister.undo().await {
+            Result::Ok(Some(description)) => Some(trf("notice.undone", &[&description])),
+            Result::Ok(None) => Some(tr("notice.nothing_to_undo").to_string()),
+            Err(e) => Some(trf("notice.cannot_undo", &[&e])),
+        };
+    }
+    /// Zopakuje naposledy vrátenú zmenu záznamov.
+    pub async fn redo(&mut self) {
+        self.notice = match self.record_lister.redo().await {
+            Result::Ok(Some(description)) => Some(trf("notice.redone", &[&description])),
+            Result::Ok(None) => Some(tr("notice.nothing_to_redo").to_string()),
+            Err(e) => Some(trf("notice.cannot_redo", &[&e])),
+        };
+    }
+    /// Otvorí vyhľadávací riadok.
+    pub fn search_enter(&mut self) {
+        self.search_mode = true;
+        self.focusing_widget = FocusedWidget::Records;
+    }
+    /// Pridá znak do dopytu a hneď prefiltruje záznamy.
+    pub async fn search_char(&mut self, c: char) {
+        self.search_input.push(c);
+        self.update_search().await;
+    }
+    /// Odstráni posledný znak dopytu a hneď prefiltruje záznamy.
+    pub async fn search_rem_char(&mut self) {
+        self.search_input.pop();
+        self.update_search().await;
+    }
+    /// Zatvorí vyhľadávací riadok a ponechá posledný platný filter.
+    pub fn search_confirm(&mut self) {
+        self.search_mode = false;
+    }
+    /// Zruší filter a zatvorí vyhľadávací riadok.
+    pub async fn search_cancel(&mut self) {
+        self.search_input.clear();
+        self.update_search().await;
+        self.search_mode = false;
+    }
+    /// Spracuje dopyt. Pri chybe ostane v platnosti predchádzajúci filter.
+    async fn update_search(&mut self) {
+        match parse_query(&self.search_input) {
+            Result::Ok(filter) => {
+                self.search_error = None;
+                self.record_lister.set_filter(filter).await;
+            }
+            Err(e) => self.search_error = Some(e),
+        }
+    }
    /// Prepne aplikáciu do režimu úpravy záznamu.
     pub fn enter_edit_mode(&mut self) {
         if self.focusing_widget != FocusedWidget::Records
-            || self.record_lister.record_manager.get_all().len() == 0
+            || self.record_lister.record_manager.get_all().is_empty()
         {
             return;
         }
@@ -171,8 +532,12 @@ impl App {
             .state
             .selected()
             .expect("No selected error in enter_edit_mode");
-        let record = self.record_lister.record_manager.get_all()[record];
-        self.input_buffer = record_to_edit_mode(&record);
+        let record = &self.record_lister.record_manager.get_all()[record];
+        if record.status.is_locked() {
+            self.notice = Some(self.locked_notice());
+            return;
+        }
+        self.input_buffer = record_to_edit_mode(record);
         self.update_mode = true;
         self.input_mode = true;
     }
@@ -182,11 +547,14 @@ impl App {
             return;
         }
 
+        if let Some(account) = &config::current().defaults.account {
+            self.input_buffer[4] = account.clone();
+        }
         self.input_mode = true;
     }
  /// Potvrdí zadanie alebo úpravu záznamu.
-    pub async fn EnterConfirm(&mut self) {
-        let success = if self.update_mode {
+    pub async fn enter_confirm(&mut self) {
+        let saved = if self.update_mode {
             let selected_index = self
                 .record_lister
                 .state
@@ -202,34 +570,81 @@ impl App {
                 .await
         };
 
-        if !success {
-            println!("Error: wrong parameters");
+        if let Err(message) = saved {
+            self.notice = Some(message);
+        } else if let Some(category) = self.suggested_category {
+            let amount = self.input_buffer[0].trim().parse().ok();
+            let time = NaiveDate::parse_from_str(self.input_buffer[3].trim(), date_format()).ok();
+            if let (Some(amount), Some(time)) = (amount, time) {
+                let text = |i: usize| {
+                    Some(self.input_buffer[i].trim().to_string()).filter(|t| !t.is_empty())
+                };
+                let feedback = Feedback {
+                    amount,
+                    time,
+                    account: text(4),
+                    note: text(6),
+                    category,
+                    accepted: self.input_buffer[2].trim() == category.code(),
+                };
+                self.record_lister.record_feedback(feedback).await;
+            }
         }
 
-        self.EscReset();
+        self.esc_reset();
     }
 /// Resetuje vstupný režim.
-    pub fn EscReset(&mut self) {
+    pub fn esc_reset(&mut self) {
         self.input_buffer.iter_mut().for_each(|i| i.clear());
         self.input_select = 0;
         self.input_mode = false;
         self.update_mode = false;
+        self.suggested_category = None;
     }
 /// Posunie kurzor na ďalšie vstupné pole.
     pub fn tab_input(&mut self) {
-        if self.input_select == 3 {
+        if self.input_select == self.input_buffer.len() - 1 {
             self.input_select = 0;
         } else {
             self.input_select += 1;
         }
+        self.prefill_category();
     }
  /// Posunie kurzor na predchádzajúce vstupné pole.
-    pub fn BackTabInput(&mut self) {
+    pub fn back_tab_input(&mut self) {
         if self.input_select == 0 {
-            self.input_select = 3;
+            self.input_select = self.input_buffer.len() - 1;
         } else {
             self.input_select -= 1;
         }
+        self.prefill_category();
+    }
+    /// Predvyplní kategóriu výdavku návrhom z histórie.
+    ///
+    /// Návrh sa vytvorí iba pri pridávaní nového výdavku so zadanou sumou,
+    /// keď je pole kategórie prázdne alebo v ňom ostal predchádzajúci
+    /// návrh. Pri každom presune kurzora sa tak návrh spresní podľa účtu
+    /// a poznámky. Ak dátum ešte nie je vyplnený, použije sa dnešný deň.
+    pub fn prefill_category(&mut self) {
+        let category = self.input_buffer[2].trim();
+        let untouched = category.is_empty()
+            || self.suggested_category.is_some_and(|c| c.code() == category);
+        if self.update_mode || !untouched || self.input_buffer[1].trim() != "-" {
+            return;
+        }
+        let Some(amount) = self.input_buffer[0].trim().parse::<f32>().ok() else {
+            return;
+        };
+        let time = NaiveDate::parse_from_str(self.input_buffer[3].trim(), date_format())
+            .unwrap_or_else(|_| Local::now().date_naive());
+        let mut draft = Record::new(0, MoneyType::EXPENSE, amount, None, time);
+        draft.account = Some(self.input_buffer[4].trim().to_string()).filter(|a| !a.is_empty());
+        draft.note = Some(self.input_buffer[6].trim().to_string()).filter(|n| !n.is_empty());
+
+        if let Some(category) = self.record_lister.suggester.suggest(&draft) {
+            self.input_buffer[2] = category.code().to_string();
+            self.suggested_category = Some(category);
+        }
     }
  /// Odstráni posledný znak zo vstupu.
     pub fn rem_char(&mut self) {
@@ -257,7 +672,6 @@ impl App {
     }
 
     /// Posunie výber záznamov nahor.
-  
     pub fn record_check_increment(&mut self) {
         if self.focusing_widget != FocusedWidget::Records {
             return;
@@ -283,8 +697,12 @@ impl App {
             None => return,
         };
 
-        let selected = self.record_lister.record_manager.get_all()[selected];
-        self.record_lister.remove_record(selected).await;
+        let selected = self.record_lister.record_manager.get_all()[selected].clone();
+        if selected.status.is_locked() {
+            self.notice = Some(self.locked_notice());
+        } else if let Err(e) = self.record_lister.remove_record(selected).await {
+            self.notice = Some(e.to_string());
+        }
     }
 }
 /// Konvertuje záznam do formátu pre editáciu záznamu
@@ -312,7 +730,10 @@ pub fn record_to_edit_mode(record: &Record) -> Vec<String> {
         None => "NONE".to_string(),
     };
 
-    let time = record.time.format("%d.%m.%Y").to_string();
+    let time = record.time.format(date_format()).to_string();
+    let account = record.account.clone().unwrap_or_default();
+    let tags = record.tags.join(", ");
+    let note = record.note.clone().unwrap_or_default();
 
-    vec![amount, money_type, expense, time]
+    vec![amount, money_type, expense, time, account, tags, note, record.currency.clone()]
 }
@@ -0,0 +1,25 @@
+//! Modul pre obrazovku histórie záznamu z audit logu.
+//!
+//! Obsahuje štruktúru [`AuditView`], ktorá drží zmeny jedného záznamu
+//! od najnovšej spolu s autorom a časom.
+use financial_lib::{audit::AuditEntry, db::record_history};
+
+/// Dáta obrazovky histórie záznamu.
+#[derive(Debug)]
+pub struct AuditView {
+    /// ID záznamu, ktorého história sa zobrazuje.
+    pub record_id: i32,
+    /// Zmeny záznamu od najnovšej.
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditView {
+    /// Načíta históriu záznamu z audit logu.
+    pub async fn load(record_id: i32) -> Self {
+        let mut entries = tokio::task::spawn_blocking(move || record_history(record_id))
+            .await
+            .unwrap();
+        entries.reverse();
+        AuditView { record_id, entries }
+    }
+}
@@ -1,34 +1,15 @@
 //! Modul pre výpočty dát pre grafy aplikácie.
+#[allow(clippy::module_inception)]
 pub mod chart_calculator {
-    use chrono::{DateTime, naive::NaiveDate};
-    use financial_lib::record::{ExpenseType, Record};
-    use ratatui::{layout::Rect, style::Color, text::Span};
-    use std::collections::BTreeMap;
+    use chrono::{Local, Months, naive::NaiveDate};
+    use financial_lib::{
+        config::date_format,
+        record::ExpenseType,
+        report::{MonthSummary, monthly_report},
+    };
+    use ratatui::text::Span;
 
     use crate::record_list::RecordLister;
- /// Vracia farbu pre danú kategóriu výdavku.
-    ///
-    /// # Arguments
-    ///
-    /// * `category` - Referencia na typ výdavku (`ExpenseType`).
-    ///
-    /// # Returns
-    ///
-    /// Farba (`Color`) zodpovedajúca kategórii.
-    pub fn color_for_category(category: &ExpenseType) -> Color {
-        match category {
-            ExpenseType::FUN => Color::Cyan,
-            ExpenseType::HOME => Color::Green,
-            ExpenseType::CAR => Color::Red,
-            ExpenseType::SHOPPING => Color::Magenta,
-            ExpenseType::TRAVEL => Color::Yellow,
-            ExpenseType::INVESTMENT => Color::Blue,
-            ExpenseType::RESTAURANT => Color::LightRed,
-            ExpenseType::CLOTH => Color::LightMagenta,
-            ExpenseType::FREETIME => Color::LightBlue,
-            ExpenseType::OTHER => Color::Gray,
-        }
-    }
    /// Vypočíta percentuálny podiel jednotlivých kategórií pre koláčový graf.
     ///
     /// # Arguments
@@ -37,19 +18,19 @@ pub mod chart_calculator {
     ///
     /// # Returns
     ///
-    /// Vektor dvojíc (`&str`, `u64`), kde prvok je názov kategórie a druhý hodnota.
-    pub fn percentage_for_pie(record_lister: &RecordLister) -> Vec<(&str, u64)> {
+    /// Vektor dvojíc (`ExpenseType`, `u64`), kde prvok je kategória a druhý hodnota.
+    pub fn percentage_for_pie(record_lister: &RecordLister) -> Vec<(ExpenseType, u64)> {
         let mut ret = Vec::new();
 
-        let original_values = record_lister.record_manager.categories_to_hash();
-
-        for (category, value) in original_values {
-            ret.push((category.into(), value as u64));
+        for (category, value) in &record_lister.aggregates.category_totals {
+            ret.push((*category, *value as u64));
         }
 
         ret
     }
   /// Vytvorí dáta pre čiarový graf podľa času.
+    ///
+    /// Ak je zapnutá predpoveď, graf končí dneškom a pokračuje [`data_for_forecast`].
     ///
     /// # Arguments
     ///
@@ -59,30 +40,91 @@ pub mod chart_calculator {
     ///
     /// Vektor dvojíc `(timestamp, balance)` kde `timestamp` je `f64` a `balance` je kumulatívne
     pub fn data_for_time_graph(record_lister: &RecordLister) -> Vec<(f64, f64)> {
-        let mut ret = Vec::new();
-        let mut days: BTreeMap<NaiveDate, f64> = BTreeMap::new();
-        let all_records = record_lister.record_manager.get_all();
+        let today = Local::now().date_naive();
         let mut balance: f64 = 0.0;
 
-        for r in all_records {
-            match r.money_type {
-                financial_lib::record::MoneyType::INCOME => balance += r.amount as f64,
-                financial_lib::record::MoneyType::EXPENSE => balance -= r.amount as f64,
-            }
-
-            days.insert(r.time, balance);
-        }
+        record_lister
+            .aggregates
+            .daily
+            .iter()
+            .filter(|day| record_lister.forecast.is_none() || day.start <= today)
+            .map(|day| {
+                balance += day.net as f64;
+                (timestamp(day.start), balance)
+            })
+            .collect()
+    }
+    /// Vytvorí úseky prerušovanej čiary, ktorá pokračuje v grafe zostatku po dnešku.
+    ///
+    /// Predpoveď sa rozdelí na rovnako dlhé úseky a vracia sa každý druhý,
+    /// medzery medzi nimi tvoria prerušenia čiary. Pri nepárnom počte bodov
+    /// sa pridá krátky posledný úsek, aby čiara končila posledným dňom
+    /// predpovede.
+    ///
+    /// # Arguments
+    ///
+    /// * `record_lister` - Referencia na `RecordLister` s vypočítanou predpoveďou.
+    ///
+    /// # Returns
+    ///
+    /// Vektor úsekov s dvojicami `(timestamp, balance)` na ich začiatku a konci,
+    /// prázdny ak predpoveď nie je zapnutá.
+    pub fn data_for_forecast(record_lister: &RecordLister) -> Vec<[(f64, f64); 2]> {
+        let Some(forecast) = &record_lister.forecast else {
+            return vec![];
+        };
+        let step = (forecast.points.len() / 40).max(1);
+        let last = forecast.points.len().saturating_sub(1);
+        let points: Vec<(f64, f64)> = forecast
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % step == 0 || *i == last)
+            .map(|(_, p)| (timestamp(p.date), p.balance as f64))
+            .collect();
 
-        for (time, amount) in days {
-            let insert = time
-                .and_hms_opt(0, 0, 0)
-                .expect("Conversion error to NaiveDateTime")
-                .and_utc()
-                .timestamp() as f64;
-            ret.push((insert, amount));
+        let mut segments: Vec<_> = points
+            .chunks_exact(2)
+            .map(|pair| [pair[0], pair[1]])
+            .collect();
+        if let [.., before, end] = points[..]
+            && points.len() % 2 == 1
+        {
+            // úsek od polovice poslednej medzery, aby prerušenie ostalo
+            let middle = ((before.0 + end.0) / 2.0, (before.1 + end.1) / 2.0);
+            segments.push([middle, end]);
         }
+        segments
+    }
+    /// Prevedie dátum na časovú značku pre os X.
+    fn timestamp(date: NaiveDate) -> f64 {
+        date.and_hms_opt(0, 0, 0)
+            .expect("Conversion error to NaiveDateTime")
+            .and_utc()
+            .timestamp() as f64
+    }
+    /// Vytvorí mesačný prehľad pre posledných `months` mesiacov so záznamami.
+    ///
+    /// # Arguments
+    ///
+    /// * `record_lister` - Referencia na `RecordLister` s mesačnými súčtami.
+    /// * `months` - Najväčší počet zobrazených mesiacov.
+    ///
+    /// # Returns
+    ///
+    /// Vektor mesačných súhrnov od najstaršieho, prázdny ak nie sú záznamy.
+    pub fn data_for_monthly_report(record_lister: &RecordLister, months: usize) -> Vec<MonthSummary> {
+        let monthly = &record_lister.aggregates.monthly;
+        let (Some(first), Some(last)) = (monthly.first(), monthly.last()) else {
+            return vec![];
+        };
+        let from = last
+            .start
+            .checked_sub_months(Months::new(months.saturating_sub(1) as u32))
+            .unwrap_or(first.start)
+            .max(first.start);
 
-        ret
+        monthly_report(monthly, from, last.start)
     }
 /// Generuje štítky pre os X v grafe.
     ///
@@ -111,7 +153,7 @@ pub mod chart_calculator {
         if new_dates.len() <= label_count {
             return new_dates
                 .into_iter()
-                .map(|r| Span::from(r.format("%d.%m.%Y").to_string()))
+                .map(|r| Span::from(r.format(date_format()).to_string()))
                 .collect();
         }
 
@@ -122,7 +164,7 @@ pub mod chart_calculator {
 
         while counter < lenght {
             ret.push(Span::from(
-                new_dates[counter].format("%d.%m.%Y").to_string(),
+                new_dates[counter].format(date_format()).to_string(),
             ));
             counter += step.max(1);
         }
@@ -132,7 +174,7 @@ pub mod chart_calculator {
                 new_dates
                     .last()
                     .expect("Chyba posledneho dates")
-                    .format("%d.%m.%Y")
+                    .format(date_format())
                     .to_string(),
             )
         {
@@ -140,7 +182,7 @@ pub mod chart_calculator {
                 new_dates
                     .last()
                     .expect("Chyba posledneho dates")
-                    .format("%d.%m.%Y")
+                    .format(date_format())
                     .to_string(),
             ));
         }
@@ -1,13 +1,12 @@
 //! Modul pre spracovanie udalostí v aplikácii.
+use crate::rpc::Reply;
 use color_eyre::eyre::OptionExt;
+use financial_lib::{config, record::Record};
 use futures::{FutureExt, StreamExt};
 use ratatui::crossterm::event::Event as CrosstermEvent;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
-/// The frequency at which tick events are emitted.
-const TICK_FPS: f64 = 30.0;
-
 /// Representation of all possible events.
 #[derive(Clone, Debug)]
 pub enum Event {
@@ -63,9 +62,77 @@ pub enum AppEvent {
     /// End input/edit mode without saving
     EscReset,
     /// Confirm edit
-    EnterCOnfirm,
+    EnterConfirm,
     /// Confirm input
     EnterInputMode,
+    /// Open search bar
+    SearchEnter,
+    /// Add char to search query
+    SearchChar(char),
+    /// Remove char from search query
+    SearchRemChar,
+    /// Keep the current filter and close search bar
+    SearchConfirm,
+    /// Clear the filter and close search bar
+    SearchCancel,
+    /// Switch between balance chart and monthly report
+    ToggleMonthly,
+    /// Cycle month comparison: previous month, same month last year, off
+    CycleComparison,
+    /// Cycle balance forecast: 30, 90, 365 days, off
+    CycleForecast,
+    /// Open or close the net worth screen
+    ToggleNetWorth,
+    /// Open or close the loans screen
+    ToggleLoans,
+    /// Next loan
+    NextLoan,
+    /// Previous loan
+    PreviousLoan,
+    /// Open or close the investments screen
+    ToggleInvestments,
+    /// Switch the selected record between pending and cleared
+    CycleStatus,
+    /// Unlock the selected reconciled record
+    UnlockRecord,
+    /// Open or close the history of the selected record
+    ToggleAudit,
+    /// Undo the last record change
+    Undo,
+    /// Redo the last undone record change
+    Redo,
+    /// Open or close the reconciliation screen
+    ToggleReconcile,
+    /// Add char to the statement form
+    ReconcileChar(char),
+    /// Remove char from the statement form
+    ReconcileRemChar,
+    /// Next statement form field
+    ReconcileTab,
+    /// Start the reconciliation or reconcile the ticked records
+    ReconcileConfirm,
+    /// Tick or untick the selected record
+    ReconcileToggle,
+    /// Next record to reconcile
+    NextReconcile,
+    /// Previous record to reconcile
+    PreviousReconcile,
+    /// Open or close the trash screen
+    ToggleTrash,
+    /// Next record in the trash
+    NextTrash,
+    /// Previous record in the trash
+    PreviousTrash,
+    /// Restore the selected record from the trash
+    RestoreTrash,
+    /// Permanently delete the selected record from the trash
+    PurgeTrash,
+    /// Add records pushed through the control socket
+    RpcPushRecords(Vec<Record>, Reply),
+    /// Send the selected record to the control socket
+    RpcSelection(Reply),
+    /// Send the current filter to the control socket
+    RpcFilter(Reply),
     /// Quit the application.
     Quit,
 }
@@ -79,6 +146,12 @@ pub struct EventHandler {
     receiver: mpsc::UnboundedReceiver<Event>,
 }
 
+impl Default for EventHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EventHandler {
     /// Constructs a new instance of [`EventHandler`] and spawns a new thread to handle events.
     pub fn new() -> Self {
@@ -113,6 +186,12 @@ impl EventHandler {
         // reference to it
         let _ = self.sender.send(Event::App(app_event));
     }
+
+    /// Returns a sender for other tasks which turn their input into events, e.g. the control
+    /// socket.
+    pub fn sender(&self) -> mpsc::UnboundedSender<Event> {
+        self.sender.clone()
+    }
 }
 
 /// A thread that handles reading crossterm events and emitting tick events on a regular schedule.
@@ -129,9 +208,10 @@ impl EventTask {
 
     /// Runs the event thread.
     ///
-    /// This function emits tick events at a fixed rate and polls for crossterm events in between.
+    /// This function emits tick events at the configured rate (`ui.tick_rate`) and polls for
+    /// crossterm events in between.
     async fn run(self) -> color_eyre::Result<()> {
-        let tick_rate = Duration::from_secs_f64(1.0 / TICK_FPS);
+        let tick_rate = Duration::from_secs_f64(1.0 / config::current().ui.tick_rate);
         let mut reader = crossterm::event::EventStream::new();
         let mut tick = tokio::time::interval(tick_rate);
         loop {
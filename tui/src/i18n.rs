@@ -0,0 +1,413 @@
+//! Texty TUI v angličtine a slovenčine.
+//!
+//! Jazyk sa vyberá v nastaveniach, pozri [`financial_lib::i18n`].
+//! Názvy kláves, kódy kategórií a syntax vyhľadávania sa neprekladajú,
+//! používateľ ich tak píše v každom jazyku rovnako.
+use std::fmt;
+
+use financial_lib::i18n::{Catalogue, Table};
+
+/// Texty TUI.
+pub static TEXTS: Catalogue = Catalogue { en: EN, sk: SK };
+
+/// Vráti text TUI v jazyku z nastavení.
+pub fn tr(key: &str) -> &'static str {
+    TEXTS.text(key)
+}
+
+/// Vráti text TUI s doplnenými hodnotami namiesto `{}`.
+pub fn trf(key: &str, args: &[&dyn fmt::Display]) -> String {
+    TEXTS.format(key, args)
+}
+
+const EN: Table = &[
+    // formulár záznamu
+    ("form.title", "Add record"),
+    ("form.amount", "Amount"),
+    ("form.type", "Type(+/-)"),
+    ("form.category", "Category(Fn, Rest, Shop, Inv, Free, Hm, Cloth, Car, Trl, Oth)"),
+    ("form.date", "Date- {}"),
+    ("form.account", "Account"),
+    ("form.tags", "Tags (comma separated)"),
+    ("form.note", "Note"),
+    ("form.currency", "Currency (empty = account or base currency)"),
+    ("form.suggested", "{} - suggested"),
+    // hlavná obrazovka
+    ("records.title", "Records ({})"),
+    ("records.notice", "Records - {}"),
+    ("comparison.title", "{} vs {} ({})"),
+    ("comparison.last_month", "last month"),
+    ("comparison.last_year", "same month last year"),
+    ("comparison.new", "new"),
+    ("expenses.title", "Expenses"),
+    ("balance.title", "Balance over time"),
+    ("balance.forecast", "Balance over time (in {} days: {})"),
+    ("monthly.title", "Monthly "),
+    ("monthly.income", "income"),
+    ("monthly.vs", " vs "),
+    ("monthly.expense", "expense"),
+    ("monthly.net", "net"),
+    ("search.title", "Search"),
+    ("help.title", "Help"),
+    ("help.example", "e.g."),
+    ("help.categories", "Category codes:"),
+    // stĺpce tabuliek
+    ("column.id", "ID"),
+    ("column.name", "Name"),
+    ("column.kind", "Kind"),
+    ("column.value", "Value"),
+    ("column.valued_on", "Valued on"),
+    ("column.category", "Category"),
+    ("column.before", "Before"),
+    ("column.now", "Now"),
+    ("column.change", "Change"),
+    ("column.rate", "Rate"),
+    ("column.payment", "Payment"),
+    ("column.remaining", "Remaining"),
+    ("column.interest_paid", "Interest paid"),
+    ("column.payoff", "Payoff"),
+    ("column.date", "Date"),
+    ("column.principal", "Principal"),
+    ("column.interest", "Interest"),
+    ("column.balance", "Balance"),
+    ("column.time", "Time"),
+    ("column.actor", "Actor"),
+    ("column.fields", "Fields (before -> after)"),
+    ("column.amount", "Amount"),
+    ("column.account", "Account"),
+    ("column.deleted", "Deleted"),
+    ("column.purged_after", "Purged after"),
+    ("column.note", "Note"),
+    ("column.symbol", "Symbol"),
+    ("column.quantity", "Quantity"),
+    ("column.cost_basis", "Cost basis"),
+    ("column.price", "Price"),
+    ("column.priced_on", "Priced on"),
+    ("column.market_value", "Market value"),
+    ("column.unrealised", "Unrealised"),
+    ("column.realised", "Realised"),
+    ("column.status", "Status"),
+    // obrazovky
+    ("net_worth.title", "Net worth"),
+    ("net_worth.summary", "Net worth: {} (assets {}, liabilities {})"),
+    ("net_worth.table", "Assets and liabilities ({} to close)"),
+    ("net_worth.investments", "Investments (market value)"),
+    ("net_worth.asset", "Asset"),
+    ("net_worth.liability", "Liability"),
+    ("net_worth.today", "today"),
+    ("loans.title", "Loans ({} / {} select, {} to close)"),
+    ("loans.notice", "Loans - {}"),
+    ("loans.payments", "Payments: principal and interest"),
+    ("loans.never", "never"),
+    ("history.title", "History of record {} ({} to close)"),
+    ("operation.INSERT", "insert"),
+    ("operation.UPDATE", "update"),
+    ("operation.DELETE", "delete"),
+    ("trash.title", "Trash, kept for {} days ({} restore, {} purge, {} to close)"),
+    ("investments.title", "Investments ({} to close)"),
+    ("investments.total", "Total"),
+    ("reconcile.form_title", "Reconcile statement ({} next field, {} start, {} to close)"),
+    ("reconcile.end_date", "Statement end date - {}"),
+    ("reconcile.balance", "Statement balance"),
+    ("reconcile.account", "Account (empty = all)"),
+    ("reconcile.title", "Reconcile ({} tick, {} reconcile, {} to close)"),
+    (
+        "reconcile.summary",
+        "Statement {} {}: {}    Opening balance: {}    Ticked balance: {}",
+    ),
+    ("reconcile.all_accounts", "(all accounts)"),
+    ("reconcile.difference", "Difference: "),
+    ("reconcile.records", "Records up to the statement date"),
+    ("reconcile.wrong_date", "wrong statement date, expected {}"),
+    ("reconcile.wrong_balance", "wrong statement balance"),
+    ("reconcile.not_balanced", "difference {} must be zero"),
+    // upozornenia
+    ("notice.locked", "record is reconciled and locked, press {} to unlock"),
+    ("notice.socket_disabled", "control socket disabled: {}"),
+    ("notice.invalid_record", "invalid record, check the amount, type and date"),
+    ("notice.purged", "record {} permanently deleted"),
+    ("notice.undone", "undone: {}"),
+    ("notice.nothing_to_undo", "nothing to undo"),
+    ("notice.cannot_undo", "cannot undo: {}"),
+    ("notice.redone", "redone: {}"),
+    ("notice.nothing_to_redo", "nothing to redo"),
+    ("notice.cannot_redo", "cannot redo: {}"),
+    ("notice.socket_added", "{} records added from control socket"),
+    // režimy v nápovede
+    ("mode.normal", "Records"),
+    ("mode.input", "Add / edit record"),
+    ("mode.search", "Search"),
+    ("mode.help", "Help"),
+    ("mode.net_worth", "Net worth"),
+    ("mode.loans", "Loans"),
+    ("mode.investments", "Investments"),
+    ("mode.history", "Record history"),
+    ("mode.trash", "Trash"),
+    ("mode.reconcile_form", "Statement form"),
+    ("mode.reconcile", "Reconcile"),
+    // popisy príkazov v nápovede
+    ("keys.quit", "Quit"),
+    ("keys.help", "Help"),
+    ("keys.next_widget", "Next widget"),
+    ("keys.previous_widget", "Previous widget"),
+    ("keys.previous_record", "Previous record"),
+    ("keys.next_record", "Next record"),
+    ("keys.add", "Add record"),
+    ("keys.edit", "Edit record"),
+    ("keys.delete", "Move record to trash"),
+    ("keys.search", "Search records"),
+    ("keys.monthly", "Balance chart / monthly report"),
+    ("keys.comparison", "Compare month: last month / year / off"),
+    ("keys.forecast", "Forecast: 30 / 90 / 365 days / off"),
+    ("keys.status", "Mark record pending / cleared"),
+    ("keys.unlock", "Unlock reconciled record"),
+    ("keys.undo", "Undo last change (kept after restart)"),
+    ("keys.redo", "Redo last undone change"),
+    ("keys.history", "Who changed the selected record"),
+    ("keys.reconcile", "Reconcile with a statement"),
+    ("keys.trash", "Trash"),
+    ("keys.net_worth", "Net worth"),
+    ("keys.loans", "Loans"),
+    ("keys.investments", "Investments"),
+    ("keys.save", "Save record"),
+    ("keys.cancel", "Cancel"),
+    ("keys.next_field", "Next field"),
+    ("keys.previous_field", "Previous field"),
+    ("keys.delete_char", "Delete last character"),
+    ("keys.keep_filter", "Keep filter"),
+    ("keys.clear_filter", "Clear filter"),
+    ("keys.close_help", "Close help"),
+    ("keys.close", "Close"),
+    ("keys.next_loan", "Next loan"),
+    ("keys.previous_loan", "Previous loan"),
+    ("keys.restore", "Restore record"),
+    ("keys.purge", "Delete permanently"),
+    ("keys.start", "Start reconciling"),
+    ("keys.tick", "Tick / untick record"),
+    ("keys.reconcile_ticked", "Reconcile ticked records"),
+    ("keys.close_discard", "Close without changes"),
+    // chyby nastavení a ovládacieho socketu
+    ("keymap.invalid_key", "invalid key '{}'"),
+    ("keymap.unknown_mode", "unknown mode '{}'"),
+    ("keymap.unknown_command", "unknown command '{}'"),
+    ("keymap.typing", "'{}' is needed for typing, use a key with Ctrl or Alt"),
+    ("keymap.bound_twice", "'{}' is bound to both {} and {}"),
+    ("theme.error", "theme {}: {}"),
+    ("theme.no_config_dir", "configuration directory is unknown"),
+    (
+        "theme.unknown",
+        "unknown theme, expected one of {} or a theme file ({} not found)",
+    ),
+    ("theme.unknown_base", "unknown base theme '{}', expected one of {}"),
+    ("theme.unknown_color", "unknown color '{}'"),
+    ("theme.unknown_category", "unknown category '{}'"),
+    ("theme.invalid_color", "invalid color '{}'"),
+    ("rpc.not_socket", "{} exists and is not a socket"),
+    ("rpc.in_use", "another session listens on {}"),
+    ("rpc.invalid_json", "invalid JSON: {}"),
+    ("rpc.not_object", "request must be an object"),
+    ("rpc.version", "expected \"jsonrpc\": \"2.0\""),
+    ("rpc.missing_method", "missing method"),
+    ("rpc.unknown_method", "unknown method '{}'"),
+    ("rpc.closed", "application is shutting down"),
+    ("rpc.missing_records", "expected params {\"records\": [...]}"),
+    ("rpc.invalid_record", "invalid record: {}"),
+    ("rpc.no_records", "no records to push"),
+    ("rpc.record", "record {}: {}"),
+];
+
+const SK: Table = &[
+    // formulár záznamu
+    ("form.title", "Pridať záznam"),
+    ("form.amount", "Suma"),
+    ("form.type", "Typ(+/-)"),
+    ("form.category", "Kategória(Fn, Rest, Shop, Inv, Free, Hm, Cloth, Car, Trl, Oth)"),
+    ("form.date", "Dátum- {}"),
+    ("form.account", "Účet"),
+    ("form.tags", "Štítky (oddelené čiarkou)"),
+    ("form.note", "Poznámka"),
+    ("form.currency", "Mena (prázdna = mena účtu alebo základná mena)"),
+    ("form.suggested", "{} - návrh"),
+    // hlavná obrazovka
+    ("records.title", "Záznamy ({})"),
+    ("records.notice", "Záznamy - {}"),
+    ("comparison.title", "{} oproti {} ({})"),
+    ("comparison.last_month", "minulý mesiac"),
+    ("comparison.last_year", "rovnaký mesiac minulý rok"),
+    ("comparison.new", "nové"),
+    ("expenses.title", "Výdavky"),
+    ("balance.title", "Zostatok v čase"),
+    ("balance.forecast", "Zostatok v čase (o {} dní: {})"),
+    ("monthly.title", "Mesačné "),
+    ("monthly.income", "príjmy"),
+    ("monthly.vs", " oproti "),
+    ("monthly.expense", "výdavky"),
+    ("monthly.net", "úspora"),
+    ("search.title", "Hľadať"),
+    ("help.title", "Nápoveda"),
+    ("help.example", "napr."),
+    ("help.categories", "Kódy kategórií:"),
+    // stĺpce tabuliek
+    ("column.id", "ID"),
+    ("column.name", "Názov"),
+    ("column.kind", "Druh"),
+    ("column.value", "Hodnota"),
+    ("column.valued_on", "Ocenené"),
+    ("column.category", "Kategória"),
+    ("column.before", "Predtým"),
+    ("column.now", "Teraz"),
+    ("column.change", "Zmena"),
+    ("column.rate", "Úrok"),
+    ("column.payment", "Splátka"),
+    ("column.remaining", "Zostáva"),
+    ("column.interest_paid", "Zaplatený úrok"),
+    ("column.payoff", "Splatenie"),
+    ("column.date", "Dátum"),
+    ("column.principal", "Istina"),
+    ("column.interest", "Úrok"),
+    ("column.balance", "Zostatok"),
+    ("column.time", "Čas"),
+    ("column.actor", "Autor"),
+    ("column.fields", "Polia (pred -> po)"),
+    ("column.amount", "Suma"),
+    ("column.account", "Účet"),
+    ("column.deleted", "Odstránený"),
+    ("column.purged_after", "Vymaže sa po"),
+    ("column.note", "Poznámka"),
+    ("column.symbol", "Symbol"),
+    ("column.quantity", "Množstvo"),
+    ("column.cost_basis", "Nákupná cena"),
+    ("column.price", "Cena"),
+    ("column.priced_on", "Cena k"),
+    ("column.market_value", "Trhová hodnota"),
+    ("column.unrealised", "Nerealizovaný"),
+    ("column.realised", "Realizovaný"),
+    ("column.status", "Stav"),
+    // obrazovky
+    ("net_worth.title", "Čistá hodnota"),
+    ("net_worth.summary", "Čistá hodnota: {} (majetok {}, záväzky {})"),
+    ("net_worth.table", "Majetok a záväzky ({} zavrie)"),
+    ("net_worth.investments", "Investície (trhová hodnota)"),
+    ("net_worth.asset", "Majetok"),
+    ("net_worth.liability", "Záväzok"),
+    ("net_worth.today", "dnes"),
+    ("loans.title", "Úvery ({} / {} výber, {} zavrie)"),
+    ("loans.notice", "Úvery - {}"),
+    ("loans.payments", "Splátky: istina a úrok"),
+    ("loans.never", "nikdy"),
+    ("history.title", "História záznamu {} ({} zavrie)"),
+    ("operation.INSERT", "pridanie"),
+    ("operation.UPDATE", "úprava"),
+    ("operation.DELETE", "odstránenie"),
+    ("trash.title", "Kôš, záznamy ostávajú {} dní ({} obnoví, {} vymaže, {} zavrie)"),
+    ("investments.title", "Investície ({} zavrie)"),
+    ("investments.total", "Spolu"),
+    ("reconcile.form_title", "Odsúhlasenie výpisu ({} ďalšie pole, {} začne, {} zavrie)"),
+    ("reconcile.end_date", "Dátum konca výpisu - {}"),
+    ("reconcile.balance", "Zostatok na výpise"),
+    ("reconcile.account", "Účet (prázdny = všetky)"),
+    ("reconcile.title", "Odsúhlasenie ({} označí, {} odsúhlasí, {} zavrie)"),
+    (
+        "reconcile.summary",
+        "Výpis {} {}: {}    Počiatočný zostatok: {}    Označený zostatok: {}",
+    ),
+    ("reconcile.all_accounts", "(všetky účty)"),
+    ("reconcile.difference", "Rozdiel: "),
+    ("reconcile.records", "Záznamy do dátumu výpisu"),
+    ("reconcile.wrong_date", "nesprávny dátum výpisu, očakáva sa {}"),
+    ("reconcile.wrong_balance", "nesprávny zostatok výpisu"),
+    ("reconcile.not_balanced", "rozdiel {} musí byť nulový"),
+    // upozornenia
+    ("notice.locked", "záznam je odsúhlasený a zamknutý, odomknete ho klávesou {}"),
+    ("notice.socket_disabled", "ovládací socket je vypnutý: {}"),
+    ("notice.invalid_record", "neplatný záznam, skontrolujte sumu, typ a dátum"),
+    ("notice.purged", "záznam {} je natrvalo vymazaný"),
+    ("notice.undone", "vrátené: {}"),
+    ("notice.nothing_to_undo", "nie je čo vrátiť"),
+    ("notice.cannot_undo", "nedá sa vrátiť: {}"),
+    ("notice.redone", "zopakované: {}"),
+    ("notice.nothing_to_redo", "nie je čo zopakovať"),
+    ("notice.cannot_redo", "nedá sa zopakovať: {}"),
+    ("notice.socket_added", "cez ovládací socket pridané záznamy: {}"),
+    // režimy v nápovede
+    ("mode.normal", "Záznamy"),
+    ("mode.input", "Pridanie / úprava záznamu"),
+    ("mode.search", "Hľadanie"),
+    ("mode.help", "Nápoveda"),
+    ("mode.net_worth", "Čistá hodnota"),
+    ("mode.loans", "Úvery"),
+    ("mode.investments", "Investície"),
+    ("mode.history", "História záznamu"),
+    ("mode.trash", "Kôš"),
+    ("mode.reconcile_form", "Formulár výpisu"),
+    ("mode.reconcile", "Odsúhlasenie"),
+    // popisy príkazov v nápovede
+    ("keys.quit", "Koniec"),
+    ("keys.help", "Nápoveda"),
+    ("keys.next_widget", "Ďalší widget"),
+    ("keys.previous_widget", "Predchádzajúci widget"),
+    ("keys.previous_record", "Predchádzajúci záznam"),
+    ("keys.next_record", "Ďalší záznam"),
+    ("keys.add", "Pridať záznam"),
+    ("keys.edit", "Upraviť záznam"),
+    ("keys.delete", "Presunúť záznam do koša"),
+    ("keys.search", "Hľadať záznamy"),
+    ("keys.monthly", "Graf zostatku / mesačný prehľad"),
+    ("keys.comparison", "Porovnať mesiac: minulý mesiac / rok / vyp."),
+    ("keys.forecast", "Predpoveď: 30 / 90 / 365 dní / vyp."),
+    ("keys.status", "Označiť záznam ako čakajúci / zúčtovaný"),
+    ("keys.unlock", "Odomknúť odsúhlasený záznam"),
+    ("keys.undo", "Vrátiť poslednú zmenu (aj po reštarte)"),
+    ("keys.redo", "Zopakovať vrátenú zmenu"),
+    ("keys.history", "Kto zmenil vybraný záznam"),
+    ("keys.reconcile", "Odsúhlasiť s výpisom"),
+    ("keys.trash", "Kôš"),
+    ("keys.net_worth", "Čistá hodnota"),
+    ("keys.loans", "Úvery"),
+    ("keys.investments", "Investície"),
+    ("keys.save", "Uložiť záznam"),
+    ("keys.cancel", "Zrušiť"),
+    ("keys.next_field", "Ďalšie pole"),
+    ("keys.previous_field", "Predchádzajúce pole"),
+    ("keys.delete_char", "Zmazať posledný znak"),
+    ("keys.keep_filter", "Ponechať filter"),
+    ("keys.clear_filter", "Zrušiť filter"),
+    ("keys.close_help", "Zavrieť nápovedu"),
+    ("keys.close", "Zavrieť"),
+    ("keys.next_loan", "Ďalší úver"),
+    ("keys.previous_loan", "Predchádzajúci úver"),
+    ("keys.restore", "Obnoviť záznam"),
+    ("keys.purge", "Vymazať natrvalo"),
+    ("keys.start", "Začať odsúhlasenie"),
+    ("keys.tick", "Označiť / odznačiť záznam"),
+    ("keys.reconcile_ticked", "Odsúhlasiť označené záznamy"),
+    ("keys.close_discard", "Zavrieť bez zmien"),
+    // chyby nastavení a ovládacieho socketu
+    ("keymap.invalid_key", "neplatná klávesa '{}'"),
+    ("keymap.unknown_mode", "neznámy režim '{}'"),
+    ("keymap.unknown_command", "neznámy príkaz '{}'"),
+    ("keymap.typing", "'{}' je potrebná na písanie, použite klávesu s Ctrl alebo Alt"),
+    ("keymap.bound_twice", "'{}' je priradená príkazom {} aj {}"),
+    ("theme.error", "téma {}: {}"),
+    ("theme.no_config_dir", "adresár nastavení sa nedá zistiť"),
+    (
+        "theme.unknown",
+        "neznáma téma, očakáva sa jedna z {} alebo súbor témy ({} neexistuje)",
+    ),
+    ("theme.unknown_base", "neznáma základná téma '{}', očakáva sa jedna z {}"),
+    ("theme.unknown_color", "neznáma farba '{}'"),
+    ("theme.unknown_category", "neznáma kategória '{}'"),
+    ("theme.invalid_color", "neplatná farba '{}'"),
+    ("rpc.not_socket", "{} existuje a nie je socket"),
+    ("rpc.in_use", "na {} už počúva iná relácia"),
+    ("rpc.invalid_json", "neplatný JSON: {}"),
+    ("rpc.not_object", "požiadavka musí byť objekt"),
+    ("rpc.version", "očakáva sa \"jsonrpc\": \"2.0\""),
+    ("rpc.missing_method", "chýba metóda"),
+    ("rpc.unknown_method", "neznáma metóda '{}'"),
+    ("rpc.closed", "aplikácia sa ukončuje"),
+    ("rpc.missing_records", "očakávajú sa parametre {\"records\": [...]}"),
+    ("rpc.invalid_record", "neplatný záznam: {}"),
+    ("rpc.no_records", "žiadne záznamy na pridanie"),
+    ("rpc.record", "záznam {}: {}"),
+];
@@ -0,0 +1,28 @@
+//! Modul pre obrazovku investícií.
+//!
+//! Obsahuje štruktúru [`InvestmentView`] s pozíciami vypočítanými
+//! z obchodov a cien k dnešnému dňu.
+use chrono::Local;
+use financial_lib::{
+    db::{load_prices, load_trades},
+    investment::{Holding, holdings},
+};
+
+/// Dáta obrazovky investícií.
+#[derive(Debug, Default)]
+pub struct InvestmentView {
+    /// Pozície podľa cenného papiera.
+    pub holdings: Vec<Holding>,
+}
+
+impl InvestmentView {
+    /// Načíta obchody a ceny z databázy a vypočíta dnešné pozície.
+    pub async fn load() -> Self {
+        let (trades, prices) = tokio::task::spawn_blocking(|| (load_trades(), load_prices()))
+            .await
+            .unwrap();
+        InvestmentView {
+            holdings: holdings(&trades, &prices, Local::now().date_naive()),
+        }
+    }
+}
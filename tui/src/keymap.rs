@@ -0,0 +1,503 @@
+//! Klávesové skratky TUI.
+//!
+//! Každý režim aplikácie ([`Mode`]) má vlastnú tabuľku príkazov
+//! ([`Command`]) s predvolenými klávesmi. [`Keymap`] z nich poskladá
+//! aktívne skratky, prepíše ich podľa sekcie `[keys.<režim>]` v súbore
+//! nastavení a premení stlačenú klávesu na [`AppEvent`]. Nápoveda sa
+//! generuje z aktívnych skratiek.
+//!
+//! ```toml
+//! [keys.normal]
+//! help = ["?"]
+//! next_record = ["down", "j"]
+//! previous_record = ["up", "k"]
+//! redo = ["ctrl+r"]
+//! ```
+//!
+//! Uvedený príkaz dostane iba zadané klávesy, `[]` ho vypne. Klávesa
+//! môže byť v režime priradená len jednému príkazu. V režimoch, kde sa
+//! píše text, sa nedajú priradiť obyčajné znaky.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::event::AppEvent;
+use crate::i18n::{tr, trf};
+
+/// Režim aplikácie, v ktorom platí vlastná sada skratiek.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Normal,
+    Input,
+    Search,
+    Help,
+    NetWorth,
+    Loans,
+    Investments,
+    History,
+    Trash,
+    ReconcileForm,
+    Reconcile,
+}
+
+impl Mode {
+    /// Všetky režimy v poradí nápovedy.
+    pub const ALL: [Mode; 11] = [
+        Mode::Normal,
+        Mode::Input,
+        Mode::Search,
+        Mode::Help,
+        Mode::ReconcileForm,
+        Mode::Reconcile,
+        Mode::NetWorth,
+        Mode::Loans,
+        Mode::Investments,
+        Mode::History,
+        Mode::Trash,
+    ];
+
+    /// Názov režimu v súbore nastavení.
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Normal => "normal",
+            Mode::Input => "input",
+            Mode::Search => "search",
+            Mode::Help => "help",
+            Mode::NetWorth => "net_worth",
+            Mode::Loans => "loans",
+            Mode::Investments => "investments",
+            Mode::History => "history",
+            Mode::Trash => "trash",
+            Mode::ReconcileForm => "reconcile_form",
+            Mode::Reconcile => "reconcile",
+        }
+    }
+
+    /// Nadpis režimu v nápovede.
+    pub fn title(self) -> &'static str {
+        tr(&format!("mode.{}", self.name()))
+    }
+
+    /// V režime sa píše text, obyčajné znaky preto nemôžu byť skratkami.
+    pub fn is_text(self) -> bool {
+        matches!(self, Mode::Input | Mode::Search | Mode::ReconcileForm)
+    }
+
+    /// Vráti udalosť pre napísaný znak, ak sa v režime píše text.
+    fn text_event(self, c: char) -> Option<AppEvent> {
+        match self {
+            Mode::Input => Some(AppEvent::Addchar(c)),
+            Mode::Search => Some(AppEvent::SearchChar(c)),
+            Mode::ReconcileForm => Some(AppEvent::ReconcileChar(c)),
+            _ => None,
+        }
+    }
+
+    /// Príkazy režimu s predvolenými klávesmi.
+    pub fn commands(self) -> &'static [Command] {
+        match self {
+            Mode::Normal => NORMAL,
+            Mode::Input => INPUT,
+            Mode::Search => SEARCH,
+            Mode::Help => HELP,
+            Mode::NetWorth => NET_WORTH,
+            Mode::Loans => LOANS,
+            Mode::Investments => INVESTMENTS,
+            Mode::History => HISTORY,
+            Mode::Trash => TRASH,
+            Mode::ReconcileForm => RECONCILE_FORM,
+            Mode::Reconcile => RECONCILE,
+        }
+    }
+}
+
+/// Príkaz, ktorý sa dá priradiť klávesom.
+#[derive(Debug)]
+pub struct Command {
+    /// názov v súbore nastavení
+    pub name: &'static str,
+    /// udalosť, ktorú príkaz pošle
+    pub event: AppEvent,
+    /// predvolené klávesy
+    pub keys: &'static [&'static str],
+    /// kľúč popisu v nápovede, pozri [`crate::i18n`]
+    pub description: &'static str,
+}
+
+const fn command(
+    name: &'static str,
+    event: AppEvent,
+    keys: &'static [&'static str],
+    description: &'static str,
+) -> Command {
+    Command {
+        name,
+        event,
+        keys,
+        description,
+    }
+}
+
+const NORMAL: &[Command] = &[
+    command("quit", AppEvent::Quit, &["q"], "keys.quit"),
+    command("help", AppEvent::HelpEnter, &["h", "?"], "keys.help"),
+    command("next_widget", AppEvent::IncrementWidget, &["tab"], "keys.next_widget"),
+    command("previous_widget", AppEvent::DecrementWidget, &["shift+tab"], "keys.previous_widget"),
+    command("previous_record", AppEvent::IncrementRecords, &["up"], "keys.previous_record"),
+    command("next_record", AppEvent::DecrementRecords, &["down"], "keys.next_record"),
+    command("add", AppEvent::EnterInputMode, &["a"], "keys.add"),
+    command("edit", AppEvent::EditRecord, &["enter"], "keys.edit"),
+    command("delete", AppEvent::RemoveRecord, &["delete"], "keys.delete"),
+    command("search", AppEvent::SearchEnter, &["/"], "keys.search"),
+    command("monthly", AppEvent::ToggleMonthly, &["m"], "keys.monthly"),
+    command("comparison", AppEvent::CycleComparison, &["c"], "keys.comparison"),
+    command("forecast", AppEvent::CycleForecast, &["f"], "keys.forecast"),
+    command("status", AppEvent::CycleStatus, &["s"], "keys.status"),
+    command("unlock", AppEvent::UnlockRecord, &["U"], "keys.unlock"),
+    command("undo", AppEvent::Undo, &["u"], "keys.undo"),
+    command("redo", AppEvent::Redo, &["ctrl+r"], "keys.redo"),
+    command("history", AppEvent::ToggleAudit, &["H"], "keys.history"),
+    command("reconcile", AppEvent::ToggleReconcile, &["r"], "keys.reconcile"),
+    command("trash", AppEvent::ToggleTrash, &["t"], "keys.trash"),
+    command("net_worth", AppEvent::ToggleNetWorth, &["n"], "keys.net_worth"),
+    command("loans", AppEvent::ToggleLoans, &["l"], "keys.loans"),
+    command("investments", AppEvent::ToggleInvestments, &["i"], "keys.investments"),
+];
+
+const INPUT: &[Command] = &[
+    command("confirm", AppEvent::EnterConfirm, &["enter"], "keys.save"),
+    command("cancel", AppEvent::EscReset, &["esc"], "keys.cancel"),
+    command("next_field", AppEvent::TabInput, &["tab"], "keys.next_field"),
+    command("previous_field", AppEvent::BackTabInput, &["shift+tab"], "keys.previous_field"),
+    command("delete_char", AppEvent::Remchar, &["backspace"], "keys.delete_char"),
+];
+
+const SEARCH: &[Command] = &[
+    command("confirm", AppEvent::SearchConfirm, &["enter"], "keys.keep_filter"),
+    command("cancel", AppEvent::SearchCancel, &["esc"], "keys.clear_filter"),
+    command("delete_char", AppEvent::SearchRemChar, &["backspace"], "keys.delete_char"),
+];
+
+const HELP: &[Command] = &[command(
+    "close",
+    AppEvent::HelpExit,
+    &["esc", "q", "h", "?"],
+    "keys.close_help",
+)];
+
+const NET_WORTH: &[Command] = &[
+    command("close", AppEvent::ToggleNetWorth, &["esc", "n"], "keys.close"),
+    command("quit", AppEvent::Quit, &["q"], "keys.quit"),
+];
+
+const LOANS: &[Command] = &[
+    command("close", AppEvent::ToggleLoans, &["esc", "l"], "keys.close"),
+    command("next", AppEvent::NextLoan, &["down"], "keys.next_loan"),
+    command("previous", AppEvent::PreviousLoan, &["up"], "keys.previous_loan"),
+    command("quit", AppEvent::Quit, &["q"], "keys.quit"),
+];
+
+const INVESTMENTS: &[Command] = &[
+    command("close", AppEvent::ToggleInvestments, &["esc", "i"], "keys.close"),
+    command("quit", AppEvent::Quit, &["q"], "keys.quit"),
+];
+
+const HISTORY: &[Command] = &[
+    command("close", AppEvent::ToggleAudit, &["esc", "H"], "keys.close"),
+    command("quit", AppEvent::Quit, &["q"], "keys.quit"),
+];
+
+const TRASH: &[Command] = &[
+    command("close", AppEvent::ToggleTrash, &["esc", "t"], "keys.close"),
+    command("next", AppEvent::NextTrash, &["down"], "keys.next_record"),
+    command("previous", AppEvent::PreviousTrash, &["up"], "keys.previous_record"),
+    command("restore", AppEvent::RestoreTrash, &["enter"], "keys.restore"),
+    command("purge", AppEvent::PurgeTrash, &["delete"], "keys.purge"),
+    command("quit", AppEvent::Quit, &["q"], "keys.quit"),
+];
+
+const RECONCILE_FORM: &[Command] = &[
+    command("start", AppEvent::ReconcileConfirm, &["enter"], "keys.start"),
+    command("next_field", AppEvent::ReconcileTab, &["tab"], "keys.next_field"),
+    command("delete_char", AppEvent::ReconcileRemChar, &["backspace"], "keys.delete_char"),
+    command("close", AppEvent::ToggleReconcile, &["esc"], "keys.close"),
+];
+
+const RECONCILE: &[Command] = &[
+    command("tick", AppEvent::ReconcileToggle, &["space"], "keys.tick"),
+    command("reconcile", AppEvent::ReconcileConfirm, &["enter"], "keys.reconcile_ticked"),
+    command("next", AppEvent::NextReconcile, &["down"], "keys.next_record"),
+    command("previous", AppEvent::PreviousReconcile, &["up"], "keys.previous_record"),
+    command("close", AppEvent::ToggleReconcile, &["esc"], "keys.close_discard"),
+    command("quit", AppEvent::Quit, &["q"], "keys.quit"),
+];
+
+/// Klávesa s modifikátormi, napr. `ctrl+r`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// Vytvorí klávesu v jednotnom tvare.
+    ///
+    /// Shift sa pri znakoch ani pri Shift+Tab neukladá, je už v samotnom
+    /// znaku (`U`) alebo kóde klávesy.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            code => code,
+        };
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Key { code, modifiers }
+    }
+
+    /// Obyčajný znak bez Ctrl a Alt, ktorý sa v textových režimoch píše.
+    fn is_text(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && self.modifiers.is_empty()
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Key::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    /// Načíta klávesu ako `q`, `U`, `enter`, `shift+tab`, `ctrl+r` alebo `f5`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || trf("keymap.invalid_key", &[&text]);
+        let (prefix, name) = match text.strip_suffix("++") {
+            Some(prefix) => (prefix, "+"),
+            None => text.rsplit_once('+').unwrap_or(("", text)),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in prefix.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid()),
+            };
+        }
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match name.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "space" => KeyCode::Char(' '),
+                f => match f.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+        Ok(Key::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => {
+                let name = match code {
+                    KeyCode::Enter => "Enter",
+                    KeyCode::Esc => "Esc",
+                    KeyCode::Tab => "Tab",
+                    KeyCode::Backspace => "Backspace",
+                    KeyCode::Delete => "Delete",
+                    KeyCode::Insert => "Insert",
+                    KeyCode::Up => "Up",
+                    KeyCode::Down => "Down",
+                    KeyCode::Left => "Left",
+                    KeyCode::Right => "Right",
+                    KeyCode::Home => "Home",
+                    KeyCode::End => "End",
+                    KeyCode::PageUp => "PageUp",
+                    KeyCode::PageDown => "PageDown",
+                    _ => "?",
+                };
+                write!(f, "{}", name)
+            }
+        }
+    }
+}
+
+/// Chybná sekcia `[keys]` v súbore nastavení.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeymapError {
+    /// sekcia, v ktorej je chyba, napr. `keys.normal`
+    pub section: String,
+    pub message: String,
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.section, self.message)
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+/// Aktívne klávesové skratky všetkých režimov.
+#[derive(Debug)]
+pub struct Keymap {
+    /// klávesy každého príkazu v poradí tabuľky režimu
+    bindings: HashMap<Mode, Vec<Vec<Key>>>,
+    /// príkaz podľa režimu a klávesy
+    lookup: HashMap<(Mode, Key), &'static Command>,
+}
+
+impl Default for Keymap {
+    /// Predvolené skratky.
+    fn default() -> Self {
+        Keymap::new(&BTreeMap::new()).expect("default key bindings have no conflicts")
+    }
+}
+
+impl Keymap {
+    /// Poskladá skratky z predvolených kláves a nastavení.
+    ///
+    /// # Arguments
+    /// * `overrides` – režim → príkaz → klávesy, sekcia `[keys]` súboru nastavení
+    ///
+    /// # Errors
+    /// Ak nastavenia obsahujú neznámy režim, príkaz alebo klávesu, ak je
+    /// klávesa v režime priradená dvom príkazom, alebo ak by skratka
+    /// v textovom režime zabránila písaniu znaku.
+    pub fn new(
+        overrides: &BTreeMap<String, BTreeMap<String, Vec<String>>>,
+    ) -> Result<Keymap, KeymapError> {
+        if let Some(name) = overrides
+            .keys()
+            .find(|name| !Mode::ALL.iter().any(|m| m.name() == name.as_str()))
+        {
+            return Err(KeymapError {
+                section: "keys".to_string(),
+                message: trf("keymap.unknown_mode", &[name]),
+            });
+        }
+        let mut keymap = Keymap {
+            bindings: HashMap::new(),
+            lookup: HashMap::new(),
+        };
+        for mode in Mode::ALL {
+            let section = format!("keys.{}", mode.name());
+            let error = |message: String| KeymapError {
+                section: section.clone(),
+                message,
+            };
+            let overrides = overrides.get(mode.name());
+            if let Some(name) = overrides
+                .into_iter()
+                .flat_map(|o| o.keys())
+                .find(|name| !mode.commands().iter().any(|c| c.name == name.as_str()))
+            {
+                return Err(error(trf("keymap.unknown_command", &[name])));
+            }
+            let mut bindings = Vec::new();
+            for command in mode.commands() {
+                let keys: Vec<Key> = match overrides.and_then(|o| o.get(command.name)) {
+                    Some(keys) => keys.iter().map(|k| k.parse()).collect::<Result<_, _>>(),
+                    None => command.keys.iter().map(|k| k.parse()).collect(),
+                }
+                .map_err(|e| error(format!("{}: {}", command.name, e)))?;
+                for key in &keys {
+                    if mode.is_text() && key.is_text() {
+                        let message = trf("keymap.typing", &[key]);
+                        return Err(error(format!("{}: {}", command.name, message)));
+                    }
+                    if let Some(other) = keymap.lookup.insert((mode, *key), command)
+                        && other.name != command.name
+                    {
+                        return Err(error(trf(
+                            "keymap.bound_twice",
+                            &[key, &other.name, &command.name],
+                        )));
+                    }
+                }
+                bindings.push(keys);
+            }
+            keymap.bindings.insert(mode, bindings);
+        }
+        Ok(keymap)
+    }
+
+    /// Vráti udalosť pre stlačenú klávesu v danom režime.
+    ///
+    /// V textových režimoch sa nepriradený znak pošle ako písaný text.
+    pub fn event(&self, mode: Mode, key_event: KeyEvent) -> Option<AppEvent> {
+        let key = Key::from(key_event);
+        match (self.lookup.get(&(mode, key)), key.code) {
+            (Some(command), _) => Some(command.event.clone()),
+            (None, KeyCode::Char(c)) if key.is_text() => mode.text_event(c),
+            _ => None,
+        }
+    }
+
+    /// Vráti klávesy príkazu na zobrazenie, napr. `Esc / t`, alebo `-`, ak nemá žiadne.
+    pub fn keys(&self, mode: Mode, name: &str) -> String {
+        mode.commands()
+            .iter()
+            .zip(&self.bindings[&mode])
+            .find(|(c, keys)| c.name == name && !keys.is_empty())
+            .map_or("-".to_string(), |(_, keys)| join(keys))
+    }
+
+    /// Vráti nápovedu režimu: klávesy a popis každého príkazu, ktorý nejaké klávesy má.
+    pub fn help(&self, mode: Mode) -> Vec<(String, &'static str)> {
+        mode.commands()
+            .iter()
+            .zip(&self.bindings[&mode])
+            .filter(|(_, keys)| !keys.is_empty())
+            .map(|(c, keys)| (join(keys), tr(c.description)))
+            .collect()
+    }
+}
+
+fn join(keys: &[Key]) -> String {
+    keys.iter()
+        .map(|k| k.to_string())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
@@ -0,0 +1,20 @@
+//! Terminálové rozhranie nad knižnicou `financial_lib`.
+//!
+//! Binárka `tui` načíta nastavenia a spustí [`app::App`]. Moduly sú
+//! v knižnici, aby sa dali testovať aj bez terminálu, napr. protokol
+//! ovládacieho socketu ([`rpc`]).
+pub mod app;
+pub mod audit;
+pub mod chart_calculator;
+pub mod event;
+pub mod i18n;
+pub mod investments;
+pub mod keymap;
+pub mod loans;
+pub mod net_worth;
+pub mod reconcile;
+pub mod record_list;
+pub mod rpc;
+pub mod theme;
+pub mod trash;
+pub mod ui;
@@ -0,0 +1,76 @@
+//! Modul pre obrazovku úverov.
+//!
+//! Obsahuje štruktúru [`LoanView`], ktorá drží úvery, ich stav podľa
+//! skutočných splátok a výber úveru, ktorého splátky sa zobrazujú.
+use financial_lib::{
+    currency::MissingRate,
+    db::{load_converter, load_loans, load_records},
+    loan::{Loan, LoanStatus},
+};
+use ratatui::widgets::TableState;
+
+/// Dáta obrazovky úverov.
+#[derive(Debug, Default)]
+pub struct LoanView {
+    /// Úvery spolu s ich stavom.
+    pub loans: Vec<(Loan, LoanStatus)>,
+    /// Vybraný úver v tabuľke.
+    pub state: TableState,
+    /// Chýbajúci kurz, pre ktorý sa splátky nedali prepočítať do základnej meny.
+    pub conversion_error: Option<MissingRate>,
+}
+
+impl LoanView {
+    /// Načíta úvery a záznamy z databázy a vypočíta stav každého úveru.
+    ///
+    /// Splátky sa prepočítajú do základnej meny. Ak kurz chýba, použijú
+    /// sa pôvodné sumy a chyba sa uloží do `conversion_error`.
+    pub async fn load() -> Self {
+        let (loans, records, converter) =
+            tokio::task::spawn_blocking(|| (load_loans(), load_records(), load_converter()))
+                .await
+                .unwrap();
+        let all = records.get_all();
+        let mut conversion_error = None;
+        let loans: Vec<(Loan, LoanStatus)> = loans
+            .into_iter()
+            .map(|loan| {
+                let status = loan.converted_status(&all, &converter).unwrap_or_else(|e| {
+                    conversion_error.get_or_insert(e);
+                    loan.status(&all)
+                });
+                (loan, status)
+            })
+            .collect();
+        let mut state = TableState::default();
+        if !loans.is_empty() {
+            state.select(Some(0));
+        }
+        LoanView {
+            loans,
+            state,
+            conversion_error,
+        }
+    }
+    /// Vráti vybraný úver a jeho stav.
+    pub fn selected(&self) -> Option<&(Loan, LoanStatus)> {
+        self.state.selected().and_then(|i| self.loans.get(i))
+    }
+    /// Posunie výber na ďalší úver.
+    pub fn select_next(&mut self) {
+        if self.loans.is_empty() {
+            return;
+        }
+        let next = self.state.selected().map_or(0, |i| (i + 1) % self.loans.len());
+        self.state.select(Some(next));
+    }
+    /// Posunie výber na predchádzajúci úver.
+    pub fn select_previous(&mut self) {
+        if self.loans.is_empty() {
+            return;
+        }
+        let len = self.loans.len();
+        let previous = self.state.selected().map_or(0, |i| (i + len - 1) % len);
+        self.state.select(Some(previous));
+    }
+}
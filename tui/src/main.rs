@@ -1,16 +1,84 @@
-use crate::app::App;
+use std::path::PathBuf;
 
-pub mod app;
-pub mod chart_calculator;
-pub mod event;
-pub mod record_list;
-pub mod ui;
+use clap::Parser;
+use financial_lib::config::{self, Config, Flags, MAX_TICK_RATE, MIN_TICK_RATE};
+use financial_lib::i18n::{MESSAGES, fill};
+use tui::app::App;
+use tui::keymap::Keymap;
+use tui::theme::{Theme, themes_dir};
+
+/// Personal finance in the terminal.
+///
+/// Settings come from the configuration file (see `financial_lib::config`),
+/// environment variables and these flags, in increasing priority.
+#[derive(Debug, Parser)]
+#[command(name = "tui", version, about)]
+struct Args {
+    /// Configuration file instead of the default one
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Database file, overrides the configuration and DATABASE_URL
+    #[arg(long, value_name = "FILE")]
+    db: Option<PathBuf>,
+    /// Account of new records, overrides the configuration and FINANCE_ACCOUNT
+    #[arg(long, value_name = "NAME")]
+    default_account: Option<String>,
+    /// Currency of totals, overrides the configuration and BASE_CURRENCY
+    #[arg(long, value_name = "CODE")]
+    base_currency: Option<String>,
+    /// Date format such as %d.%m.%Y, overrides the configuration and FINANCE_DATE_FORMAT
+    #[arg(long, value_name = "FORMAT")]
+    date_format: Option<String>,
+    /// Language of messages (en or sk), overrides the configuration and FINANCE_LANGUAGE
+    #[arg(long, value_name = "CODE")]
+    language: Option<String>,
+    /// Frames per second
+    #[arg(long)]
+    tick_rate: Option<f64>,
+    /// Colour theme: dark, light, high-contrast, or a theme name or file
+    #[arg(long)]
+    theme: Option<String>,
+}
 
 #[tokio::main]
 async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
+    let args = Args::parse();
+    let mut settings = Config::load(args.config.as_deref())?;
+    settings.apply_flags(&Flags {
+        database: args.db,
+        account: args.default_account,
+        currency: args.base_currency,
+        date_format: args.date_format,
+        language: args.language,
+    })?;
+    if let Some(rate) = args.tick_rate {
+        color_eyre::eyre::ensure!(
+            (MIN_TICK_RATE..=MAX_TICK_RATE).contains(&rate),
+            "--tick-rate: {}",
+            // nastavenia ešte nie sú nainštalované, `trf` by ich načítal znova
+            fill(
+                MESSAGES.text_in(settings.defaults.language, "config.invalid_tick_rate"),
+                &[&rate, &MIN_TICK_RATE, &MAX_TICK_RATE]
+            )
+        );
+        settings.ui.tick_rate = rate;
+    }
+    if let Some(theme) = args.theme {
+        settings.ui.theme = theme;
+    }
+    let legacy = settings.legacy_database();
+    // chyby skratiek a témy sa vypíšu v jazyku z nastavení
+    config::install(settings);
+    let settings = config::current();
+    let keymap = Keymap::new(&settings.keys)?;
+    let theme = Theme::load(&settings.ui.theme, themes_dir(args.config.as_deref()).as_deref())?;
+    if let Some(legacy) = legacy {
+        let warning = MESSAGES.format("config.legacy_database", &[&legacy.display()]);
+        eprintln!("{}", MESSAGES.format("message.warning", &[&warning]));
+    }
     let terminal = ratatui::init();
-    let result = App::new().run(terminal).await;
+    let result = App::new(keymap, theme).run(terminal).await;
     ratatui::restore();
     result
 }
@@ -0,0 +1,47 @@
+//! Modul pre obrazovku čistej hodnoty majetku.
+//!
+//! Obsahuje štruktúru [`NetWorthView`], ktorá drží majetok, záväzky,
+//! ich ocenenia, hodnotu investícií a vypočítaný vývoj čistej hodnoty.
+use chrono::Local;
+use financial_lib::{
+    db::{load_assets, load_prices, load_trades, load_valuations},
+    investment::portfolio_value,
+    networth::{Asset, NetWorthPoint, Valuation, net_worth_with_investments, value_on},
+};
+
+/// Dáta obrazovky čistej hodnoty.
+#[derive(Debug, Default)]
+pub struct NetWorthView {
+    /// Majetok a záväzky.
+    pub assets: Vec<Asset>,
+    /// Všetky ocenenia.
+    pub valuations: Vec<Valuation>,
+    /// Dnešná trhová hodnota investícií.
+    pub investments: f32,
+    /// Čistá hodnota ku každej zmene ocenenia, obchodu alebo ceny.
+    pub history: Vec<NetWorthPoint>,
+}
+
+impl NetWorthView {
+    /// Načíta majetok, ocenenia a investície z databázy a vypočíta vývoj čistej hodnoty.
+    pub async fn load() -> Self {
+        let (assets, valuations, trades, prices) = tokio::task::spawn_blocking(|| {
+            (load_assets(), load_valuations(), load_trades(), load_prices())
+        })
+        .await
+        .unwrap();
+        let history = net_worth_with_investments(&assets, &valuations, &trades, &prices);
+        let investments = portfolio_value(&trades, &prices, Local::now().date_naive());
+        NetWorthView {
+            assets,
+            valuations,
+            investments,
+            history,
+        }
+    }
+    /// Vráti posledné ocenenie položky k dnešnému dňu.
+    pub fn current_value(&self, asset: &Asset) -> Option<Valuation> {
+        let id = asset.id?;
+        value_on(&self.valuations, id, Local::now().date_naive())
+    }
+}
@@ -0,0 +1,148 @@
+//! Modul pre obrazovku odsúhlasenia s výpisom z účtu.
+//!
+//! Obsahuje štruktúru [`ReconcileView`]. Najprv sa zadá dátum a zostatok
+//! výpisu (a voliteľne účet, ktorého mena je menou výpisu), potom sa v tabuľke označujú záznamy, ktoré
+//! sú na výpise, a priebežne sa zobrazuje rozdiel. Keď je rozdiel nulový,
+//! označené záznamy sa odsúhlasia a zamknú.
+use chrono::NaiveDate;
+use financial_lib::{
+    config::{date_format, date_hint},
+    currency::{Account, base_currency},
+    db::{load_accounts, load_records},
+    reconcile::Reconciliation,
+    record::Record,
+};
+use ratatui::widgets::TableState;
+
+use crate::i18n::{tr, trf};
+
+/// Dáta obrazovky odsúhlasenia.
+#[derive(Debug, Default)]
+pub struct ReconcileView {
+    /// Všetky záznamy z databázy.
+    pub records: Vec<Record>,
+    /// Účty a ich meny, mena účtu je menou výpisu.
+    pub accounts: Vec<Account>,
+    /// Vstupné polia `[dátum výpisu, zostatok, účet]`.
+    pub inputs: Vec<String>,
+    /// Aktívne vstupné pole.
+    pub input_select: usize,
+    /// Rozpracované odsúhlasenie, `None` kým sa nezadá výpis.
+    pub reconciliation: Option<Reconciliation>,
+    /// Vybraný záznam v tabuľke.
+    pub state: TableState,
+    /// Chyba posledného kroku, ktorá sa zobrazí používateľovi.
+    pub error: Option<String>,
+}
+
+impl ReconcileView {
+    /// Načíta záznamy z databázy a otvorí formulár výpisu.
+    pub async fn load() -> Self {
+        let (records, accounts) = tokio::task::spawn_blocking(|| (load_records(), load_accounts()))
+            .await
+            .unwrap();
+        ReconcileView {
+            records: records.get_all(),
+            accounts,
+            inputs: vec![String::new(); 3],
+            ..ReconcileView::default()
+        }
+    }
+    /// Vráti záznamy, ktoré sa dajú označiť.
+    pub fn candidates(&self) -> Vec<&Record> {
+        match &self.reconciliation {
+            Some(r) => r.candidates(&self.records),
+            None => Vec::new(),
+        }
+    }
+    /// Pridá znak do aktívneho vstupného poľa.
+    pub fn char_add(&mut self, c: char) {
+        self.inputs[self.input_select].push(c);
+    }
+    /// Odstráni posledný znak z aktívneho vstupného poľa.
+    pub fn rem_char(&mut self) {
+        self.inputs[self.input_select].pop();
+    }
+    /// Posunie kurzor na ďalšie vstupné pole.
+    pub fn tab_input(&mut self) {
+        self.input_select = (self.input_select + 1) % self.inputs.len();
+    }
+    /// Spracuje formulár výpisu a začne odsúhlasenie.
+    ///
+    /// Zúčtované záznamy sa hneď označia.
+    pub fn start(&mut self) {
+        let Ok(end) = NaiveDate::parse_from_str(self.inputs[0].trim(), date_format()) else {
+            self.error = Some(trf("reconcile.wrong_date", &[&date_hint(date_format())]));
+            return;
+        };
+        let Ok(balance) = self.inputs[1].trim().parse::<f32>() else {
+            self.error = Some(tr("reconcile.wrong_balance").to_string());
+            return;
+        };
+        let account = self.inputs[2].trim();
+        let currency = self
+            .accounts
+            .iter()
+            .find(|a| a.name == account)
+            .map_or_else(base_currency, |a| a.currency.clone());
+        let mut reconciliation = Reconciliation::new(end, balance, currency);
+        if !account.is_empty() {
+            reconciliation = reconciliation.with_account(account);
+        }
+        reconciliation.select_cleared(&self.records);
+        self.error = None;
+        self.reconciliation = Some(reconciliation);
+        let has_candidates = !self.candidates().is_empty();
+        self.state.select(has_candidates.then_some(0));
+    }
+    /// Prepne označenie vybraného záznamu.
+    pub fn toggle_selected(&mut self) {
+        let Some(id) = self
+            .state
+            .selected()
+            .and_then(|i| self.candidates().get(i).map(|r| r.id))
+        else {
+            return;
+        };
+        if let Some(r) = &mut self.reconciliation {
+            r.toggle(id);
+        }
+    }
+    /// Posunie výber na ďalší záznam.
+    pub fn select_next(&mut self) {
+        let len = self.candidates().len();
+        if len == 0 {
+            return;
+        }
+        let next = self.state.selected().map_or(0, |i| (i + 1) % len);
+        self.state.select(Some(next));
+    }
+    /// Posunie výber na predchádzajúci záznam.
+    pub fn select_previous(&mut self) {
+        let len = self.candidates().len();
+        if len == 0 {
+            return;
+        }
+        let previous = self.state.selected().map_or(0, |i| (i + len - 1) % len);
+        self.state.select(Some(previous));
+    }
+    /// Vráti označené záznamy, ak sedí zostatok s výpisom.
+    ///
+    /// Inak nastaví chybu s rozdielom a vráti `None`.
+    pub fn ticked(&mut self) -> Option<Vec<Record>> {
+        let reconciliation = self.reconciliation.as_ref()?;
+        if !reconciliation.is_balanced(&self.records) {
+            let difference = format!("{:.2}", reconciliation.difference(&self.records));
+            self.error = Some(trf("reconcile.not_balanced", &[&difference]));
+            return None;
+        }
+        Some(
+            reconciliation
+                .candidates(&self.records)
+                .into_iter()
+                .filter(|r| reconciliation.selected.contains(&r.id))
+                .cloned()
+                .collect(),
+        )
+    }
+}
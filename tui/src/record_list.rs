@@ -1,175 +1,532 @@
 //! Modul pre správu a výber záznamov (`Record`) v aplikácii.
 //!
 //! Obsahuje štruktúru [`RecordLister`], ktorá uchováva [`RecordManager`] a stav výberu (`ListState`),
-//! a metódy na prechádzanie, pridávanie, aktualizovanie a mazanie záznamov.
-use chrono::NaiveDate;
+//! a metódy na prechádzanie, filtrovanie, pridávanie, aktualizovanie a mazanie záznamov.
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+
+use chrono::{Local, NaiveDate};
 
 use financial_lib::{
+    aggregate::{Bucket, Period},
+    config::{self, date_format},
+    currency::{Converter, MissingRate},
     db::{
-        delete_record, get_next_id, insert_record, load_records, renumber_records_db, update_record,
+        aggregate, category_totals, commit, get_next_id, insert_feedback, load_accounts,
+        load_converter, load_feedback, load_records, purge_expired_trash, purge_record,
+        query_records, redo, undo,
     },
+    history::{Action, Change, HistoryError},
+    i18n::MESSAGES,
+    query::{RecordFilter, SortKey, SortOrder},
+    reconcile::RecordStatus,
     record::{ExpenseType, MoneyType, Record, RecordManager},
+    forecast::Forecast,
+    report::{CategoryChange, Comparison, compare_categories},
+    suggest::{CategorySuggester, Feedback},
+    transfer::check_record,
+    trash::retention_days,
 };
 use ratatui::widgets::ListState;
 
+use crate::i18n::tr;
+
 /// Štruktúra na správu zoznamu záznamov s výberom.
 #[derive(Debug)]
 pub struct RecordLister {
-    /// Správca záznamov.
+    /// Všetky záznamy z databázy.
+    pub all_records: RecordManager,
+    /// Záznamy vyhovujúce aktuálnemu filtru načítané cez SQL, ktoré sa zobrazujú v zozname a grafoch.
     pub record_manager: RecordManager,
+    /// Aktuálny filter zoznamu.
+    pub filter: RecordFilter,
     /// Stav vybraného záznamu v UI.
     pub state: ListState,
+    /// Klasifikátor navrhujúci kategórie nových výdavkov.
+    pub suggester: CategorySuggester,
+    /// Súčty pre grafy a reporty podľa aktuálneho filtra.
+    pub aggregates: Aggregates,
+    /// S akým obdobím sa porovnáva aktuálny mesiac, `None` ak sa porovnanie nezobrazuje.
+    pub comparison: Option<Comparison>,
+    /// Priebežný zostatok po každom zázname podľa ID, počítaný zo všetkých záznamov.
+    pub balances: HashMap<i32, f32>,
+    /// Dĺžka predpovede zostatku v dňoch, `None` ak sa predpoveď nezobrazuje.
+    pub forecast_days: Option<u32>,
+    /// Predpoveď zostatku zo zobrazených záznamov.
+    pub forecast: Option<Forecast>,
+    /// Prepočet súm do základnej meny.
+    pub converter: Converter,
+    /// Chýbajúci kurz, pre ktorý sa nedali sumy prepočítať do základnej meny.
+    pub conversion_error: Option<MissingRate>,
+}
+
+/// Súčty vypočítané v databáze, ktoré zobrazujú grafy a reporty.
+#[derive(Debug, Default)]
+pub struct Aggregates {
+    /// Súčty výdavkov podľa kategórií.
+    pub category_totals: BTreeMap<ExpenseType, f32>,
+    /// Denné súčty.
+    pub daily: Vec<Bucket>,
+    /// Mesačné súčty.
+    pub monthly: Vec<Bucket>,
+    /// Porovnanie aktuálneho mesiaca s iným obdobím, ak je zapnuté.
+    pub comparison: Option<ComparisonReport>,
+}
+
+/// Porovnanie výdavkov aktuálneho mesiaca s iným obdobím.
+#[derive(Debug)]
+pub struct ComparisonReport {
+    pub mode: Comparison,
+    /// porovnávané (staršie) obdobie
+    pub previous: RangeInclusive<NaiveDate>,
+    /// aktuálne obdobie
+    pub current: RangeInclusive<NaiveDate>,
+    /// zmeny podľa kategórií od najväčšieho nárastu
+    pub changes: Vec<CategoryChange>,
+}
+
+/// Prepočíta záznamy do základnej meny.
+///
+/// Ak niektorý kurz chýba, vráti pôvodné sumy spolu s chybou.
+fn in_base_currency(
+    records: &RecordManager,
+    converter: &Converter,
+) -> (RecordManager, Option<MissingRate>) {
+    match converter.convert_all(records) {
+        Ok(converted) => (converted, None),
+        Err(e) => (records.get_all().iter().cloned().collect(), Some(e)),
+    }
+}
+
+/// Poradie priebežného zostatku: podľa dátumu a v rámci dňa podľa ID.
+const LEDGER_ORDER: (SortKey, SortOrder) = (SortKey::Date, SortOrder::Ascending);
+
+/// Vráti filter, podľa ktorého sa načíta zoznam záznamov.
+///
+/// Ak si používateľ triedenie nezvolil, zoznam sa zoradí chronologicky
+/// v rovnakom poradí ako [`RecordManager::ledger`], aby priebežný
+/// zostatok v zozname rástol a klesal postupne.
+fn list_filter(filter: &RecordFilter) -> RecordFilter {
+    if filter.sort == RecordFilter::new().sort {
+        filter.clone().sort_by(LEDGER_ORDER.0, LEDGER_ORDER.1)
+    } else {
+        filter.clone()
+    }
+}
+
+/// Vypočíta priebežný zostatok v základnej mene po každom zázname v chronologickom poradí.
+fn running_balances(records: &RecordManager, converter: &Converter) -> HashMap<i32, f32> {
+    in_base_currency(records, converter)
+        .0
+        .ledger()
+        .into_iter()
+        .map(|e| (e.record.id, e.balance))
+        .collect()
+}
+
+/// Vypočíta v databáze súčty v základnej mene, ktoré zobrazujú grafy a reporty.
+fn load_aggregates(
+    filter: &RecordFilter,
+    comparison: Option<Comparison>,
+    converter: &Converter,
+) -> Result<Aggregates, MissingRate> {
+    let comparison = match comparison {
+        Some(mode) => {
+            let (current, previous) = mode.ranges(Period::Month, Local::now().date_naive());
+            let totals = |range: &RangeInclusive<NaiveDate>| {
+                category_totals(&filter.clone().between(*range.start(), *range.end()), converter)
+            };
+            Some(ComparisonReport {
+                mode,
+                changes: compare_categories(&totals(&previous)?, &totals(&current)?),
+                previous,
+                current,
+            })
+        }
+        None => None,
+    };
+    Ok(Aggregates {
+        category_totals: category_totals(filter, converter)?,
+        daily: aggregate(filter, Period::Day, None, converter)?,
+        monthly: aggregate(filter, Period::Month, None, converter)?,
+        comparison,
+    })
+}
+
+impl Default for RecordLister {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RecordLister {
     /// Vytvorí nový [`RecordLister`] a načíta záznamy z databázy.
+    ///
+    /// Najprv natrvalo vymaže záznamy, ktoré sú v koši dlhšie než lehota.
     pub fn new() -> Self {
-        RecordLister {
-            record_manager: load_records(),
+        purge_expired_trash(Local::now().date_naive(), retention_days());
+        let all_records = load_records();
+        let suggester = CategorySuggester::train(&all_records, &load_feedback());
+        let filter = RecordFilter::new();
+        let converter = load_converter();
+        let (aggregates, conversion_error) = match load_aggregates(&filter, None, &converter) {
+            Ok(a) => (a, None),
+            Err(e) => (Aggregates::default(), Some(e)),
+        };
+        let mut lister = RecordLister {
+            record_manager: query_records(&list_filter(&filter)).into_iter().collect(),
+            balances: running_balances(&all_records, &converter),
+            all_records,
+            filter,
             state: ListState::default(),
+            suggester,
+            aggregates,
+            comparison: None,
+            forecast_days: config::current().ui.forecast_days,
+            forecast: None,
+            converter,
+            conversion_error,
+        };
+        lister.update_forecast();
+        lister
+    }
+    /// Nastaví nový filter a prepočíta zobrazené záznamy aj súčty pre grafy.
+    pub async fn set_filter(&mut self, filter: RecordFilter) {
+        self.filter = filter;
+        self.apply_filter().await;
+        self.refresh_aggregates().await;
+    }
+    /// Vráti `true`, ak je zoznam zoradený chronologicky a zobrazuje priebežný zostatok.
+    ///
+    /// Pri inom triedení by zostatok medzi riadkami preskakoval, preto sa skryje.
+    pub fn shows_balance(&self) -> bool {
+        list_filter(&self.filter).sort == LEDGER_ORDER
+    }
+    /// Prepne dĺžku predpovede zostatku: 30, 90, 365 dní a vypnutá.
+    pub fn cycle_forecast(&mut self) {
+        self.forecast_days = match self.forecast_days {
+            None => Some(30),
+            Some(30) => Some(90),
+            Some(90) => Some(365),
+            Some(_) => None,
+        };
+        self.update_forecast();
+    }
+    /// Prepočíta predpoveď zostatku v základnej mene zo zobrazených záznamov.
+    fn update_forecast(&mut self) {
+        self.forecast = self.forecast_days.map(|days| {
+            in_base_currency(&self.record_manager, &self.converter)
+                .0
+                .forecast(Local::now().date_naive(), days)
+        });
+    }
+    /// Prepne porovnanie aktuálneho mesiaca: s minulým mesiacom,
+    /// s rovnakým mesiacom minulého roka a vypnuté.
+    pub async fn cycle_comparison(&mut self) {
+        self.comparison = match self.comparison {
+            None => Some(Comparison::PreviousPeriod),
+            Some(Comparison::PreviousPeriod) => Some(Comparison::PreviousYear),
+            Some(Comparison::PreviousYear) => None,
+        };
+        self.refresh_aggregates().await;
+    }
+    /// Znovu vypočíta súčty pre grafy a reporty podľa aktuálneho filtra.
+    ///
+    /// Ak chýba kurz, grafy sa vyprázdnia a chyba sa uloží do `conversion_error`.
+    async fn refresh_aggregates(&mut self) {
+        let filter = self.filter.clone();
+        let comparison = self.comparison;
+        let converter = self.converter.clone();
+        let result =
+            tokio::task::spawn_blocking(move || load_aggregates(&filter, comparison, &converter))
+                .await
+                .unwrap();
+        (self.aggregates, self.conversion_error) = match result {
+            Ok(a) => (a, None),
+            Err(e) => (Aggregates::default(), Some(e)),
+        };
+    }
+    /// Načíta z databázy záznamy podľa filtra, prepočíta predpoveď a opraví výber.
+    async fn apply_filter(&mut self) {
+        let filter = list_filter(&self.filter);
+        self.record_manager = tokio::task::spawn_blocking(move || query_records(&filter))
+            .await
+            .unwrap()
+            .into_iter()
+            .collect();
+        self.update_forecast();
+        let count = self.record_manager.get_all().len();
+        match self.state.selected() {
+            _ if count == 0 => self.state.select(None),
+            Some(i) if i >= count => self.state.select(Some(count - 1)),
+            _ => {}
         }
     }
+    /// Znovu načíta záznamy, kurzy a spätnú väzbu z databázy a pretrénuje návrhy kategórií.
+    pub async fn reload(&mut self) {
+        let (all_records, feedback, converter) =
+            tokio::task::spawn_blocking(|| (load_records(), load_feedback(), load_converter()))
+                .await
+                .unwrap();
+        self.suggester = CategorySuggester::train(&all_records, &feedback);
+        self.converter = converter;
+        self.balances = running_balances(&all_records, &self.converter);
+        self.all_records = all_records;
+        self.apply_filter().await;
+        self.refresh_aggregates().await;
+    }
+    /// Uloží spätnú väzbu k navrhnutej kategórii a pretrénuje návrhy.
+    pub async fn record_feedback(&mut self, feedback: Feedback) {
+        let feedback = tokio::task::spawn_blocking(move || {
+            insert_feedback(&feedback);
+            load_feedback()
+        })
+        .await
+        .unwrap();
+        self.suggester = CategorySuggester::train(&self.all_records, &feedback);
+    }
 /// Posunie výber na ďalší záznam.
     pub fn select_next(&mut self) {
-        if self.record_manager.get_all().len() == 0 {
+        if self.record_manager.get_all().is_empty() {
             return;
         }
 
-        let selected_now = match self.state.selected() {
-            Some(i) => i,
-            None => 0,
-        };
+        let selected_now = self.state.selected().unwrap_or_default();
 
         let lenght = self.record_manager.get_all().len();
-        let mut select_next = 0;
-        if selected_now == lenght - 1 {
-            select_next = 0;
+        let select_next = if selected_now == lenght - 1 {
+            0
         } else {
-            select_next = selected_now + 1;
-        }
+            selected_now + 1
+        };
 
         self.state.select(Some(select_next));
     }
 
     /// Posunie výber na predchádzajúci záznam.
     pub fn select_previous(&mut self) {
-        if self.record_manager.get_all().len() == 0 {
+        if self.record_manager.get_all().is_empty() {
             return;
         }
 
-        let selected_now = match self.state.selected() {
-            Some(i) => i,
-            None => 0,
-        };
+        let selected_now = self.state.selected().unwrap_or_default();
 
         let lenght = self.record_manager.get_all().len();
-        let mut select_next = 0;
-        if selected_now == 0 {
-            select_next = lenght - 1;
+        let select_next = if selected_now == 0 {
+            lenght - 1
         } else {
-            select_next = selected_now - 1;
-        }
+            selected_now - 1
+        };
 
         self.state.select(Some(select_next));
     }
  /// Pridá nový záznam alebo aktualizuje existujúci podľa `select_num`.
+    ///
+    /// Záznam sa pred uložením skontroluje cez [`check_record`] rovnako
+    /// ako v CLI, API a pri importe.
     ///
     /// # Argumenty
     ///
-    /// * `input` - Vektor obsahujúci údaje záznamu vo formáte `[amount, money_type, expense, time]`.
+    /// * `input` - Vektor obsahujúci údaje záznamu vo formáte
+    ///   `[amount, money_type, expense, time, account, tags, note, currency]`.
+    ///   Ak mena nie je zadaná, použije sa mena účtu, inak základná mena.
     /// * `select_num` - Index existujúceho záznamu. Ak je -1, vytvorí sa nový záznam.
     ///
     /// # Návratová hodnota
     ///
-    /// Vracia `true`, ak bol záznam úspešne spracovaný, inak `false`.
+    /// Vracia `Err` so správou pre používateľa, ak je zadanie neplatné
+    /// alebo ak je upravovaný záznam odsúhlasený a zamknutý.
     pub async fn add_record_from_input_or_update(
         &mut self,
         input: Vec<String>,
         select_num: i32,
-    ) -> bool {
-        let amount: f32 = match input[0].trim().parse() {
-            Ok(a) => a,
-            Err(_) => return false,
-        };
+    ) -> Result<(), String> {
+        let invalid = || tr("notice.invalid_record").to_string();
+        let amount: f32 = input[0].trim().parse().map_err(|_| invalid())?;
 
         let money_type1 = match input[1].trim() {
             "+" => MoneyType::INCOME,
             "-" => MoneyType::EXPENSE,
-            _ => return false,
+            _ => return Err(invalid()),
         };
 
         let expanse = match input[2].trim() {
-            "FUN" => Some(ExpenseType::FUN),
-            "RESTAURANT" => Some(ExpenseType::RESTAURANT),
-            "SHOPPING" => Some(ExpenseType::SHOPPING),
-            "INVESTMENT" => Some(ExpenseType::INVESTMENT),
-            "FREETIME" => Some(ExpenseType::FREETIME),
-            "HOME" => Some(ExpenseType::HOME),
-            "CLOTH" => Some(ExpenseType::CLOTH),
-            "CAR" => Some(ExpenseType::CAR),
-            "TRAVEL" => Some(ExpenseType::TRAVEL),
-            "OTHER" => Some(ExpenseType::OTHER),
-            "NONE" => None,
-            _ => None,
+            "" | "NONE" => None,
+            code => Some(code.parse::<ExpenseType>()?),
         };
 
-        let time = match NaiveDate::parse_from_str(&input[3], "%d.%m.%Y") {
-            Ok(t) => t,
-            Err(_) => return false,
+        let time = NaiveDate::parse_from_str(&input[3], date_format()).map_err(|_| invalid())?;
+
+        let account = Some(input[4].trim().to_string()).filter(|a| !a.is_empty());
+        let tags: Vec<String> = input[5]
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect();
+        let note = Some(input[6].trim().to_string()).filter(|n| !n.is_empty());
+        let currency = match input[7].trim().to_uppercase() {
+            c if !c.is_empty() => c,
+            _ => {
+                let accounts = tokio::task::spawn_blocking(load_accounts).await.unwrap();
+                accounts
+                    .into_iter()
+                    .find(|a| Some(&a.name) == account.as_ref())
+                    .map(|a| a.currency)
+                    .unwrap_or_else(|| self.converter.base.clone())
+            }
         };
 
-        if select_num != -1 {
+        let (record, action) = if select_num != -1 {
             let record_num = select_num as usize;
-            let mut change = self.record_manager.get_all()[record_num];
+            let before = self.record_manager.get_all()[record_num].clone();
+            let mut change = before.clone();
             change.amount = amount;
             change.expense = expanse;
             change.time = time;
             change.money_type = money_type1;
-            tokio::task::spawn_blocking(move || {
-                update_record(&change);
-            })
-            .await
-            .unwrap();
-
-            self.record_manager = tokio::task::spawn_blocking(|| load_records())
-                .await
-                .unwrap();
-            return true;
+            change.account = account;
+            change.tags = tags;
+            change.note = note;
+            change.currency = currency;
+            let action = Action::new(
+                MESSAGES.format("action.edit", &[&change.id]),
+                vec![Change::update(before, change.clone())],
+            );
+            (change, action)
         } else {
-            tokio::task::spawn_blocking(|| renumber_records_db())
-                .await
-                .unwrap();
-
-            let id = get_next_id();
+            let id = tokio::task::spawn_blocking(get_next_id).await.unwrap();
 
             let ret = Record {
-                id: id,
+                id,
                 money_type: money_type1,
-                amount: amount,
+                amount,
                 expense: expanse,
-                time: time,
+                time,
+                account,
+                tags,
+                note,
+                currency,
+                status: RecordStatus::Pending,
+                deleted_on: None,
             };
 
-            tokio::task::spawn_blocking(move || insert_record(&ret))
-                .await
-                .unwrap();
+            let action = Action::new(
+                MESSAGES.format("action.add", &[&id]),
+                vec![Change::insert(ret.clone())],
+            );
+            (ret, action)
+        };
+        check_record(&record)?;
 
-            self.record_manager = tokio::task::spawn_blocking(|| load_records())
-                .await
-                .unwrap();
-            true
-        }
+        let saved = tokio::task::spawn_blocking(move || commit(&action))
+            .await
+            .unwrap();
+        self.reload().await;
+        saved.map_err(|e| e.to_string())
     }
-/// Odstráni vybraný záznam z databázy a obnoví zoznam záznamov.
-    pub async fn remove_record(&mut self, selected: Record) {
-        tokio::task::spawn_blocking(move || {
-            renumber_records_db();
-            delete_record(selected);
-        })
-        .await
-        .unwrap();
-        self.record_manager = tokio::task::spawn_blocking(|| load_records())
+    /// Pridá hotové záznamy jednou akciou a obnoví zoznam záznamov.
+    ///
+    /// Záznamy dostanú nové ID v poradí, v akom prišli. Pridanie sa dá
+    /// vrátiť cez [`RecordLister::undo`].
+    ///
+    /// # Returns
+    ///
+    /// ID pridaných záznamov.
+    pub async fn add_records(&mut self, records: Vec<Record>) -> Result<Vec<i32>, HistoryError> {
+        let first = tokio::task::spawn_blocking(get_next_id).await.unwrap();
+        let records: Vec<Record> = records
+            .into_iter()
+            .zip(first..)
+            .map(|(mut record, id)| {
+                record.id = id;
+                record.deleted_on = None;
+                record
+            })
+            .collect();
+        let ids: Vec<i32> = records.iter().map(|r| r.id).collect();
+        let description = match ids.as_slice() {
+            [id] => MESSAGES.format("action.add", &[id]),
+            _ => MESSAGES.format("action.add_many", &[&ids.len()]),
+        };
+        let changes = records.into_iter().map(Change::insert).collect();
+        self.commit(Action::new(description, changes)).await?;
+        Ok(ids)
+    }
+/// Presunie vybraný záznam do koša a obnoví zoznam záznamov.
+    ///
+    /// Odstránenie sa dá vrátiť cez [`RecordLister::undo`].
+    pub async fn remove_record(&mut self, selected: Record) -> Result<(), HistoryError> {
+        let mut trashed = selected.clone();
+        trashed.deleted_on = Some(Local::now().date_naive());
+        let action = Action::new(
+            MESSAGES.format("action.delete", &[&selected.id]),
+            vec![Change::update(selected, trashed)],
+        );
+        self.commit(action).await
+    }
+    /// Obnoví záznam z koša a obnoví zoznam záznamov.
+    ///
+    /// Obnovenie sa dá vrátiť cez [`RecordLister::undo`].
+    pub async fn restore_record(&mut self, trashed: Record) -> Result<(), HistoryError> {
+        let mut restored = trashed.clone();
+        restored.deleted_on = None;
+        let action = Action::new(
+            MESSAGES.format("action.restore", &[&trashed.id]),
+            vec![Change::update(trashed, restored)],
+        );
+        self.commit(action).await
+    }
+    /// Natrvalo vymaže záznam z koša. Vymazanie sa nedá vrátiť.
+    ///
+    /// Vracia `true`, ak sa záznam vymazal.
+    pub async fn purge_record(&mut self, record_id: i32) -> bool {
+        let purged = tokio::task::spawn_blocking(move || purge_record(record_id))
             .await
             .unwrap();
+        self.reload().await;
+        purged
+    }
+    /// Nastaví stav záznamom jednou akciou a obnoví zoznam záznamov.
+    ///
+    /// # Argumenty
+    ///
+    /// * `selected` - Záznamy, ktorým sa mení stav.
+    /// * `status` - Nový stav.
+    /// * `description` - Popis akcie v histórii.
+    pub async fn set_status(
+        &mut self,
+        selected: &[Record],
+        status: RecordStatus,
+        description: String,
+    ) -> Result<(), HistoryError> {
+        let changes = selected
+            .iter()
+            .map(|r| Change::update(r.clone(), r.clone().with_status(status)))
+            .collect();
+        self.commit(Action::new(description, changes)).await
+    }
+    /// Uplatní akciu, uloží ju do histórie a obnoví zoznam záznamov.
+    async fn commit(&mut self, action: Action) -> Result<(), HistoryError> {
+        let result = tokio::task::spawn_blocking(move || commit(&action))
+            .await
+            .unwrap();
+        self.reload().await;
+        result
+    }
+    /// Vráti späť poslednú akciu a obnoví zoznam záznamov.
+    ///
+    /// Vracia popis vrátenej akcie, alebo `None`, ak nebolo čo vrátiť.
+    pub async fn undo(&mut self) -> Result<Option<String>, HistoryError> {
+        let result = tokio::task::spawn_blocking(undo).await.unwrap();
+        self.reload().await;
+        result.map(|a| a.map(|a| a.description))
+    }
+    /// Zopakuje naposledy vrátenú akciu a obnoví zoznam záznamov.
+    ///
+    /// Vracia popis zopakovanej akcie, alebo `None`, ak nebolo čo zopakovať.
+    pub async fn redo(&mut self) -> Result<Option<String>, HistoryError> {
+        let result = tokio::task::spawn_blocking(redo).await.unwrap();
+        self.reload().await;
+        result.map(|a| a.map(|a| a.description))
     }
 }
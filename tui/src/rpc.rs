@@ -0,0 +1,264 @@
+//! Ovládací socket bežiacej TUI aplikácie.
+//!
+//! Externé nástroje sa pripoja na Unix socket a posielajú požiadavky
+//! JSON-RPC 2.0, jednu na riadok. Každá požiadavka sa zmení na
+//! [`AppEvent`] v kanáli [`EventHandler`](crate::event::EventHandler),
+//! vybaví ju teda hlavný cyklus aplikácie rovnako ako stlačenú klávesu
+//! a zápisy do databázy sa nikdy neprekrývajú.
+//!
+//! Metódy:
+//! - `records.push` s parametrami `{"records": [...]}` pridá záznamy jednou
+//!   akciou histórie (dá sa vrátiť klávesou `u`), vráti `{"ids": [...]}`
+//! - `selection.get` vráti vybraný záznam alebo `null`
+//! - `filter.get` vráti aktuálny vyhľadávací dopyt
+use std::fmt;
+use std::fs::{self, DirBuilder};
+use std::io::{self, ErrorKind};
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use financial_lib::{record::Record, transfer::check_record};
+use serde_json::{Map, Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc;
+
+use crate::event::{AppEvent, Event};
+use crate::i18n::{tr, trf};
+
+/// Kód chyby pre požiadavku, ktorá nie je platný JSON.
+pub const PARSE_ERROR: i64 = -32700;
+/// Kód chyby pre JSON, ktorý nie je požiadavka JSON-RPC 2.0.
+pub const INVALID_REQUEST: i64 = -32600;
+/// Kód chyby pre neznámu metódu.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// Kód chyby pre chybné parametre metódy.
+pub const INVALID_PARAMS: i64 = -32602;
+/// Kód chyby, keď aplikácia požiadavku nevedela vykonať.
+pub const APP_ERROR: i64 = -32000;
+
+/// Kanál, ktorým aplikácia vráti výsledok požiadavky.
+pub type Reply = mpsc::UnboundedSender<Result<Value, RpcError>>;
+
+/// Chyba požiadavky v tvare objektu `error` z JSON-RPC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// Otvorený ovládací socket. Pri zahodení sa súbor socketu zmaže.
+#[derive(Debug)]
+pub struct ControlSocket {
+    pub path: PathBuf,
+}
+
+impl Drop for ControlSocket {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Cesta k socketu: `FINANCE_TUI_SOCKET`, inak `finance-tui.sock`
+/// v `XDG_RUNTIME_DIR`, inak v dočasnom adresári.
+pub fn socket_path() -> PathBuf {
+    if let Ok(path) = std::env::var("FINANCE_TUI_SOCKET") {
+        return PathBuf::from(path);
+    }
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("finance-tui.sock")
+}
+
+/// Začne počúvať na sockete a posielať požiadavky ako udalosti do `sender`.
+///
+/// Starý socket po spadnutej relácii sa nahradí, iný súbor na jeho mieste
+/// nie. Nový socket je prístupný iba vlastníkovi.
+///
+/// # Errors
+/// Vráti chybu, ak na sockete už počúva iná relácia, na jeho mieste je
+/// iný súbor alebo sa socket nedá vytvoriť.
+pub fn start(sender: mpsc::UnboundedSender<Event>) -> io::Result<ControlSocket> {
+    let path = socket_path();
+    if let Ok(metadata) = fs::symlink_metadata(&path) {
+        if !metadata.file_type().is_socket() {
+            return Err(io::Error::new(
+                ErrorKind::AlreadyExists,
+                trf("rpc.not_socket", &[&path.display()]),
+            ));
+        }
+        if std::os::unix::net::UnixStream::connect(&path).is_ok() {
+            return Err(io::Error::new(
+                ErrorKind::AddrInUse,
+                trf("rpc.in_use", &[&path.display()]),
+            ));
+        }
+        fs::remove_file(&path)?;
+    }
+    let listener = bind_private(&path)?;
+    let socket = ControlSocket { path };
+    tokio::spawn(accept(listener, sender));
+    Ok(socket)
+}
+
+/// Vytvorí socket prístupný iba vlastníkovi.
+///
+/// Socket vznikne v novom adresári s právami 0700 a až po nastavení práv
+/// sa presunie na `path`, nikto iný sa teda nestihne pripojiť.
+fn bind_private(path: &Path) -> io::Result<UnixListener> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let dir = path.with_file_name(format!(".{}.{}", name, std::process::id()));
+    DirBuilder::new().mode(0o700).create(&dir)?;
+    let temporary = dir.join("socket");
+    let result = UnixListener::bind(&temporary).and_then(|listener| {
+        fs::set_permissions(&temporary, fs::Permissions::from_mode(0o600))?;
+        fs::rename(&temporary, path)?;
+        Ok(listener)
+    });
+    let _ = fs::remove_file(&temporary);
+    let _ = fs::remove_dir(&dir);
+    result
+}
+
+/// Prijíma spojenia, kým aplikácia beží.
+async fn accept(listener: UnixListener, sender: mpsc::UnboundedSender<Event>) {
+    loop {
+        tokio::select! {
+            _ = sender.closed() => break,
+            accepted = listener.accept() => {
+                if let Ok((stream, _)) = accepted {
+                    tokio::spawn(connection(stream, sender.clone()));
+                }
+            }
+        }
+    }
+}
+
+/// Vybavuje požiadavky jedného spojenia v poradí, v akom prišli.
+async fn connection(stream: UnixStream, sender: mpsc::UnboundedSender<Event>) -> io::Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&line, &sender).await {
+            write.write_all(format!("{}\n", response).as_bytes()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Vybaví jednu požiadavku a vráti odpoveď, `None` pre notifikáciu bez `id`.
+///
+/// Platné volanie metódy sa pošle ako udalosť do `sender` a čaká sa na
+/// odpoveď aplikácie.
+pub async fn handle_line(line: &str, sender: &mpsc::UnboundedSender<Event>) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => {
+            let error = RpcError::new(PARSE_ERROR, trf("rpc.invalid_json", &[&e]));
+            return Some(response(Value::Null, Err(error)));
+        }
+    };
+    let id = request.get("id").cloned();
+    let result = match parse_request(&request) {
+        Ok((method, params)) => call(method, params, sender).await,
+        Err(e) => Err(e),
+    };
+    match id {
+        Some(id) => Some(response(id, result)),
+        None if request.is_object() && request.get("method").is_some() => None,
+        None => Some(response(Value::Null, result)),
+    }
+}
+
+/// Vytiahne z požiadavky názov metódy a parametre.
+fn parse_request(request: &Value) -> Result<(&str, &Value), RpcError> {
+    let invalid = |message: &str| RpcError::new(INVALID_REQUEST, message);
+    let request = request
+        .as_object()
+        .ok_or_else(|| invalid(tr("rpc.not_object")))?;
+    if request.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Err(invalid(tr("rpc.version")));
+    }
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid(tr("rpc.missing_method")))?;
+    Ok((method, request.get("params").unwrap_or(&Value::Null)))
+}
+
+/// Pošle požiadavku aplikácii ako udalosť a počká na výsledok.
+async fn call(
+    method: &str,
+    params: &Value,
+    sender: &mpsc::UnboundedSender<Event>,
+) -> Result<Value, RpcError> {
+    let (reply, mut result) = mpsc::unbounded_channel();
+    let event = match method {
+        "records.push" => AppEvent::RpcPushRecords(parse_records(params)?, reply),
+        "selection.get" => AppEvent::RpcSelection(reply),
+        "filter.get" => AppEvent::RpcFilter(reply),
+        _ => {
+            return Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                trf("rpc.unknown_method", &[&method]),
+            ));
+        }
+    };
+    let closed = || RpcError::new(APP_ERROR, tr("rpc.closed"));
+    sender.send(Event::App(event)).map_err(|_| closed())?;
+    result.recv().await.unwrap_or_else(|| Err(closed()))
+}
+
+/// Načíta a skontroluje záznamy z parametrov `{"records": [...]}`.
+fn parse_records(params: &Value) -> Result<Vec<Record>, RpcError> {
+    let invalid = |message: String| RpcError::new(INVALID_PARAMS, message);
+    let records = params
+        .get("records")
+        .cloned()
+        .ok_or_else(|| invalid(tr("rpc.missing_records").to_string()))?;
+    let records: Vec<Record> = serde_json::from_value(records)
+        .map_err(|e| invalid(trf("rpc.invalid_record", &[&e])))?;
+    if records.is_empty() {
+        return Err(invalid(tr("rpc.no_records").to_string()));
+    }
+    for (i, record) in records.iter().enumerate() {
+        check_record(record).map_err(|e| invalid(trf("rpc.record", &[&i, &e])))?;
+    }
+    Ok(records)
+}
+
+/// Zostaví odpoveď JSON-RPC.
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    let mut response = Map::new();
+    response.insert("jsonrpc".to_string(), json!("2.0"));
+    match result {
+        Ok(result) => response.insert("result".to_string(), result),
+        Err(e) => response.insert(
+            "error".to_string(),
+            json!({ "code": e.code, "message": e.message }),
+        ),
+    };
+    response.insert("id".to_string(), id);
+    Value::Object(response)
+}
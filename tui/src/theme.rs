@@ -0,0 +1,321 @@
+//! Farebné témy TUI.
+//!
+//! Vstavané témy sú `dark` (predvolená), `light` pre svetlé terminály
+//! a `high-contrast`. Téma sa vyberá v nastaveniach (`ui.theme`),
+//! premennou `FINANCE_THEME` alebo prepínačom `--theme`. Namiesto názvu
+//! vstavanej témy môže byť uvedená cesta k súboru `.toml` alebo názov
+//! súboru v adresári `themes` vedľa súboru nastavení
+//! (`theme = "solarized"` načíta `themes/solarized.toml`).
+//!
+//! Súbor témy prepisuje farby vybranej vstavanej témy:
+//!
+//! ```toml
+//! base = "light"                # predvolene dark
+//! focus = "blue"                # pozadie rámu vybraného widgetu
+//! selected = "#d0d0ff"          # pozadie vybraného riadku
+//!
+//! [categories]
+//! RESTAURANT = "light-red"
+//! ```
+//!
+//! Farba je názov (`red`, `light-blue`, `dark-gray`, `reset`…), číslo
+//! z palety 0–255 alebo `#rrggbb`.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use financial_lib::{config, record::ExpenseType};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::i18n::{tr, trf};
+
+/// Názvy vstavaných tém.
+pub const BUILT_IN: [&str; 3] = ["dark", "light", "high-contrast"];
+
+/// Farby, ktorými sa vykresľujú všetky widgety.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// pozadie formulárov a nápovedy
+    pub background: Color,
+    /// text nápovedy
+    pub text: Color,
+    /// pozadie rámu vybraného widgetu
+    pub focus: Color,
+    /// pozadie vybraného riadku
+    pub selected: Color,
+    /// text vybraného riadku
+    pub selected_text: Color,
+    /// práve upravované pole formulára
+    pub active: Color,
+    /// príjmy, majetok, zisky a poklesy výdavkov
+    pub positive: Color,
+    /// výdavky, záväzky, straty a chyby
+    pub negative: Color,
+    /// čistá úspora v mesačnom prehľade
+    pub accent: Color,
+    /// graf zostatku a čistej hodnoty
+    pub balance: Color,
+    /// predpoveď zostatku
+    pub forecast: Color,
+    /// pomocné čiary
+    pub muted: Color,
+    /// stĺpce kategórií v grafe výdavkov, v poradí [`ExpenseType::ALL`]
+    pub categories: [Color; 10],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    /// Tmavá téma, pôvodné farby aplikácie.
+    pub fn dark() -> Self {
+        Self {
+            background: Color::Black,
+            text: Color::White,
+            focus: Color::LightCyan,
+            selected: Color::LightMagenta,
+            selected_text: Color::Reset,
+            active: Color::Yellow,
+            positive: Color::Green,
+            negative: Color::LightRed,
+            accent: Color::Yellow,
+            balance: Color::LightYellow,
+            forecast: Color::LightCyan,
+            muted: Color::Gray,
+            categories: [
+                Color::Cyan,
+                Color::LightRed,
+                Color::Magenta,
+                Color::Blue,
+                Color::LightBlue,
+                Color::Green,
+                Color::LightMagenta,
+                Color::Red,
+                Color::Yellow,
+                Color::Gray,
+            ],
+        }
+    }
+
+    /// Téma pre svetlé terminály s tmavými farbami na svetlom pozadí.
+    pub fn light() -> Self {
+        Self {
+            background: Color::White,
+            text: Color::Black,
+            focus: Color::Blue,
+            selected: Color::Blue,
+            selected_text: Color::White,
+            active: Color::Blue,
+            positive: Color::Green,
+            negative: Color::Red,
+            accent: Color::Magenta,
+            balance: Color::Blue,
+            forecast: Color::Cyan,
+            muted: Color::DarkGray,
+            categories: [
+                Color::Cyan,
+                Color::Red,
+                Color::Magenta,
+                Color::Blue,
+                Color::Indexed(25),
+                Color::Green,
+                Color::Indexed(127),
+                Color::Indexed(88),
+                Color::Indexed(130),
+                Color::DarkGray,
+            ],
+        }
+    }
+
+    /// Téma s najvyšším kontrastom: čierne pozadie a iba jasné farby.
+    pub fn high_contrast() -> Self {
+        Self {
+            background: Color::Black,
+            text: Color::White,
+            focus: Color::Yellow,
+            selected: Color::White,
+            selected_text: Color::Black,
+            active: Color::LightYellow,
+            positive: Color::LightGreen,
+            negative: Color::LightRed,
+            accent: Color::LightYellow,
+            balance: Color::White,
+            forecast: Color::LightCyan,
+            muted: Color::White,
+            categories: [
+                Color::LightCyan,
+                Color::LightRed,
+                Color::LightMagenta,
+                Color::LightBlue,
+                Color::White,
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::Red,
+                Color::Yellow,
+                Color::Gray,
+            ],
+        }
+    }
+
+    /// Vráti vstavanú tému podľa názvu.
+    pub fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Načíta tému podľa nastavenia `ui.theme`.
+    ///
+    /// # Arguments
+    /// * `name` – názov vstavanej témy, cesta k súboru `.toml` alebo názov
+    ///   súboru v adresári `themes`
+    /// * `themes` – adresár s témami používateľa, zvyčajne [`themes_dir`]
+    ///
+    /// # Errors
+    /// Ak téma neexistuje alebo jej súbor nie je platný.
+    pub fn load(name: &str, themes: Option<&Path>) -> Result<Theme, ThemeError> {
+        if let Some(theme) = Theme::built_in(name) {
+            return Ok(theme);
+        }
+        let path = if name.ends_with(".toml") {
+            PathBuf::from(name)
+        } else {
+            themes
+                .ok_or_else(|| ThemeError::new(name, tr("theme.no_config_dir")))?
+                .join(format!("{}.toml", name))
+        };
+        if !path.exists() {
+            return Err(ThemeError::new(
+                name,
+                trf("theme.unknown", &[&BUILT_IN.join(", "), &path.display()]),
+            ));
+        }
+        Theme::from_file(&path)
+    }
+
+    /// Načíta tému zo súboru TOML.
+    ///
+    /// # Errors
+    /// Ak sa súbor nedá prečítať, obsahuje neznámy kľúč, neznámu základnú
+    /// tému alebo neplatnú farbu.
+    pub fn from_file(path: &Path) -> Result<Theme, ThemeError> {
+        let origin = path.display();
+        let error = |message: String| ThemeError::new(&origin, message);
+        let text = std::fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        let file: ThemeFile = toml::from_str(&text).map_err(|e| error(e.message().to_string()))?;
+        let base = file.base.as_deref().unwrap_or("dark");
+        let mut theme = Theme::built_in(base).ok_or_else(|| {
+            error(trf("theme.unknown_base", &[&base, &BUILT_IN.join(", ")]))
+        })?;
+        for (name, value) in &file.colors {
+            let color = parse_color(value).map_err(|e| error(format!("{}: {}", name, e)))?;
+            *theme.role(name).ok_or_else(|| error(trf("theme.unknown_color", &[name])))? = color;
+        }
+        for (code, value) in &file.categories {
+            let category = ExpenseType::from_str(code)
+                .map_err(|_| error(trf("theme.unknown_category", &[code])))?;
+            theme.categories[category as usize] =
+                parse_color(value).map_err(|e| error(format!("{}: {}", code, e)))?;
+        }
+        Ok(theme)
+    }
+
+    /// Vráti farbu podľa jej názvu v súbore témy.
+    fn role(&mut self, name: &str) -> Option<&mut Color> {
+        Some(match name {
+            "background" => &mut self.background,
+            "text" => &mut self.text,
+            "focus" => &mut self.focus,
+            "selected" => &mut self.selected,
+            "selected_text" => &mut self.selected_text,
+            "active" => &mut self.active,
+            "positive" => &mut self.positive,
+            "negative" => &mut self.negative,
+            "accent" => &mut self.accent,
+            "balance" => &mut self.balance,
+            "forecast" => &mut self.forecast,
+            "muted" => &mut self.muted,
+            _ => return None,
+        })
+    }
+
+    /// Štýl vybraného riadku zoznamu alebo tabuľky.
+    pub fn selected(&self) -> Style {
+        Style::new()
+            .bg(self.selected)
+            .fg(self.selected_text)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Štýl rámu vybraného widgetu.
+    pub fn focus(&self) -> Style {
+        Style::new().bg(self.focus)
+    }
+
+    /// Štýl pozadia formulárov a nápovedy.
+    pub fn popup(&self) -> Style {
+        Style::new().bg(self.background).fg(self.text)
+    }
+
+    /// Farba kategórie výdavku.
+    pub fn category(&self, category: ExpenseType) -> Color {
+        self.categories[category as usize]
+    }
+}
+
+/// Obsah súboru témy. Neznáme farby odmietne [`Theme::role`].
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    base: Option<String>,
+    #[serde(default)]
+    categories: BTreeMap<String, String>,
+    #[serde(flatten)]
+    colors: BTreeMap<String, String>,
+}
+
+/// Načíta farbu: názov, číslo z palety alebo `#rrggbb`.
+fn parse_color(value: &str) -> Result<Color, String> {
+    Color::from_str(value.trim()).map_err(|_| trf("theme.invalid_color", &[&value]))
+}
+
+/// Adresár `themes` vedľa súboru nastavení.
+///
+/// # Arguments
+/// * `config` – súbor nastavení zadaný prepínačom, inak [`config::config_path`]
+pub fn themes_dir(config: Option<&Path>) -> Option<PathBuf> {
+    let path = config.map(Path::to_path_buf).or_else(config::config_path)?;
+    path.parent().map(|dir| dir.join("themes"))
+}
+
+/// Chybná téma.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThemeError {
+    /// názov témy alebo cesta k jej súboru
+    pub origin: String,
+    pub message: String,
+}
+
+impl ThemeError {
+    fn new(origin: impl fmt::Display, message: impl Into<String>) -> Self {
+        Self {
+            origin: origin.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&trf("theme.error", &[&self.origin, &self.message]))
+    }
+}
+
+impl std::error::Error for ThemeError {}
@@ -0,0 +1,57 @@
+//! Modul pre obrazovku koša.
+//!
+//! Obsahuje štruktúru [`TrashView`], ktorá drží odstránené záznamy
+//! od naposledy odstráneného. Záznam sa dá obnoviť alebo natrvalo vymazať,
+//! inak sa vymaže automaticky po uplynutí lehoty.
+use financial_lib::{db::load_trash, record::Record, trash::retention_days};
+use ratatui::widgets::TableState;
+
+/// Dáta obrazovky koša.
+#[derive(Debug, Default)]
+pub struct TrashView {
+    /// Záznamy v koši od naposledy odstráneného.
+    pub records: Vec<Record>,
+    /// Koľko dní ostáva záznam v koši.
+    pub retention: u64,
+    /// Vybraný záznam v tabuľke.
+    pub state: TableState,
+}
+
+impl TrashView {
+    /// Načíta záznamy v koši z databázy.
+    pub async fn load() -> Self {
+        let (records, retention) =
+            tokio::task::spawn_blocking(|| (load_trash(), retention_days()))
+                .await
+                .unwrap();
+        let mut state = TableState::default();
+        state.select((!records.is_empty()).then_some(0));
+        TrashView {
+            records,
+            retention,
+            state,
+        }
+    }
+    /// Vráti vybraný záznam.
+    pub fn selected(&self) -> Option<&Record> {
+        self.state.selected().and_then(|i| self.records.get(i))
+    }
+    /// Posunie výber na ďalší záznam.
+    pub fn select_next(&mut self) {
+        let len = self.records.len();
+        if len == 0 {
+            return;
+        }
+        let next = self.state.selected().map_or(0, |i| (i + 1) % len);
+        self.state.select(Some(next));
+    }
+    /// Posunie výber na predchádzajúci záznam.
+    pub fn select_previous(&mut self) {
+        let len = self.records.len();
+        if len == 0 {
+            return;
+        }
+        let previous = self.state.selected().map_or(0, |i| (i + len - 1) % len);
+        self.state.select(Some(previous));
+    }
+}
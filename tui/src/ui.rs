@@ -3,48 +3,71 @@
 //! Obsahuje implementáciu metód pre [`App`] na vykreslenie:
 //! - vstupného formulára (input mode),
 //! - zoznamu záznamov,
-//! - grafov (pseudo pie chart, balance line chart),
+//! - grafov (pseudo pie chart, balance line chart, mesačný prehľad),
+//! - tabuľky porovnania výdavkov dvoch období,
+//! - obrazovky čistej hodnoty (tabuľka majetku a záväzkov, graf),
+//! - obrazovky úverov (prehľad úverov a rozdelenie splátok),
+//! - obrazovky investícií (pozície, hodnota a zisky),
+//! - obrazovky odsúhlasenia s výpisom (formulár výpisu, označovanie záznamov),
+//! - histórie vybraného záznamu z audit logu,
+//! - koša odstránených záznamov,
 //! - nápovedy (help text).
 use core::f64;
 
-use crossterm::style;
-use ratatui::layout::{Constraint, Direction, Layout};
+use chrono::NaiveDate;
+
+use ratatui::layout::{Constraint, Direction, Layout, Margin};
 use ratatui::style::{Modifier, Style};
-use ratatui::symbols::{self, block};
-use ratatui::text::Span;
-use ratatui::widgets::{Axis, BarChart, Borders, Chart, Dataset, List, ListItem, StatefulWidget};
-use ratatui::{DefaultTerminal, Frame};
+use ratatui::symbols;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
+use ratatui::widgets::{
+    Axis, Bar, BarChart, BarGroup, Borders, Cell, Chart, Dataset, List, ListItem, Row, StatefulWidget, Table,
+};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
-    style::{Color, Stylize},
     widgets::{Block, BorderType, Paragraph, Widget},
 };
 
 use crate::app::{App, FocusedWidget};
-use crate::chart_calculator;
+use crate::i18n::{tr, trf};
+use crate::keymap::Mode;
+use financial_lib::config::{date_format, date_hint};
+use financial_lib::networth::AssetKind;
+use financial_lib::report::Comparison;
+use financial_lib::trash::expires_on;
 use crate::chart_calculator::chart_calculator::{
-    data_for_time_graph, generate_x_labels, percentage_for_pie,
+    data_for_forecast, data_for_monthly_report, data_for_time_graph, generate_x_labels,
+    percentage_for_pie,
 };
 
-const SELECTED: Style = Style::new()
-    .bg(Color::LightMagenta)
-    .add_modifier(Modifier::BOLD);
+/// Šírka jedného stĺpca v mesačnom prehľade.
+const MONTH_BAR_WIDTH: u16 = 2;
+/// Medzera medzi mesiacmi v mesačnom prehľade.
+const MONTH_GAP: u16 = 2;
+/// Koľko kategórií s najväčším nárastom sa v porovnaní zvýrazní.
+const HIGHLIGHTED_INCREASES: usize = 3;
 
 impl App {
     /// Vykreslí vstupný formulár pre pridanie alebo úpravu záznamu.
     pub fn render_input_mode(&mut self, buf: &mut Buffer, area: Rect) {
         let block = Block::default()
-            .title("Add record")
+            .title(tr("form.title"))
             .borders(Borders::ALL)
-            .style(Style::default().bg(Color::Black));
+            .style(self.theme.popup());
         block.render(area, buf);
 
+        let date_title = trf("form.date", &[&date_hint(date_format())]);
         let titles = [
-            "Amount",
-            "Type(+/-)",
-            "Category(Fn, Rest, Shop, Inv, Free, Hm, Cloth, Car, Trl, Oth)",
-            "Date- dd.mm.yyyy",
+            tr("form.amount"),
+            tr("form.type"),
+            tr("form.category"),
+            &date_title,
+            tr("form.account"),
+            tr("form.tags"),
+            tr("form.note"),
+            tr("form.currency"),
         ];
 
         let chunks = Layout::default()
@@ -56,21 +79,28 @@ impl App {
         for (i, window) in chunks.iter().enumerate() {
             let buffer = &self.input_buffer[i];
 
-            let style = if i == self.input_select as usize {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .bg(Color::Black)
+            let style = if i == self.input_select {
+                self.theme
+                    .popup()
+                    .fg(self.theme.active)
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().bg(Color::Black)
+                self.theme.popup()
+            };
+
+            let title = match self.suggested_category {
+                Some(category) if i == 2 && buffer.trim() == category.code() => {
+                    trf("form.suggested", &[&titles[i]])
+                }
+                _ => titles[i].to_string(),
             };
 
             let paragraph = Paragraph::new(buffer.as_str())
                 .block(
                     Block::default()
-                        .title(titles[i])
+                        .title(title)
                         .borders(Borders::ALL)
-                        .style(Style::default().bg(Color::Black)),
+                        .style(self.theme.popup()),
                 )
                 .style(style);
 
@@ -80,20 +110,35 @@ impl App {
     }
 /// Vykreslí zoznam záznamov.
     pub fn render_records(&mut self, area: Rect, buf: &mut Buffer) {
+        let balances = self
+            .record_lister
+            .shows_balance()
+            .then_some(&self.record_lister.balances);
         let items: Vec<ListItem> = self
             .record_lister
             .record_manager
-            .format_all()
-            .into_iter()
-            .map(|r| ListItem::new(r))
+            .get_all()
+            .iter()
+            .map(|r| match balances {
+                Some(b) => {
+                    let balance = b.get(&r.id).copied().unwrap_or_default();
+                    ListItem::new(r.format_with_balance(balance))
+                }
+                None => ListItem::new(r.format_record()),
+            })
             .collect();
 
+        let title = match (&self.notice, &self.record_lister.conversion_error) {
+            (Some(notice), _) => trf("records.notice", &[notice]),
+            (None, Some(e)) => trf("records.notice", &[e]),
+            (None, None) => trf("records.title", &[&self.record_lister.converter.base]),
+        };
         let mut border = Block::bordered()
-            .title("Records")
+            .title(title)
             .title_alignment(Alignment::Center)
             .border_type(BorderType::Rounded);
         if self.focusing_widget == FocusedWidget::Records {
-            border = border.border_style(Style::new().bg(Color::LightCyan));
+            border = border.border_style(self.theme.focus());
         }
 
         if self.record_lister.state.selected().is_none()
@@ -103,7 +148,7 @@ impl App {
         }
 
         let highlight_style = if self.focusing_widget == FocusedWidget::Records {
-            SELECTED
+            self.theme.selected()
         } else {
             Style::new()
         };
@@ -115,52 +160,135 @@ impl App {
             .highlight_spacing(ratatui::widgets::HighlightSpacing::Always);
         StatefulWidget::render(record_list, area, buf, &mut self.record_lister.state);
     }
+    /// Vykreslí tabuľku porovnania výdavkov aktuálneho mesiaca s iným obdobím.
+    ///
+    /// Kategórie s najväčším nárastom sú zvýraznené, poklesy sú zelené.
+    pub fn render_comparison_table(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(report) = &self.record_lister.aggregates.comparison else {
+            return;
+        };
+        let against = match report.mode {
+            Comparison::PreviousPeriod => tr("comparison.last_month"),
+            Comparison::PreviousYear => tr("comparison.last_year"),
+        };
+        let mut border = Block::bordered()
+            .title(trf(
+                "comparison.title",
+                &[
+                    &report.current.start().format("%m/%Y"),
+                    &report.previous.start().format("%m/%Y"),
+                    &against,
+                ],
+            ))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded);
+        if self.focusing_widget == FocusedWidget::PieChart {
+            border = border.border_style(self.theme.focus());
+        }
+
+        let header = Row::new([
+            tr("column.category"),
+            tr("column.before"),
+            tr("column.now"),
+            tr("column.change"),
+            "%",
+        ])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = report.changes.iter().enumerate().map(|(i, change)| {
+            let style = if change.delta > 0.0 && i < HIGHLIGHTED_INCREASES {
+                Style::default().fg(self.theme.negative).add_modifier(Modifier::BOLD)
+            } else if change.delta < 0.0 {
+                Style::default().fg(self.theme.positive)
+            } else {
+                Style::default()
+            };
+            let percent = match change.percent {
+                Some(p) => format!("{:+.1}%", p),
+                None => tr("comparison.new").to_string(),
+            };
+            let category: &str = change.category.into();
+            Row::new([
+                Cell::from(category),
+                Cell::from(format!("{:.2}", change.previous)),
+                Cell::from(format!("{:.2}", change.current)),
+                Cell::from(format!("{:+.2}", change.delta)),
+                Cell::from(percent),
+            ])
+            .style(style)
+        });
+        let widths = [
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(8),
+        ];
+
+        Widget::render(Table::new(rows, widths).header(header).block(border), area, buf);
+    }
 /// Vykreslí pseudo pie chart kategórií výdavkov.
     pub fn render_pseudo_pie_chart(&mut self, area: Rect, buf: &mut Buffer) {
         let data = percentage_for_pie(&self.record_lister);
 
         let mut border = Block::bordered()
-            .title("Expenses")
+            .title(tr("expenses.title"))
             .title_alignment(Alignment::Center)
             .border_type(BorderType::Rounded);
 
         if self.focusing_widget == FocusedWidget::PieChart {
-            border = border.border_style(Style::new().bg(Color::LightCyan));
+            border = border.border_style(self.theme.focus());
         }
 
         let bars_count = data.len() as u16;
         let inner_width = area.width.saturating_sub(2);
 
-        let bar_width = (inner_width / bars_count).saturating_sub(1);
+        let bar_width = (inner_width / bars_count.max(1)).saturating_sub(1);
         let bar_gap = 2;
 
+        let bars: Vec<Bar> = data
+            .iter()
+            .map(|(category, value)| {
+                let color = self.theme.category(*category);
+                Bar::default()
+                    .label(Line::from(<&str>::from(*category)))
+                    .value(*value)
+                    .style(Style::default().fg(color))
+                    .value_style(
+                        Style::default()
+                            .fg(self.theme.background)
+                            .bg(color)
+                            .add_modifier(Modifier::BOLD),
+                    )
+            })
+            .collect();
         let chart = BarChart::default()
             .block(border)
-            .data(&data)
+            .data(BarGroup::default().bars(&bars))
             .bar_width(bar_width)
-            .bar_gap(bar_gap)
-            .bar_style(Style::default().fg(Color::LightRed))
-            .value_style(
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::LightRed)
-                    .add_modifier(Modifier::BOLD),
-            );
+            .bar_gap(bar_gap);
 
         chart.render(area, buf);
     }
-/// Vykreslí graf zostatku v čase.
+/// Vykreslí graf zostatku v čase, prípadne s predpoveďou po dnešku.
     pub fn render_balance_chart(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = match &self.record_lister.forecast {
+            Some(forecast) => trf(
+                "balance.forecast",
+                &[&forecast.points.len(), &format!("{:.2}", forecast.end_balance())],
+            ),
+            None => tr("balance.title").to_string(),
+        };
         let mut border = Block::bordered()
-            .title("Balance over time")
+            .title(title)
             .border_type(BorderType::Rounded);
         if self.focusing_widget == FocusedWidget::LineChart {
-            border = border.border_style(Style::new().bg(Color::LightCyan));
+            border = border.border_style(self.theme.focus());
         }
 
         let data = data_for_time_graph(&self.record_lister);
+        let forecast = data_for_forecast(&self.record_lister);
 
-        if data.is_empty() {
+        if data.is_empty() && forecast.is_empty() {
             border.render(area, buf);
             return;
         }
@@ -168,19 +296,27 @@ impl App {
             .marker(symbols::Marker::Dot)
             .style(
                 Style::default()
-                    .bg(Color::LightYellow)
+                    .bg(self.theme.balance)
                     .add_modifier(Modifier::BOLD),
             )
             .data(&data);
+        let forecast_datasets = forecast.iter().map(|segment| {
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(ratatui::widgets::GraphType::Line)
+                .style(Style::default().fg(self.theme.forecast))
+                .data(segment)
+        });
 
-        let y_min = data
-            .iter()
-            .map(|(_, y)| y.clone())
+        let all_points = || data.iter().chain(forecast.iter().flatten());
+        let y_min = all_points()
+            .map(|(_, y)| *y)
             .fold(f64::INFINITY, f64::min);
-        let y_max = data
-            .iter()
-            .map(|(_, y)| y.clone())
+        let y_max = all_points()
+            .map(|(_, y)| *y)
             .fold(f64::NEG_INFINITY, f64::max);
+        let x_min = all_points().next().unwrap().0;
+        let x_max = all_points().last().unwrap().0;
 
         let y_labels = vec![
             Span::from(format!("{:.2}", y_min)),
@@ -189,67 +325,742 @@ impl App {
         ];
 
         let min_label_width = 20;
-        let label_count = (area.width as usize / min_label_width);
+        let label_count = area.width as usize / min_label_width;
+
+        let mut x_labels = generate_x_labels(&self.record_lister, label_count);
+        if let Some(last) = self.record_lister.forecast.as_ref().and_then(|f| f.points.last()) {
+            x_labels.push(Span::from(last.date.format(date_format()).to_string()));
+        }
 
-        let x_labels = generate_x_labels(&self.record_lister, label_count);
+        let chart = Chart::new(std::iter::once(dataset).chain(forecast_datasets).collect())
+            .block(border)
+            .x_axis(Axis::default().bounds([x_min, x_max]).labels(x_labels))
+            .y_axis(Axis::default().bounds([y_min, y_max]).labels(y_labels));
 
+        chart.render(area, buf);
+    }
+    /// Vykreslí mesačný prehľad: príjmy a výdavky vedľa seba a čiaru čistých úspor.
+    pub fn render_monthly_chart(&mut self, area: Rect, buf: &mut Buffer) {
+        let title = Line::from(vec![
+            Span::from(tr("monthly.title")),
+            Span::styled(tr("monthly.income"), Style::default().fg(self.theme.positive)),
+            Span::from(tr("monthly.vs")),
+            Span::styled(tr("monthly.expense"), Style::default().fg(self.theme.negative)),
+            Span::from(", "),
+            Span::styled(tr("monthly.net"), Style::default().fg(self.theme.accent)),
+        ]);
+        let mut border = Block::bordered().title(title).border_type(BorderType::Rounded);
+        if self.focusing_widget == FocusedWidget::LineChart {
+            border = border.border_style(self.theme.focus());
+        }
+        let inner = border.inner(area);
+        border.render(area, buf);
+        if inner.height < 2 {
+            return;
+        }
+
+        let group_width = MONTH_BAR_WIDTH * 2 + MONTH_GAP;
+        let months = (inner.width / group_width).max(1) as usize;
+        let data = data_for_monthly_report(&self.record_lister, months);
+        if data.is_empty() {
+            return;
+        }
+
+        let y_max = data
+            .iter()
+            .map(|m| m.income.max(m.expense).max(m.net) as f64)
+            .fold(1.0, f64::max);
+        let y_min = data.iter().map(|m| m.net as f64).fold(0.0, f64::min);
+        let bar = MONTH_BAR_WIDTH as f64;
+        let group = group_width as f64;
+        let theme = &self.theme;
+
+        let chart_area = Rect {
+            height: inner.height - 1,
+            ..inner
+        };
+        Canvas::default()
+            .marker(symbols::Marker::HalfBlock)
+            .x_bounds([0.0, inner.width as f64])
+            .y_bounds([y_min, y_max])
+            .paint(|ctx| {
+                for (i, month) in data.iter().enumerate() {
+                    let x = i as f64 * group;
+                    for (offset, value, color) in [
+                        (0.0, month.income, theme.positive),
+                        (bar, month.expense, theme.negative),
+                    ] {
+                        for column in 0..MONTH_BAR_WIDTH {
+                            let x = x + offset + column as f64 + 0.5;
+                            ctx.draw(&CanvasLine::new(x, 0.0, x, value as f64, color));
+                        }
+                    }
+                }
+                ctx.layer();
+                if y_min < 0.0 {
+                    ctx.draw(&CanvasLine::new(
+                        0.0,
+                        0.0,
+                        inner.width as f64,
+                        0.0,
+                        theme.muted,
+                    ));
+                }
+                let points: Vec<(f64, f64)> = data
+                    .iter()
+                    .enumerate()
+                    .map(|(i, m)| (i as f64 * group + bar, m.net as f64))
+                    .collect();
+                for pair in points.windows(2) {
+                    let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+                    ctx.draw(&CanvasLine::new(x1, y1, x2, y2, theme.accent));
+                }
+                if let [(x, y)] = points.as_slice() {
+                    ctx.draw(&CanvasLine::new(*x - bar, *y, *x + bar, *y, theme.accent));
+                }
+            })
+            .render(chart_area, buf);
+
+        for (i, month) in data.iter().enumerate() {
+            buf.set_string(
+                inner.x + i as u16 * group_width,
+                inner.y + inner.height - 1,
+                month.month.format("%m/%y").to_string(),
+                Style::default(),
+            );
+        }
+    }
+    /// Vykreslí obrazovku čistej hodnoty: tabuľku majetku a záväzkov a graf.
+    pub fn render_net_worth(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(view) = &self.net_worth else {
+            return;
+        };
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(area);
+
+        let header = Row::new(["column.name", "column.kind", "column.value", "column.valued_on"].map(tr))
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let investments = Row::new([
+            Cell::from(tr("net_worth.investments")),
+            Cell::from(tr("net_worth.asset")),
+            Cell::from(format!("{:.2}", view.investments)),
+            Cell::from(tr("net_worth.today")),
+        ])
+        .style(Style::default().fg(self.theme.positive));
+        let rows = view.assets.iter().map(|asset| {
+            let (kind, style) = match asset.kind {
+                AssetKind::Asset => (tr("net_worth.asset"), Style::default().fg(self.theme.positive)),
+                AssetKind::Liability => {
+                    (tr("net_worth.liability"), Style::default().fg(self.theme.negative))
+                }
+            };
+            let (value, date) = match view.current_value(asset) {
+                Some(v) => (format!("{:.2}", v.value), v.time.format(date_format()).to_string()),
+                None => ("-".to_string(), "-".to_string()),
+            };
+            Row::new([
+                Cell::from(asset.name.clone()),
+                Cell::from(kind),
+                Cell::from(value),
+                Cell::from(date),
+            ])
+            .style(style)
+        });
+        let rows = rows.chain(std::iter::once(investments));
+        let widths = [
+            Constraint::Min(20),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(10),
+        ];
+        let table_border = Block::bordered()
+            .title(trf(
+                "net_worth.table",
+                &[&self.keymap.keys(Mode::NetWorth, "close")],
+            ))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded);
+        Widget::render(
+            Table::new(rows, widths).header(header).block(table_border),
+            split[0],
+            buf,
+        );
+
+        let title = match view.history.last() {
+            Some(last) => trf(
+                "net_worth.summary",
+                &[
+                    &format!("{:.2}", last.net),
+                    &format!("{:.2}", last.assets),
+                    &format!("{:.2}", last.liabilities),
+                ],
+            ),
+            None => tr("net_worth.title").to_string(),
+        };
+        let border = Block::bordered().title(title).border_type(BorderType::Rounded);
+        let data: Vec<(f64, f64)> = view
+            .history
+            .iter()
+            .map(|p| {
+                let x = p
+                    .date
+                    .and_hms_opt(0, 0, 0)
+                    .expect("Conversion error to NaiveDateTime")
+                    .and_utc()
+                    .timestamp() as f64;
+                (x, p.net as f64)
+            })
+            .collect();
+        let (Some(first), Some(last)) = (view.history.first(), view.history.last()) else {
+            border.render(split[1], buf);
+            return;
+        };
+
+        let y_min = data.iter().map(|(_, y)| *y).fold(f64::INFINITY, f64::min);
+        let y_max = data.iter().map(|(_, y)| *y).fold(f64::NEG_INFINITY, f64::max);
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(ratatui::widgets::GraphType::Line)
+            .style(Style::default().fg(self.theme.balance))
+            .data(&data);
         let chart = Chart::new(vec![dataset])
             .block(border)
             .x_axis(
                 Axis::default()
-                    .bounds([data.first().unwrap().0, data.last().unwrap().0])
-                    .labels(x_labels),
+                    .bounds([data[0].0, data[data.len() - 1].0])
+                    .labels(vec![
+                        Span::from(first.date.format(date_format()).to_string()),
+                        Span::from(last.date.format(date_format()).to_string()),
+                    ]),
             )
-            .y_axis(Axis::default().bounds([y_min, y_max]).labels(y_labels));
+            .y_axis(Axis::default().bounds([y_min, y_max]).labels(vec![
+                Span::from(format!("{:.2}", y_min)),
+                Span::from(format!("{:.2}", y_max)),
+            ]));
+        chart.render(split[1], buf);
+    }
+    /// Vykreslí obrazovku úverov: prehľad úverov a splátky vybraného úveru.
+    pub fn render_loans(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(view) = &mut self.loans else {
+            return;
+        };
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(area);
 
-        chart.render(area, buf);
+        let header = Row::new(
+            [
+                "column.name",
+                "column.rate",
+                "column.payment",
+                "column.remaining",
+                "column.interest_paid",
+                "column.payoff",
+            ]
+            .map(tr),
+        )
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = view.loans.iter().map(|(loan, status)| {
+            let payoff = match status.payoff_date {
+                Some(d) => d.format(date_format()).to_string(),
+                None => tr("loans.never").to_string(),
+            };
+            Row::new([
+                Cell::from(loan.name.clone()),
+                Cell::from(format!("{:.2}%", loan.annual_rate)),
+                Cell::from(format!("{:.2}", loan.payment())),
+                Cell::from(format!("{:.2}", status.remaining)),
+                Cell::from(format!("{:.2}", status.interest_paid)),
+                Cell::from(payoff),
+            ])
+        });
+        let widths = [
+            Constraint::Min(16),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(13),
+            Constraint::Length(10),
+        ];
+        let title = match &view.conversion_error {
+            Some(e) => trf("loans.notice", &[e]),
+            None => trf(
+                "loans.title",
+                &[
+                    &self.keymap.keys(Mode::Loans, "previous"),
+                    &self.keymap.keys(Mode::Loans, "next"),
+                    &self.keymap.keys(Mode::Loans, "close"),
+                ],
+            ),
+        };
+        let border = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded);
+        StatefulWidget::render(
+            Table::new(rows, widths)
+                .header(header)
+                .block(border)
+                .row_highlight_style(self.theme.selected()),
+            split[0],
+            buf,
+            &mut view.state,
+        );
+
+        let border = Block::bordered()
+            .title(tr("loans.payments"))
+            .border_type(BorderType::Rounded);
+        let Some((_, status)) = view.selected() else {
+            border.render(split[1], buf);
+            return;
+        };
+        let header = Row::new(
+            [
+                "column.date",
+                "column.payment",
+                "column.principal",
+                "column.interest",
+                "column.balance",
+            ]
+            .map(tr),
+        )
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        // najnovšie splátky navrchu
+        let rows = status.payments.iter().rev().map(|p| {
+            Row::new([
+                Cell::from(p.date.format(date_format()).to_string()),
+                Cell::from(format!("{:.2}", p.payment)),
+                Cell::from(format!("{:.2}", p.principal)),
+                Cell::from(format!("{:.2}", p.interest)),
+                Cell::from(format!("{:.2}", p.balance)),
+            ])
+        });
+        let widths = [Constraint::Length(12); 5];
+        Widget::render(Table::new(rows, widths).header(header).block(border), split[1], buf);
+    }
+    /// Vykreslí históriu záznamu: kto, kedy a ako ho zmenil.
+    pub fn render_audit(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(view) = &self.audit else {
+            return;
+        };
+        let header = Row::new(["column.time", "column.actor", "column.change", "column.fields"].map(tr))
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows = view.entries.iter().map(|e| {
+            let lines: Vec<Line> = e
+                .changed_fields()
+                .into_iter()
+                .map(|f| Line::from(format!("{}: {} -> {}", f.field, f.before, f.after)))
+                .collect();
+            let height = lines.len().max(1) as u16;
+            Row::new([
+                Cell::from(e.time.format(&format!("{} %H:%M:%S", date_format())).to_string()),
+                Cell::from(e.actor.clone()),
+                Cell::from(tr(&format!("operation.{}", e.operation.code()))),
+                Cell::from(lines),
+            ])
+            .height(height)
+        });
+        let widths = [
+            Constraint::Length(19),
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Min(20),
+        ];
+        let border = Block::bordered()
+            .title(trf(
+                "history.title",
+                &[&view.record_id, &self.keymap.keys(Mode::History, "close")],
+            ))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded);
+        Widget::render(Table::new(rows, widths).header(header).block(border), area, buf);
+    }
+    /// Vykreslí kôš: odstránené záznamy s dátumom odstránenia a vymazania.
+    pub fn render_trash(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(view) = &mut self.trash else {
+            return;
+        };
+        let date = |d: Option<NaiveDate>| {
+            d.map_or(String::new(), |d| d.format(date_format()).to_string())
+        };
+        let header = Row::new(
+            [
+                "column.id",
+                "column.date",
+                "column.amount",
+                "column.category",
+                "column.account",
+                "column.deleted",
+                "column.purged_after",
+                "column.note",
+            ]
+            .map(tr),
+        )
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows: Vec<Row> = view
+            .records
+            .iter()
+            .map(|r| {
+                Row::new([
+                    Cell::from(r.id.to_string()),
+                    Cell::from(r.time.format(date_format()).to_string()),
+                    Cell::from(format!("{:.2} {}", r.signed_amount(), r.currency)),
+                    Cell::from(r.expense.map_or(String::new(), |e| e.code().to_string())),
+                    Cell::from(r.account.clone().unwrap_or_default()),
+                    Cell::from(date(r.deleted_on)),
+                    Cell::from(date(expires_on(r, view.retention))),
+                    Cell::from(r.note.clone().unwrap_or_default()),
+                ])
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(10),
+            Constraint::Length(14),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Min(10),
+        ];
+        let border = Block::bordered()
+            .title(trf(
+                "trash.title",
+                &[
+                    &view.retention,
+                    &self.keymap.keys(Mode::Trash, "restore"),
+                    &self.keymap.keys(Mode::Trash, "purge"),
+                    &self.keymap.keys(Mode::Trash, "close"),
+                ],
+            ))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded);
+        StatefulWidget::render(
+            Table::new(rows, widths)
+                .header(header)
+                .block(border)
+                .row_highlight_style(self.theme.selected()),
+            area,
+            buf,
+            &mut view.state,
+        );
+    }
+    /// Vykreslí obrazovku investícií: pozície s hodnotou, nákladmi a ziskami.
+    pub fn render_investments(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(view) = &self.investments else {
+            return;
+        };
+        let money = |value: Option<f32>| value.map_or("-".to_string(), |v| format!("{:.2}", v));
+        let gain_style = |value: Option<f32>| match value {
+            Some(v) if v < 0.0 => Style::default().fg(self.theme.negative),
+            Some(v) if v > 0.0 => Style::default().fg(self.theme.positive),
+            _ => Style::default(),
+        };
+
+        let header = Row::new(
+            [
+                "column.symbol",
+                "column.quantity",
+                "column.cost_basis",
+                "column.price",
+                "column.priced_on",
+                "column.market_value",
+                "column.unrealised",
+                "column.realised",
+            ]
+            .map(tr),
+        )
+        .style(Style::default().add_modifier(Modifier::BOLD));
+        let mut rows: Vec<Row> = view
+            .holdings
+            .iter()
+            .map(|h| {
+                let unrealised = h.unrealised_gain();
+                Row::new([
+                    Cell::from(h.symbol.clone()),
+                    Cell::from(format!("{:.4}", h.quantity())),
+                    Cell::from(format!("{:.2}", h.cost_basis())),
+                    Cell::from(money(h.price.as_ref().map(|p| p.price))),
+                    Cell::from(h.price.as_ref().map_or("-".to_string(), |p| {
+                        p.time.format(date_format()).to_string()
+                    })),
+                    Cell::from(money(h.market_value())),
+                    Cell::from(money(unrealised)).style(gain_style(unrealised)),
+                    Cell::from(money(Some(h.realised_gain))).style(gain_style(Some(h.realised_gain))),
+                ])
+            })
+            .collect();
+
+        let cost_basis: f32 = view.holdings.iter().map(|h| h.cost_basis()).sum();
+        let market_value: f32 = view.holdings.iter().filter_map(|h| h.market_value()).sum();
+        let unrealised: f32 = view.holdings.iter().filter_map(|h| h.unrealised_gain()).sum();
+        let realised: f32 = view.holdings.iter().map(|h| h.realised_gain).sum();
+        rows.push(
+            Row::new([
+                Cell::from(tr("investments.total")),
+                Cell::from(""),
+                Cell::from(format!("{:.2}", cost_basis)),
+                Cell::from(""),
+                Cell::from(""),
+                Cell::from(format!("{:.2}", market_value)),
+                Cell::from(format!("{:.2}", unrealised)).style(gain_style(Some(unrealised))),
+                Cell::from(format!("{:.2}", realised)).style(gain_style(Some(realised))),
+            ])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+        );
+
+        let widths = [
+            Constraint::Min(10),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(13),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ];
+        let border = Block::bordered()
+            .title(trf(
+                "investments.title",
+                &[&self.keymap.keys(Mode::Investments, "close")],
+            ))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded);
+        Widget::render(Table::new(rows, widths).header(header).block(border), area, buf);
+    }
+    /// Vykreslí obrazovku odsúhlasenia.
+    ///
+    /// Kým nie je zadaný výpis, zobrazí sa jeho formulár. Potom sa zobrazí
+    /// súhrn s priebežným rozdielom a tabuľka záznamov na označenie.
+    pub fn render_reconcile(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(view) = &mut self.reconcile else {
+            return;
+        };
+        let error = view
+            .error
+            .as_ref()
+            .map(|e| Line::styled(e.clone(), Style::default().fg(self.theme.negative)));
+
+        let Some(reconciliation) = &view.reconciliation else {
+            let date_title = trf("reconcile.end_date", &[&date_hint(date_format())]);
+            let titles = [
+                &date_title,
+                tr("reconcile.balance"),
+                tr("reconcile.account"),
+            ];
+            let border = Block::bordered()
+                .title(trf(
+                    "reconcile.form_title",
+                    &[
+                        &self.keymap.keys(Mode::ReconcileForm, "next_field"),
+                        &self.keymap.keys(Mode::ReconcileForm, "start"),
+                        &self.keymap.keys(Mode::ReconcileForm, "close"),
+                    ],
+                ))
+                .title_alignment(Alignment::Center)
+                .border_type(BorderType::Rounded);
+            let inner = border.inner(area);
+            border.render(area, buf);
+            let mut constraints = vec![Constraint::Length(3); titles.len()];
+            constraints.push(Constraint::Min(0));
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(constraints)
+                .split(inner);
+            for (i, title) in titles.iter().enumerate() {
+                let style = if i == view.input_select {
+                    Style::default()
+                        .fg(self.theme.active)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                Paragraph::new(view.inputs[i].as_str())
+                    .block(Block::bordered().title(*title))
+                    .style(style)
+                    .render(chunks[i], buf);
+            }
+            if let Some(error) = error {
+                Paragraph::new(error).render(chunks[titles.len()], buf);
+            }
+            return;
+        };
+
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .split(area);
+
+        let difference = reconciliation.difference(&view.records);
+        let difference_style = if reconciliation.is_balanced(&view.records) {
+            Style::default().fg(self.theme.positive).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.theme.negative).add_modifier(Modifier::BOLD)
+        };
+        let mut lines = vec![
+            Line::from(trf(
+                "reconcile.summary",
+                &[
+                    &reconciliation.end.format(date_format()),
+                    &reconciliation
+                        .account
+                        .as_deref()
+                        .unwrap_or(tr("reconcile.all_accounts")),
+                    &format!("{:.2}", reconciliation.statement_balance),
+                    &format!("{:.2}", reconciliation.opening_balance(&view.records)),
+                    &format!("{:.2}", reconciliation.cleared_balance(&view.records)),
+                ],
+            )),
+            Line::from(vec![
+                Span::raw(tr("reconcile.difference")),
+                Span::styled(format!("{:.2}", difference), difference_style),
+            ]),
+        ];
+        lines.extend(error);
+        let border = Block::bordered()
+            .title(trf(
+                "reconcile.title",
+                &[
+                    &self.keymap.keys(Mode::Reconcile, "tick"),
+                    &self.keymap.keys(Mode::Reconcile, "reconcile"),
+                    &self.keymap.keys(Mode::Reconcile, "close"),
+                ],
+            ))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded);
+        Paragraph::new(lines).block(border).render(split[0], buf);
+
+        let header = Row::new([
+            "",
+            tr("column.id"),
+            tr("column.date"),
+            tr("column.amount"),
+            tr("column.account"),
+            tr("column.status"),
+            tr("column.note"),
+        ])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+        let rows: Vec<Row> = reconciliation
+            .candidates(&view.records)
+            .into_iter()
+            .map(|r| {
+                let ticked = if reconciliation.selected.contains(&r.id) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                Row::new([
+                    Cell::from(ticked),
+                    Cell::from(r.id.to_string()),
+                    Cell::from(r.time.format(date_format()).to_string()),
+                    Cell::from(format!("{:.2} {}", r.signed_amount(), r.currency)),
+                    Cell::from(r.account.clone().unwrap_or_default()),
+                    Cell::from(r.status.name()),
+                    Cell::from(r.note.clone().unwrap_or_default()),
+                ])
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Length(4),
+            Constraint::Length(10),
+            Constraint::Length(14),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Min(10),
+        ];
+        let border = Block::bordered()
+            .title(tr("reconcile.records"))
+            .border_type(BorderType::Rounded);
+        StatefulWidget::render(
+            Table::new(rows, widths)
+                .header(header)
+                .block(border)
+                .row_highlight_style(self.theme.selected()),
+            split[1],
+            buf,
+            &mut view.state,
+        );
+    }
+    /// Vykreslí vyhľadávací riadok s dopytom a prípadnou chybou.
+    ///
+    /// Pri chybe sa pod dopyt vykreslí šípka na pozíciu chyby a jej popis.
+    pub fn render_search_bar(&mut self, area: Rect, buf: &mut Buffer) {
+        let mut border = Block::bordered()
+            .title(tr("search.title"))
+            .border_type(BorderType::Rounded);
+        if self.search_mode {
+            border = border.border_style(self.theme.focus());
+        }
+
+        let cursor = if self.search_mode { "_" } else { "" };
+        let mut lines = vec![Line::from(format!("/{}{}", self.search_input, cursor))];
+        if let Some(error) = &self.search_error {
+            lines.push(Line::styled(
+                format!("{}^ {}", " ".repeat(error.position + 1), error.message),
+                Style::default().fg(self.theme.negative),
+            ));
+        }
+
+        Paragraph::new(lines).block(border).render(area, buf);
     }
     /// Vykreslí nápovedu s popisom klávesových skratiek.
+    ///
+    /// Nápoveda sa skladá z aktívnych skratiek, zmeny v nastaveniach sa v nej prejavia.
     pub fn render_help_text(&mut self, area: Rect, buf: &mut Buffer) {
         let block = Block::default()
-            .title("Help")
+            .title(tr("help.title"))
             .borders(Borders::ALL)
-            .style(Style::default().bg(Color::Black).fg(Color::White));
+            .style(self.theme.popup());
 
         block.render(area, buf);
 
-        let help_text = vec![
-            
-            "KEYS:",
-            "",
-            "q          - Quit",
-            "Tab        - Next widget",
-            "Shift+Tab  - Previous widget",
-            "",
-            "Records:",
-            "Up / Down  - Select record",    
-            "Delete     - Remove record",
-            "a          - Add record",
-            "Enter      - Edit record",
-            "",
-            "Input mode:                            Categories: ",
-            "Enter      - Confirm                        FUN, RESTAURANT,",
-            "Esc        - Cancel                         SHOPPING, INVESTMENT,",
-            "Tab        - Next input                     FREETIME, HOME, CLOTH,",
-            "Shift+Tab  - Previous input                 CAR,TRAVEL, OTHER, NONE",
-            "",
-            "Help mode:",
-            "h          - Toggle help",
-            "q          - Quit help mode",
-        ]
-        .join("\n");
-
-        let paragraph = Paragraph::new(help_text)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .style(Style::default().bg(Color::Black)),
-            )
-            .style(Style::default().bg(Color::Black).fg(Color::White))
-            .alignment(Alignment::Left)
-            .wrap(ratatui::widgets::Wrap { trim: false });
-
-        paragraph.render(area, buf);
+        let inner = area.inner(Margin::new(1, 1));
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Percentage(32),
+                Constraint::Percentage(28),
+            ])
+            .split(inner);
+        let sections: [&[Mode]; 3] = [
+            &[Mode::Normal],
+            &[Mode::Input, Mode::Search, Mode::Help, Mode::ReconcileForm, Mode::Reconcile],
+            &[Mode::NetWorth, Mode::Loans, Mode::Investments, Mode::History, Mode::Trash],
+        ];
+        for (column, modes) in columns.iter().zip(sections) {
+            let mut lines = Vec::new();
+            for mode in modes {
+                lines.push(format!("{}:", mode.title()));
+                let help = self.keymap.help(*mode);
+                let width = help.iter().map(|(keys, _)| keys.len()).max().unwrap_or(0);
+                for (keys, description) in help {
+                    lines.push(format!("{:<width$} - {}", keys, description));
+                }
+                if *mode == Mode::Search {
+                    let example = tr("help.example");
+                    let indent = " ".repeat(example.chars().count());
+                    lines.push(format!("{} cat:RESTAURANT amount>20 -tag:work", example));
+                    lines.push(format!("{} date:2026-09..2026-10 status:pending", indent));
+                }
+                lines.push(String::new());
+            }
+            if modes.contains(&Mode::Normal) {
+                lines.push(tr("help.categories").to_string());
+                lines.push("FUN, RESTAURANT, SHOPPING, INVESTMENT,".to_string());
+                lines.push("FREETIME, HOME, CLOTH, CAR, TRAVEL,".to_string());
+                lines.push("OTHER, NONE".to_string());
+            }
+            Paragraph::new(lines.join("\n"))
+                .style(self.theme.popup())
+                .alignment(Alignment::Left)
+                .wrap(ratatui::widgets::Wrap { trim: false })
+                .render(*column, buf);
+        }
     }
 }
 
@@ -269,19 +1080,53 @@ impl Widget for &mut App {
             .split(main_split[0]);
 
         if self.input_mode || self.update_mode {
+            let height = (self.input_buffer.len() as u16 * 3 + 2)
+                .max(area.height / 2)
+                .min(area.height);
             let pop_up = ratatui::layout::Rect {
                 x: area.x + area.width / 4,
-                y: area.y + area.height / 4,
+                y: area.y + (area.height - height) / 2,
                 width: area.width / 2,
-                height: area.height / 2,
+                height,
             };
             self.render_input_mode(buf, pop_up);
         } else if self.help_show {
             self.render_help_text(area, buf);
+        } else if self.net_worth.is_some() {
+            self.render_net_worth(area, buf);
+        } else if self.loans.is_some() {
+            self.render_loans(area, buf);
+        } else if self.investments.is_some() {
+            self.render_investments(area, buf);
+        } else if self.reconcile.is_some() {
+            self.render_reconcile(area, buf);
+        } else if self.audit.is_some() {
+            self.render_audit(area, buf);
+        } else if self.trash.is_some() {
+            self.render_trash(area, buf);
         } else {
-            self.render_records(top_split[0], buf);
-            self.render_balance_chart(main_split[1], buf);
-            self.render_pseudo_pie_chart(top_split[1], buf);
+            let records_area = if self.search_mode || !self.search_input.is_empty() {
+                let bar_height = if self.search_error.is_some() { 4 } else { 3 };
+                let records_split = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(bar_height), Constraint::Min(0)])
+                    .split(top_split[0]);
+                self.render_search_bar(records_split[0], buf);
+                records_split[1]
+            } else {
+                top_split[0]
+            };
+            self.render_records(records_area, buf);
+            if self.monthly_view {
+                self.render_monthly_chart(main_split[1], buf);
+            } else {
+                self.render_balance_chart(main_split[1], buf);
+            }
+            if self.record_lister.comparison.is_some() {
+                self.render_comparison_table(top_split[1], buf);
+            } else {
+                self.render_pseudo_pie_chart(top_split[1], buf);
+            }
         }
     }
 }
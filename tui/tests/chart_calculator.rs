@@ -0,0 +1,46 @@
+//! Testy dát pre grafy nad dočasnou databázou.
+use std::collections::BTreeMap;
+
+use chrono::{Days, NaiveDate};
+use financial_lib::config::{self, Config, DatabaseConfig};
+use financial_lib::forecast::{Forecast, ForecastPoint};
+use tempfile::TempDir;
+use tui::chart_calculator::chart_calculator::data_for_forecast;
+use tui::record_list::RecordLister;
+
+fn forecast(days: u64) -> Forecast {
+    let start = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+    Forecast {
+        points: (0..days)
+            .map(|day| ForecastPoint {
+                date: start + Days::new(day),
+                balance: day as f32,
+            })
+            .collect(),
+        start_balance: 0.0,
+        recurring: vec![],
+        daily_spending: BTreeMap::new(),
+    }
+}
+
+#[test]
+fn forecast_line_ends_on_the_last_day() {
+    let dir = TempDir::new().expect("temporary directory");
+    config::install(Config {
+        database: DatabaseConfig {
+            path: Some(dir.path().join("test.db")),
+        },
+        ..Config::default()
+    });
+    let mut lister = RecordLister::new();
+
+    for days in [2, 3, 7, 8, 81] {
+        lister.forecast = Some(forecast(days));
+        let segments = data_for_forecast(&lister);
+        let end = segments.last().unwrap()[1];
+        assert_eq!(end.1, (days - 1) as f64, "{} days", days);
+        assert!(segments.windows(2).all(|pair| pair[0][1].0 < pair[1][0].0), "{} days", days);
+    }
+    lister.forecast = Some(forecast(1));
+    assert!(data_for_forecast(&lister).is_empty());
+}
@@ -0,0 +1,53 @@
+//! Testy, že každý text TUI, ktorý sa používa, má anglický preklad.
+use std::fs;
+use std::path::Path;
+
+use financial_lib::audit::Operation;
+use financial_lib::i18n::{Language, MESSAGES, source_keys};
+use tui::i18n::TEXTS;
+use tui::keymap::Mode;
+
+#[test]
+fn every_used_key_is_in_english_table() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut keys = source_keys(&src, &["tr(", "trf("]);
+    // popisy príkazov sa prekladajú až pri zobrazení
+    let keymap = src.join("keymap.rs");
+    let source = fs::read_to_string(&keymap).unwrap();
+    keys.extend(
+        source
+            .split('"')
+            .filter(|s| s.starts_with("keys.") && !s.contains('{'))
+            .map(|key| (keymap.clone(), key.to_string())),
+    );
+    for (path, key) in &keys {
+        assert!(TEXTS.has(Language::En, key), "{}: missing text '{}'", path.display(), key);
+    }
+    // texty knižnice, napr. popisy akcií zdieľané s CLI a serverom
+    for (path, key) in source_keys(&src, &["MESSAGES.format("]) {
+        assert!(
+            MESSAGES.has(Language::En, &key),
+            "{}: missing library text '{}'",
+            path.display(),
+            key
+        );
+    }
+    assert!(keys.len() > 100, "only {} keys found", keys.len());
+}
+
+#[test]
+fn operation_and_mode_names_are_in_every_language() {
+    let operations = [Operation::Insert, Operation::Update, Operation::Delete]
+        .map(|o| format!("operation.{}", o.code()));
+    let modes = Mode::ALL.map(|m| format!("mode.{}", m.name()));
+    for key in operations.iter().chain(&modes) {
+        for language in Language::ALL {
+            assert!(TEXTS.has(language, key), "missing {} text '{}'", language, key);
+        }
+    }
+}
+
+#[test]
+fn every_translation_has_english_text() {
+    assert_eq!(TEXTS.inconsistencies(), Vec::<String>::new());
+}
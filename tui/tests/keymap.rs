@@ -0,0 +1,76 @@
+//! Testy kontroly klávesových skratiek zo súboru nastavení.
+//!
+//! Chybné skratky sa odmietnu pri spustení skôr, než sa prepne terminál,
+//! preto sa dajú overiť spustením binárky a kontrolou chybového výstupu.
+use std::fs;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+/// Spustí TUI so sekciou `[keys]` v súbore nastavení a vráti chybový výstup.
+fn start_with_keys(keys: &str) -> String {
+    let dir = TempDir::new().expect("temporary directory");
+    let config = dir.path().join("config.toml");
+    fs::write(&config, keys).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_tui"))
+        .arg("--config")
+        .arg(&config)
+        .arg("--db")
+        .arg(dir.path().join("test.db"))
+        .env("RUST_BACKTRACE", "0")
+        .env("RUST_LIB_BACKTRACE", "0")
+        .env_remove("FINANCE_LANGUAGE")
+        .output()
+        .expect("tui runs");
+    assert!(!output.status.success());
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn key_bound_twice_in_one_mode_is_rejected() {
+    let stderr = start_with_keys("[keys.normal]\nhelp = [\"q\"]\n");
+    assert!(
+        stderr.contains("keys.normal: 'q' is bound to both quit and help"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn unknown_command_is_rejected() {
+    let stderr = start_with_keys("[keys.search]\njump = [\"ctrl+j\"]\n");
+    assert!(stderr.contains("keys.search: unknown command 'jump'"), "{}", stderr);
+}
+
+#[test]
+fn unknown_mode_is_rejected() {
+    let stderr = start_with_keys("[keys.editor]\nquit = [\"q\"]\n");
+    assert!(stderr.contains("keys: unknown mode 'editor'"), "{}", stderr);
+}
+
+#[test]
+fn plain_character_in_text_mode_is_rejected() {
+    let stderr = start_with_keys("[keys.input]\ncancel = [\"x\"]\n");
+    assert!(
+        stderr.contains("keys.input: cancel: 'x' is needed for typing"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn invalid_key_is_rejected() {
+    let stderr = start_with_keys("[keys.normal]\nquit = [\"hyper+q\"]\n");
+    assert!(
+        stderr.contains("keys.normal: quit: invalid key 'hyper+q'"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn error_is_in_configured_language() {
+    let stderr =
+        start_with_keys("[defaults]\nlanguage = \"sk\"\n\n[keys.search]\njump = [\"ctrl+j\"]\n");
+    assert!(stderr.contains("keys.search: neznámy príkaz 'jump'"), "{}", stderr);
+}
@@ -0,0 +1,50 @@
+//! Testy formulára na pridanie záznamu nad dočasnou databázou.
+use financial_lib::config::{self, Config, DatabaseConfig};
+use financial_lib::db::load_records;
+use financial_lib::record::ExpenseType;
+use tempfile::TempDir;
+use tui::record_list::RecordLister;
+
+/// Vyplní formulár v poradí `[amount, money_type, expense, time, account, tags, note, currency]`.
+fn form(amount: &str, expense: &str, currency: &str) -> Vec<String> {
+    [amount, "-", expense, "01.09.2026", "", "", "", currency]
+        .map(str::to_string)
+        .to_vec()
+}
+
+#[tokio::test]
+async fn invalid_input_is_reported_and_not_saved() {
+    let dir = TempDir::new().expect("temporary directory");
+    config::install(Config {
+        database: DatabaseConfig {
+            path: Some(dir.path().join("test.db")),
+        },
+        ..Config::default()
+    });
+    let mut lister = RecordLister::new();
+
+    for (input, message) in [
+        (form("10", "FUN", "EURO"), "invalid currency 'EURO'"),
+        (form("10", "FUN", "12"), "invalid currency '12'"),
+        (form("-10", "FUN", "EUR"), "invalid amount '-10'"),
+        (form("inf", "FUN", "EUR"), "invalid amount 'inf'"),
+        (form("10", "FOOD", "EUR"), "unknown category 'FOOD'"),
+        (form("ten", "FUN", "EUR"), "invalid record"),
+    ] {
+        let error = lister
+            .add_record_from_input_or_update(input.clone(), -1)
+            .await
+            .unwrap_err();
+        assert!(error.starts_with(message), "{:?}: {}", input, error);
+    }
+    assert!(load_records().get_all().is_empty());
+
+    lister
+        .add_record_from_input_or_update(form("10", "CAR", "usd"), -1)
+        .await
+        .unwrap();
+    let records = load_records().get_all();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].expense, Some(ExpenseType::CAR));
+    assert_eq!(records[0].currency, "USD");
+}
@@ -0,0 +1,129 @@
+//! Testy protokolu JSON-RPC ovládacieho socketu.
+//!
+//! Požiadavky sa vybavujú cez [`rpc::handle_line`] bez terminálu, úlohu
+//! aplikácie hrá test, ktorý číta udalosti z kanála.
+use std::fs;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::sync::Once;
+
+use financial_lib::config::{self, Config};
+use serde_json::{Value, json};
+use tempfile::TempDir;
+use tokio::sync::mpsc;
+use tui::event::{AppEvent, Event};
+use tui::rpc::{self, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR};
+
+/// Nastaví predvolené nastavenia, aby sa texty chýb nebrali zo súboru používateľa.
+fn setup() {
+    static SETUP: Once = Once::new();
+    SETUP.call_once(|| config::install(Config::default()));
+}
+
+/// Vybaví požiadavku, na ktorú sa aplikácia nemá pýtať.
+async fn handle(line: &str) -> Option<Value> {
+    setup();
+    let (sender, mut events) = mpsc::unbounded_channel();
+    let response = rpc::handle_line(line, &sender).await;
+    assert!(events.try_recv().is_err(), "request reached the application");
+    response
+}
+
+fn error_code(response: &Value) -> i64 {
+    response["error"]["code"].as_i64().expect("error response")
+}
+
+#[tokio::test]
+async fn invalid_json_is_parse_error() {
+    let response = handle("{\"jsonrpc\": ").await.unwrap();
+    assert_eq!(error_code(&response), PARSE_ERROR);
+    assert_eq!(response["id"], Value::Null);
+}
+
+#[tokio::test]
+async fn request_without_jsonrpc_version_is_invalid() {
+    let response = handle(r#"{"method": "filter.get", "id": 1}"#).await.unwrap();
+    assert_eq!(error_code(&response), INVALID_REQUEST);
+    assert_eq!(response["id"], 1);
+
+    let response = handle("[1, 2]").await.unwrap();
+    assert_eq!(error_code(&response), INVALID_REQUEST);
+}
+
+#[tokio::test]
+async fn unknown_method_is_reported() {
+    let response = handle(r#"{"jsonrpc": "2.0", "method": "records.drop", "id": "a"}"#)
+        .await
+        .unwrap();
+    assert_eq!(error_code(&response), METHOD_NOT_FOUND);
+    assert_eq!(response["id"], "a");
+}
+
+#[tokio::test]
+async fn notification_gets_no_response() {
+    assert_eq!(handle(r#"{"jsonrpc": "2.0", "method": "records.drop"}"#).await, None);
+}
+
+#[tokio::test]
+async fn invalid_records_are_rejected_before_the_application() {
+    for params in [
+        json!({}),
+        json!({ "records": [] }),
+        json!({ "records": [{ "amount": 5.0 }] }),
+        json!({ "records": [{
+            "money_type": "EXPENSE", "amount": -5.0, "time": "2026-03-01"
+        }] }),
+    ] {
+        let request = json!({ "jsonrpc": "2.0", "method": "records.push", "params": params, "id": 7 });
+        let response = handle(&request.to_string()).await.unwrap();
+        assert_eq!(error_code(&response), INVALID_PARAMS, "{}", params);
+    }
+}
+
+#[tokio::test]
+async fn valid_push_is_answered_by_the_application() {
+    setup();
+    let (sender, mut events) = mpsc::unbounded_channel();
+    let request = json!({
+        "jsonrpc": "2.0",
+        "method": "records.push",
+        "params": { "records": [{ "money_type": "EXPENSE", "amount": 5.0, "time": "2026-03-01" }] },
+        "id": 3,
+    });
+    let app = tokio::spawn(async move {
+        match events.recv().await {
+            Some(Event::App(AppEvent::RpcPushRecords(records, reply))) => {
+                assert_eq!(records.len(), 1);
+                reply.send(Ok(json!({ "ids": [42] }))).unwrap();
+            }
+            _ => panic!("expected records.push event"),
+        }
+    });
+
+    let response = rpc::handle_line(&request.to_string(), &sender).await.unwrap();
+    app.await.unwrap();
+    assert_eq!(response, json!({ "jsonrpc": "2.0", "result": { "ids": [42] }, "id": 3 }));
+}
+
+#[tokio::test]
+async fn socket_is_private_and_replaces_only_sockets() {
+    let dir = TempDir::new().expect("temporary directory");
+    let path = dir.path().join("tui.sock");
+    // jediný test, ktorý premennú číta
+    unsafe { std::env::set_var("FINANCE_TUI_SOCKET", &path) };
+
+    fs::write(&path, "data").unwrap();
+    let (sender, _events) = mpsc::unbounded_channel();
+    assert!(rpc::start(sender.clone()).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "data");
+    fs::remove_file(&path).unwrap();
+
+    let socket = rpc::start(sender.clone()).unwrap();
+    let metadata = fs::symlink_metadata(&path).unwrap();
+    assert!(metadata.file_type().is_socket());
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    assert!(rpc::start(sender).is_err(), "socket of a running session was replaced");
+
+    drop(socket);
+    assert!(!path.exists());
+}
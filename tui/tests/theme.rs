@@ -0,0 +1,112 @@
+//! Testy načítania farebných tém zo súborov.
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Once;
+
+use financial_lib::config::{self, Config};
+use ratatui::style::Color;
+use tempfile::TempDir;
+use tui::theme::Theme;
+
+/// Zapíše tému `name` do adresára tém a vráti tento adresár.
+///
+/// Nastaví aj predvolené nastavenia, aby chyby boli v angličtine.
+fn themes(name: &str, text: &str) -> TempDir {
+    static SETUP: Once = Once::new();
+    SETUP.call_once(|| config::install(Config::default()));
+    let dir = TempDir::new().expect("temporary directory");
+    fs::write(dir.path().join(format!("{}.toml", name)), text).unwrap();
+    dir
+}
+
+/// Načíta tému zo súboru s jedinou farbou `focus`.
+fn focus(value: &str) -> Result<Color, String> {
+    let dir = themes("test", &format!("focus = \"{}\"\n", value));
+    Theme::load("test", Some(dir.path()))
+        .map(|theme| theme.focus)
+        .map_err(|e| e.message)
+}
+
+#[test]
+fn colours_are_names_indexes_or_rgb() {
+    assert_eq!(focus("light-blue"), Ok(Color::LightBlue));
+    assert_eq!(focus("dark-gray"), Ok(Color::DarkGray));
+    assert_eq!(focus("reset"), Ok(Color::Reset));
+    assert_eq!(focus("0"), Ok(Color::Indexed(0)));
+    assert_eq!(focus("255"), Ok(Color::Indexed(255)));
+    assert_eq!(focus("#d0d0ff"), Ok(Color::Rgb(0xd0, 0xd0, 0xff)));
+}
+
+#[test]
+fn invalid_colours_are_rejected() {
+    for value in ["ultraviolet", "256", "#d0d0f", "#gggggg", ""] {
+        let error = focus(value).unwrap_err();
+        assert!(error.starts_with("focus: "), "{}: {}", value, error);
+        assert!(error.contains(&format!("'{}'", value)), "{}: {}", value, error);
+    }
+}
+
+#[test]
+fn file_overrides_its_base_theme() {
+    let dir = themes(
+        "paper",
+        "base = \"light\"\nselected = \"#d0d0ff\"\n\n[categories]\nRESTAURANT = \"light-red\"\n",
+    );
+    let theme = Theme::load("paper", Some(dir.path())).unwrap();
+    let light = Theme::light();
+    assert_eq!(theme.selected, Color::Rgb(0xd0, 0xd0, 0xff));
+    assert_eq!(theme.categories[1], Color::LightRed);
+    assert_eq!(theme.text, light.text);
+    assert_eq!(theme.categories[0], light.categories[0]);
+
+    let dir = themes("plain", "muted = \"red\"\n");
+    let theme = Theme::load("plain", Some(dir.path())).unwrap();
+    assert_eq!(theme, Theme { muted: Color::Red, ..Theme::dark() });
+}
+
+#[test]
+fn unknown_base_colour_or_category_is_rejected() {
+    for (text, expected) in [
+        ("base = \"sepia\"\n", "sepia"),
+        ("shadow = \"red\"\n", "shadow"),
+        ("[categories]\nPETS = \"red\"\n", "PETS"),
+    ] {
+        let dir = themes("bad", text);
+        let error = Theme::load("bad", Some(dir.path())).unwrap_err();
+        assert!(error.message.contains(expected), "{}", error);
+    }
+}
+
+#[test]
+fn themes_are_found_by_name_or_path() {
+    let dir = themes("solarized", "focus = \"blue\"\n");
+    let file = dir.path().join("solarized.toml");
+    assert_eq!(Theme::load("solarized", Some(dir.path())).unwrap().focus, Color::Blue);
+    assert_eq!(Theme::load(file.to_str().unwrap(), None).unwrap().focus, Color::Blue);
+
+    assert_eq!(Theme::load("light", Some(Path::new("/nonexistent"))), Ok(Theme::light()));
+    assert!(Theme::load("missing", Some(dir.path())).is_err());
+    assert!(Theme::load("solarized", None).is_err());
+}
+
+#[test]
+fn invalid_theme_stops_the_start() {
+    let dir = TempDir::new().expect("temporary directory");
+    fs::create_dir(dir.path().join("themes")).unwrap();
+    fs::write(dir.path().join("themes").join("bad.toml"), "focus = \"ultraviolet\"\n").unwrap();
+    fs::write(dir.path().join("config.toml"), "[ui]\ntheme = \"bad\"\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_tui"))
+        .arg("--config")
+        .arg(dir.path().join("config.toml"))
+        .arg("--db")
+        .arg(dir.path().join("test.db"))
+        .env("RUST_BACKTRACE", "0")
+        .env_remove("FINANCE_THEME")
+        .env_remove("FINANCE_LANGUAGE")
+        .output()
+        .expect("tui runs");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("focus: invalid color 'ultraviolet'"), "{}", stderr);
+}